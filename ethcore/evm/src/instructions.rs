@@ -209,6 +209,7 @@ lazy_static! {
 		arr[GASPRICE as usize] =		InstructionInfo::new("GASPRICE",		0, 1, GasPriceTier::Base);
 		arr[EXTCODESIZE as usize] = 	InstructionInfo::new("EXTCODESIZE",		1, 1, GasPriceTier::Special);
 		arr[EXTCODECOPY as usize] = 	InstructionInfo::new("EXTCODECOPY",		4, 0, GasPriceTier::Special);
+		arr[EXTCODEHASH as usize] = 	InstructionInfo::new("EXTCODEHASH",		1, 1, GasPriceTier::Special);
 		arr[BLOCKHASH as usize] =		InstructionInfo::new("BLOCKHASH",		1, 1, GasPriceTier::Ext);
 		arr[COINBASE as usize] =		InstructionInfo::new("COINBASE",		0, 1, GasPriceTier::Base);
 		arr[TIMESTAMP as usize] =		InstructionInfo::new("TIMESTAMP",		0, 1, GasPriceTier::Base);
@@ -397,6 +398,8 @@ pub const EXTCODECOPY: Instruction = 0x3c;
 pub const RETURNDATASIZE: Instruction = 0x3d;
 /// copy return data buffer to memory
 pub const RETURNDATACOPY: Instruction = 0x3e;
+/// get external code hash (from another contract)
+pub const EXTCODEHASH: Instruction = 0x3f;
 
 /// get hash of most recent complete block
 pub const BLOCKHASH: Instruction = 0x40;
@@ -165,6 +165,55 @@ fn test_extcodecopy(factory: super::Factory) {
 	assert_store(&ext, 0, "6005600055000000000000000000000000000000000000000000000000000000");
 }
 
+evm_test!{test_extcodehash: test_extcodehash_int}
+fn test_extcodehash(factory: super::Factory) {
+		// 33 - sender
+		// 3f - extcodehash
+		// 60 00 - push 0
+		// 55 - sstore
+
+	let address = Address::from_str("0f572e5295c57f15886f9b263e2f6d2d6c7b5ec6").unwrap();
+	let sender = Address::from_str("cd1722f2947def4cf144679da39c4c32bdc35681").unwrap();
+	let code = "333f60005500".from_hex().unwrap();
+	let sender_code = "6005600055".from_hex().unwrap();
+
+	let mut params = ActionParams::default();
+	params.address = address.clone();
+	params.sender = sender.clone();
+	params.gas = U256::from(100_000);
+	params.code = Some(Arc::new(code));
+	let mut ext = FakeExt::new_constantinople();
+	ext.codes.insert(sender, Arc::new(sender_code));
+
+	let gas_left = {
+		let mut vm = factory.create(&params.gas);
+		test_finalize(vm.exec(params, &mut ext)).unwrap()
+	};
+
+	assert_eq!(gas_left, U256::from(79_595));
+	assert_store(&ext, 0, "ffa3059b9f97094288461a40a0a9fb69492fa436dd1cfa108f04ae159f23aafe");
+}
+
+evm_test!{test_extcodehash_disabled: test_extcodehash_disabled_int}
+fn test_extcodehash_disabled(factory: super::Factory) {
+	let code = "333f60005500".from_hex().unwrap();
+
+	let mut params = ActionParams::default();
+	params.gas = U256::from(100_000);
+	params.code = Some(Arc::new(code));
+	let mut ext = FakeExt::new_byzantium();
+
+	let err = {
+		let mut vm = factory.create(&params.gas);
+		test_finalize(vm.exec(params, &mut ext)).unwrap_err()
+	};
+
+	match err {
+		vm::Error::BadInstruction { instruction: 0x3f } => {},
+		_ => panic!("Expected bad instruction error, got {:?}", err),
+	}
+}
+
 evm_test!{test_log_empty: test_log_empty_int}
 fn test_log_empty(factory: super::Factory) {
 	let address = Address::from_str("0f572e5295c57f15886f9b263e2f6d2d6c7b5ec6").unwrap();
@@ -716,7 +765,7 @@ fn test_jumps(factory: super::Factory) {
 		test_finalize(vm.exec(params, &mut ext)).unwrap()
 	};
 
-	assert_eq!(ext.sstore_clears, 1);
+	assert_eq!(ext.sstore_clears, ext.schedule.sstore_refund_gas as i128);
 	assert_store(&ext, 0, "0000000000000000000000000000000000000000000000000000000000000000"); // 5!
 	assert_store(&ext, 1, "0000000000000000000000000000000000000000000000000000000000000078"); // 5!
 	assert_eq!(gas_left, U256::from(54_117));
@@ -1027,6 +1076,33 @@ fn test_sar(factory: super::Factory) {
 		"0000000000000000000000000000000000000000000000000000000000000000");
 }
 
+evm_test!{test_sstore_eip1283: test_sstore_eip1283_int}
+fn test_sstore_eip1283(factory: super::Factory) {
+	// (code, gas used, refund) for an originally empty storage slot
+	let cases = [
+		("60006000556000600055", 412, 0),
+		("60006000556001600055", 20212, 0),
+		("60016000556000600055", 20212, 19800),
+		("60016000556002600055", 20212, 0),
+		("60016000556001600055", 20212, 0),
+	];
+
+	for &(code, used, refund) in cases.iter() {
+		let mut params = ActionParams::default();
+		params.gas = U256::from(100_000);
+		params.code = Some(Arc::new(code.from_hex().unwrap()));
+		let mut ext = FakeExt::new_constantinople();
+
+		let gas_left = {
+			let mut vm = factory.create(&params.gas);
+			test_finalize(vm.exec(params, &mut ext)).unwrap()
+		};
+
+		assert_eq!(U256::from(100_000) - gas_left, U256::from(used), "gas used by {}", code);
+		assert_eq!(ext.sstore_clears, refund, "refund of {}", code);
+	}
+}
+
 fn push_two_pop_one_constantinople_test(factory: &super::Factory, opcode: u8, push1: &str, push2: &str, result: &str) {
 	let mut push1 = push1.from_hex().unwrap();
 	let mut push2 = push2.from_hex().unwrap();
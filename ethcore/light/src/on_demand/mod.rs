@@ -219,9 +219,15 @@ fn guess_capabilities(requests: &[CheckedRequest]) -> Capabilities {
 			CheckedRequest::Code(ref req, _) => if let Ok(ref hdr) = req.header.as_ref() {
 				update_since(&mut caps.serve_state_since, hdr.number());
 			},
+			CheckedRequest::Storage(ref req, _) => if let Ok(ref hdr) = req.header.as_ref() {
+				update_since(&mut caps.serve_state_since, hdr.number());
+			},
 			CheckedRequest::Execution(ref req, _) => if let Ok(ref hdr) = req.header.as_ref() {
 				update_since(&mut caps.serve_state_since, hdr.number());
 			},
+			CheckedRequest::ExecutionProof(ref req, _) => if let Ok(ref hdr) = req.header.as_ref() {
+				update_since(&mut caps.serve_state_since, hdr.number());
+			},
 		}
 	}
 
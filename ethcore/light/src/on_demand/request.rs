@@ -19,8 +19,10 @@
 use std::sync::Arc;
 
 use ethcore::basic_account::BasicAccount;
+use ethcore::client::CallAnalytics;
 use ethcore::encoded;
 use ethcore::engines::{EthEngine, StateDependentProof};
+use ethcore::executive::TransactOptions;
 use ethcore::machine::EthereumMachine;
 use ethcore::receipt::Receipt;
 use ethcore::state::{self, ProvedExecution};
@@ -58,8 +60,12 @@ pub enum Request {
 	Account(Account),
 	/// A request for a contract's code.
 	Code(Code),
+	/// A request for a storage slot of an account.
+	Storage(Storage),
 	/// A request for proof of execution.
 	Execution(TransactionProof),
+	/// A request for the state items proving execution.
+	ExecutionProof(ExecutionProof),
 	/// A request for epoch change signal.
 	Signal(Signal),
 }
@@ -142,7 +148,9 @@ impl_single!(Receipts, BlockReceipts, Vec<Receipt>);
 impl_single!(Body, Body, encoded::Block);
 impl_single!(Account, Account, Option<BasicAccount>);
 impl_single!(Code, Code, Bytes);
+impl_single!(Storage, Storage, H256);
 impl_single!(Execution, TransactionProof, super::ExecutionResult);
+impl_single!(ExecutionProof, ExecutionProof, Vec<DBValue>);
 impl_single!(Signal, Signal, Vec<u8>);
 
 macro_rules! impl_args {
@@ -252,7 +260,9 @@ pub enum CheckedRequest {
 	Body(Body, net_request::IncompleteBodyRequest),
 	Account(Account, net_request::IncompleteAccountRequest),
 	Code(Code, net_request::IncompleteCodeRequest),
+	Storage(Storage, net_request::IncompleteStorageRequest),
 	Execution(TransactionProof, net_request::IncompleteExecutionRequest),
+	ExecutionProof(ExecutionProof, net_request::IncompleteExecutionRequest),
 	Signal(Signal, net_request::IncompleteSignalRequest)
 }
 
@@ -306,6 +316,14 @@ impl From<Request> for CheckedRequest {
 				};
 				CheckedRequest::Code(req, net_req)
 			}
+			Request::Storage(req) => {
+				let net_req = net_request::IncompleteStorageRequest {
+					block_hash: req.header.field(),
+					address_hash: ::hash::keccak(&req.address).into(),
+					key_hash: ::hash::keccak(&req.key).into(),
+				};
+				CheckedRequest::Storage(req, net_req)
+			}
 			Request::Execution(req) => {
 				let net_req = net_request::IncompleteExecutionRequest {
 					block_hash: req.header.field(),
@@ -318,6 +336,18 @@ impl From<Request> for CheckedRequest {
 				};
 				CheckedRequest::Execution(req, net_req)
 			}
+			Request::ExecutionProof(req) => {
+				let net_req = net_request::IncompleteExecutionRequest {
+					block_hash: req.header.field(),
+					from: req.tx.sender(),
+					gas: req.tx.gas,
+					gas_price: req.tx.gas_price,
+					action: req.tx.action.clone(),
+					value: req.tx.value,
+					data: req.tx.data.clone(),
+				};
+				CheckedRequest::ExecutionProof(req, net_req)
+			}
 			Request::Signal(req) => {
 				let net_req = net_request::IncompleteSignalRequest {
 					block_hash: req.hash.into(),
@@ -341,7 +371,9 @@ impl CheckedRequest {
 			CheckedRequest::Body(_, req) => NetRequest::Body(req),
 			CheckedRequest::Account(_, req) => NetRequest::Account(req),
 			CheckedRequest::Code(_, req) => NetRequest::Code(req),
+			CheckedRequest::Storage(_, req) => NetRequest::Storage(req),
 			CheckedRequest::Execution(_, req) => NetRequest::Execution(req),
+			CheckedRequest::ExecutionProof(_, req) => NetRequest::Execution(req),
 			CheckedRequest::Signal(_, req) => NetRequest::Signal(req),
 		}
 	}
@@ -356,7 +388,9 @@ impl CheckedRequest {
 			CheckedRequest::Body(ref x, _) => x.0.needs_header(),
 			CheckedRequest::Account(ref x, _) => x.header.needs_header(),
 			CheckedRequest::Code(ref x, _) => x.header.needs_header(),
+			CheckedRequest::Storage(ref x, _) => x.header.needs_header(),
 			CheckedRequest::Execution(ref x, _) => x.header.needs_header(),
+			CheckedRequest::ExecutionProof(ref x, _) => x.header.needs_header(),
 			_ => None,
 		}
 	}
@@ -370,7 +404,9 @@ impl CheckedRequest {
 			CheckedRequest::Body(ref mut x, _) => x.0 = HeaderRef::Stored(header),
 			CheckedRequest::Account(ref mut x, _) => x.header = HeaderRef::Stored(header),
 			CheckedRequest::Code(ref mut x, _) => x.header = HeaderRef::Stored(header),
+			CheckedRequest::Storage(ref mut x, _) => x.header = HeaderRef::Stored(header),
 			CheckedRequest::Execution(ref mut x, _) => x.header = HeaderRef::Stored(header),
+			CheckedRequest::ExecutionProof(ref mut x, _) => x.header = HeaderRef::Stored(header),
 			_ => {},
 		}
 	}
@@ -464,7 +500,9 @@ macro_rules! match_me {
 			CheckedRequest::Body($check, $req) => $e,
 			CheckedRequest::Account($check, $req) => $e,
 			CheckedRequest::Code($check, $req) => $e,
+			CheckedRequest::Storage($check, $req) => $e,
 			CheckedRequest::Execution($check, $req) => $e,
+			CheckedRequest::ExecutionProof($check, $req) => $e,
 			CheckedRequest::Signal($check, $req) => $e,
 		}
 	}
@@ -493,7 +531,17 @@ impl IncompleteRequest for CheckedRequest {
 			CheckedRequest::Body(_, ref req) => req.check_outputs(f),
 			CheckedRequest::Account(_, ref req) => req.check_outputs(f),
 			CheckedRequest::Code(_, ref req) => req.check_outputs(f),
+			CheckedRequest::Storage(ref check, ref req) => {
+				req.check_outputs(&mut f)?;
+
+				// make sure the storage root is definitively a hash.
+				match check.storage_root {
+					Field::BackReference(r, idx) => f(r, idx, OutputKind::Hash),
+					_ => Ok(()),
+				}
+			}
 			CheckedRequest::Execution(_, ref req) => req.check_outputs(f),
+			CheckedRequest::ExecutionProof(_, ref req) => req.check_outputs(f),
 			CheckedRequest::Signal(_, ref req) => req.check_outputs(f),
 		}
 	}
@@ -503,6 +551,15 @@ impl IncompleteRequest for CheckedRequest {
 	}
 
 	fn fill<F>(&mut self, f: F) where F: Fn(usize, usize) -> Result<Output, net_request::NoSuchOutput> {
+		// the storage root is only known to the checker, not the network request.
+		if let CheckedRequest::Storage(ref mut check, _) = *self {
+			if let Field::BackReference(req, idx) = check.storage_root {
+				if let Ok(Output::Hash(root)) = f(req, idx) {
+					check.storage_root = Field::Scalar(root);
+				}
+			}
+		}
+
 		match_me!(*self, (_, ref mut req) => req.fill(f))
 	}
 
@@ -515,13 +572,21 @@ impl IncompleteRequest for CheckedRequest {
 			CheckedRequest::Body(_, req) => req.complete().map(CompleteRequest::Body),
 			CheckedRequest::Account(_, req) => req.complete().map(CompleteRequest::Account),
 			CheckedRequest::Code(_, req) => req.complete().map(CompleteRequest::Code),
+			CheckedRequest::Storage(_, req) => req.complete().map(CompleteRequest::Storage),
 			CheckedRequest::Execution(_, req) => req.complete().map(CompleteRequest::Execution),
+			CheckedRequest::ExecutionProof(_, req) => req.complete().map(CompleteRequest::Execution),
 			CheckedRequest::Signal(_, req) => req.complete().map(CompleteRequest::Signal),
 		}
 	}
 
 
-	fn adjust_refs<F>(&mut self, mapping: F) where F: FnMut(usize) -> usize {
+	fn adjust_refs<F>(&mut self, mut mapping: F) where F: FnMut(usize) -> usize {
+		if let CheckedRequest::Storage(ref mut check, _) = *self {
+			if let Field::BackReference(ref mut req_idx, _) = check.storage_root {
+				*req_idx = mapping(*req_idx);
+			}
+		}
+
 		match_me!(*self, (_, ref mut req) => req.adjust_refs(mapping))
 	}
 }
@@ -568,9 +633,15 @@ impl net_request::CheckedRequest for CheckedRequest {
 			CheckedRequest::Code(ref prover, _) =>
 				expect!((&NetResponse::Code(ref res), &CompleteRequest::Code(ref req)) =>
 					prover.check_response(cache, &req.code_hash, &res.code).map(Response::Code)),
+			CheckedRequest::Storage(ref prover, _) =>
+				expect!((&NetResponse::Storage(ref res), _) =>
+					prover.check_response(cache, &res.proof).map(Response::Storage)),
 			CheckedRequest::Execution(ref prover, _) =>
 				expect!((&NetResponse::Execution(ref res), _) =>
 					prover.check_response(cache, &res.items).map(Response::Execution)),
+			CheckedRequest::ExecutionProof(ref prover, _) =>
+				expect!((&NetResponse::Execution(ref res), _) =>
+					prover.check_response(cache, &res.items).map(Response::ExecutionProof)),
 			CheckedRequest::Signal(ref prover, _) =>
 				expect!((&NetResponse::Signal(ref res), _) =>
 					prover.check_response(cache, &res.signal).map(Response::Signal)),
@@ -597,8 +668,13 @@ pub enum Response {
 	Account(Option<BasicAccount>),
 	/// Response to a request for code.
 	Code(Vec<u8>),
+	/// Response to a request for a storage slot.
+	Storage(H256),
 	/// Response to a request for proved execution.
 	Execution(super::ExecutionResult),
+	/// Response to a request for the state items proving execution.
+	/// These are only checked once executed against the state root.
+	ExecutionProof(Vec<DBValue>),
 	/// Response to a request for epoch change signal.
 	Signal(Vec<u8>),
 }
@@ -615,6 +691,7 @@ impl net_request::ResponseLike for Response {
 				f(0, Output::Hash(acc.code_hash));
 				f(1, Output::Hash(acc.storage_root));
 			}
+			Response::Storage(ref value) => f(0, Output::Hash(*value)),
 			_ => {}
 		}
 	}
@@ -635,6 +712,8 @@ pub enum Error {
 	HeaderByNumber,
 	/// Unresolved header reference.
 	UnresolvedHeader(usize),
+	/// Unresolved storage root reference.
+	UnresolvedStorageRoot(usize),
 	/// Wrong header number.
 	WrongNumber(u64, u64),
 	/// Wrong hash.
@@ -870,6 +949,40 @@ impl Code {
 	}
 }
 
+/// Request for a storage slot of an account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Storage {
+	/// Header reference.
+	pub header: HeaderRef,
+	/// Address of the account owning the storage.
+	pub address: Address,
+	/// Storage key requested.
+	pub key: H256,
+	/// Account's storage root, typically a back-reference to an `Account` request.
+	pub storage_root: Field<H256>,
+}
+
+impl Storage {
+	/// Check a response with a storage proof against the account's storage root.
+	pub fn check_response(&self, _: &Mutex<::cache::Cache>, proof: &[Bytes]) -> Result<H256, Error> {
+		let storage_root = match self.storage_root {
+			Field::Scalar(ref root) => *root,
+			Field::BackReference(idx, _) => return Err(Error::UnresolvedStorageRoot(idx)),
+		};
+
+		let mut db = MemoryDB::new();
+		for node in proof { db.insert(&node[..]); }
+
+		match TrieDB::new(&db, &storage_root).and_then(|t| t.get(&keccak(&self.key)))? {
+			Some(val) => {
+				let value: U256 = ::rlp::decode(&val)?;
+				Ok(value.into())
+			}
+			None => Ok(H256::new()),
+		}
+	}
+}
+
 /// Request for transaction execution, along with the parts necessary to verify the proof.
 #[derive(Clone)]
 pub struct TransactionProof {
@@ -882,23 +995,32 @@ pub struct TransactionProof {
 	pub env_info: EnvInfo,
 	/// Consensus engine.
 	pub engine: Arc<EthEngine>,
+	/// Tracing to perform while re-executing the proved transaction locally.
+	/// State diffing is not supported.
+	pub analytics: CallAnalytics,
 }
 
 impl TransactionProof {
 	/// Check the proof, returning the proved execution or indicate that the proof was bad.
 	pub fn check_response(&self, _: &Mutex<::cache::Cache>, state_items: &[DBValue]) -> Result<super::ExecutionResult, Error> {
+		self.check_with_preceding(state_items, &[])
+	}
+
+	/// Check the proof after executing the given preceding transactions of the block,
+	/// returning the proved execution or indicate that the proof was bad.
+	pub fn check_with_preceding(&self, state_items: &[DBValue], preceding: &[SignedTransaction]) -> Result<super::ExecutionResult, Error> {
 		let root = self.header.as_ref()?.state_root();
 
 		let mut env_info = self.env_info.clone();
 		env_info.gas_limit = self.tx.gas.clone();
 
-		let proved_execution = state::check_proof(
-			state_items,
-			root,
-			&self.tx,
-			self.engine.machine(),
-			&self.env_info,
-		);
+		let machine = self.engine.machine();
+		let proved_execution = match (self.analytics.transaction_tracing, self.analytics.vm_tracing) {
+			(true, true) => state::check_proof_of_sequence(state_items, root, preceding, &self.tx, machine, &self.env_info, TransactOptions::with_tracing_and_vm_tracing()),
+			(true, false) => state::check_proof_of_sequence(state_items, root, preceding, &self.tx, machine, &self.env_info, TransactOptions::with_tracing()),
+			(false, true) => state::check_proof_of_sequence(state_items, root, preceding, &self.tx, machine, &self.env_info, TransactOptions::with_vm_tracing()),
+			(false, false) => state::check_proof_of_sequence(state_items, root, preceding, &self.tx, machine, &self.env_info, TransactOptions::with_no_tracing()),
+		};
 
 		match proved_execution {
 			ProvedExecution::BadProof => Err(Error::BadProof),
@@ -908,6 +1030,25 @@ impl TransactionProof {
 	}
 }
 
+/// Request for the state items proving execution of a transaction, for checking
+/// together with the proofs of other transactions of the same block.
+#[derive(Clone)]
+pub struct ExecutionProof {
+	/// The transaction to request proof of.
+	pub tx: SignedTransaction,
+	/// Block header.
+	pub header: HeaderRef,
+}
+
+impl ExecutionProof {
+	/// Return the state items. They can't be checked in isolation: a bad proof is only
+	/// detected when executing against the state root of the header.
+	pub fn check_response(&self, _: &Mutex<::cache::Cache>, state_items: &[DBValue]) -> Result<Vec<DBValue>, Error> {
+		self.header.as_ref()?;
+		Ok(state_items.to_vec())
+	}
+}
+
 /// Request for epoch signal.
 /// Provide engine and state-dependent proof checker.
 #[derive(Clone)]
@@ -1081,6 +1222,45 @@ mod tests {
 		assert!(req.check_response(&cache, &proof[..]).is_ok());
 	}
 
+	#[test]
+	fn check_storage_proof() {
+		let mut root = H256::default();
+		let mut db = MemoryDB::new();
+		let key = H256::random();
+		let value = H256::from(&U256::from(0xdeadbeefu64));
+		{
+			let mut trie = SecTrieDBMut::new(&mut db, &mut root);
+			for _ in 0..100 {
+				trie.insert(&*H256::random(), &::rlp::encode(&U256::from(1u64))).unwrap();
+			}
+
+			trie.insert(&*key, &::rlp::encode(&U256::from(&*value))).unwrap();
+		}
+
+		let proof = {
+			let trie = SecTrieDB::new(&db, &root).unwrap();
+			let mut recorder = Recorder::new();
+
+			trie.get_with(&*key, &mut recorder).unwrap().unwrap();
+
+			recorder.drain().into_iter().map(|r| r.data).collect::<Vec<_>>()
+		};
+
+		let header = Header::new();
+		let req = Storage {
+			header: encoded::Header::new(::rlp::encode(&header).into_vec()).into(),
+			address: Address::random(),
+			key: key,
+			storage_root: root.into(),
+		};
+
+		let cache = Mutex::new(make_cache());
+		assert_eq!(req.check_response(&cache, &proof[..]), Ok(value));
+
+		let unresolved = Storage { storage_root: Field::back_ref(0, 1), ..req };
+		assert_eq!(unresolved.check_response(&cache, &proof[..]), Err(Error::UnresolvedStorageRoot(0)));
+	}
+
 	#[test]
 	fn check_code() {
 		let code = vec![1u8; 256];
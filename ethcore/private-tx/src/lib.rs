@@ -252,6 +252,7 @@ impl Provider where {
 			engine,
 			Some(&*self.accounts),
 			refuse_service_transactions,
+			None,
 		)
 	}
 
@@ -61,12 +61,14 @@ impl Default for VerificationStore {
 					max_count: MAX_QUEUE_LEN,
 					max_per_sender: MAX_QUEUE_LEN / 10,
 					max_mem_usage: 8 * 1024 * 1024,
+					replace_bump_percent: None,
 				},
 				pool::verifier::Options {
 					// TODO [ToDr] This should probably be based on some real values?
 					minimal_gas_price: 0.into(),
 					block_gas_limit: 8_000_000.into(),
 					tx_gas_limit: U256::max_value(),
+					max_nonce_gap: None,
 				},
 				pool::PrioritizationStrategy::GasPriceOnly,
 			)
@@ -138,6 +138,11 @@ impl ExecutedBlock {
 		&mut self.state
 	}
 
+	/// Get mutable access to the header.
+	pub fn header_mut(&mut self) -> &mut Header {
+		&mut self.header
+	}
+
 	/// Get mutable reference to traces.
 	pub fn traces_mut(&mut self) -> &mut Tracing {
 		&mut self.traces
@@ -363,6 +368,39 @@ impl<'x> OpenBlock<'x> {
 		Ok(self.block.receipts.last().expect("receipt just pushed; qed"))
 	}
 
+	/// Push a bundle of transactions into the block, either all of them or none.
+	///
+	/// Transactions are executed in order. Returns `Ok(false)` if any of them was executed
+	/// but did not succeed. In that case, as well as on error, the block is left as it was
+	/// before the bundle.
+	///
+	/// Bundles are refused before `eip658Transition`: without a status code in the receipts
+	/// a failed transaction can't be told apart from a successful one.
+	pub fn push_bundle(&mut self, transactions: Vec<SignedTransaction>) -> Result<bool, Error> {
+		if self.block.header.number() < self.engine.params().eip658_transition {
+			return Err("Transaction bundles require status code receipts (EIP-658).".into());
+		}
+
+		let backup = self.block.clone();
+
+		for t in transactions {
+			let succeeded = match self.push_transaction(t, None) {
+				Ok(receipt) => receipt.outcome != TransactionOutcome::StatusCode(0),
+				Err(e) => {
+					self.block = backup;
+					return Err(e);
+				},
+			};
+
+			if !succeeded {
+				self.block = backup;
+				return Ok(false);
+			}
+		}
+
+		Ok(true)
+	}
+
 	/// Push transactions onto the block.
 	#[cfg(not(feature = "slow-blocks"))]
 	fn push_transactions(&mut self, transactions: Vec<SignedTransaction>) -> Result<(), Error> {
@@ -398,7 +436,6 @@ impl<'x> OpenBlock<'x> {
 		self.block.header.set_difficulty(*header.difficulty());
 		self.block.header.set_gas_limit(*header.gas_limit());
 		self.block.header.set_timestamp(header.timestamp());
-		self.block.header.set_author(*header.author());
 		self.block.header.set_uncles_hash(*header.uncles_hash());
 		self.block.header.set_transactions_root(*header.transactions_root());
 		// TODO: that's horrible. set only for backwards compatibility
@@ -548,14 +585,15 @@ impl LockedBlock {
 	/// Provide a valid seal in order to turn this into a `SealedBlock`.
 	///
 	/// NOTE: This does not check the validity of `seal` with the engine.
-	pub fn seal(self, engine: &EthEngine, seal: Vec<Bytes>) -> Result<SealedBlock, BlockError> {
+	pub fn seal(self, engine: &EthEngine, seal: Vec<Bytes>) -> Result<SealedBlock, Error> {
 		let expected_seal_fields = engine.seal_fields(self.header());
 		let mut s = self;
 		if seal.len() != expected_seal_fields {
 			return Err(BlockError::InvalidSealArity(
-				Mismatch { expected: expected_seal_fields, found: seal.len() }));
+				Mismatch { expected: expected_seal_fields, found: seal.len() }).into());
 		}
 		s.block.header.set_seal(seal);
+		engine.on_seal_block(&mut s.block)?;
 		s.block.header.compute_hash();
 		Ok(SealedBlock { block: s.block, uncle_bytes: s.uncle_bytes })
 	}
@@ -570,6 +608,9 @@ impl LockedBlock {
 	) -> Result<SealedBlock, (Error, LockedBlock)> {
 		let mut s = self;
 		s.block.header.set_seal(seal);
+		if let Err(e) = engine.on_seal_block(&mut s.block) {
+			return Err((e, s));
+		}
 		s.block.header.compute_hash();
 
 		// TODO: passing state context to avoid engines owning it?
@@ -638,7 +679,9 @@ fn enact(
 		db,
 		parent,
 		last_hashes,
-		Address::new(),
+		// the author used for execution may differ from the header's author,
+		// e.g. Clique recovers it from the signature in the extra data.
+		engine.executive_author(&header)?,
 		(3141562.into(), 31415620.into()),
 		vec![],
 		is_epoch_begin,
@@ -734,7 +777,7 @@ mod tests {
 			db,
 			parent,
 			last_hashes,
-			Address::new(),
+			engine.executive_author(&header)?,
 			(3141562.into(), 31415620.into()),
 			vec![],
 			false,
@@ -130,6 +130,11 @@ pub trait ChainNotify : Send + Sync {
 		// does nothing by default
 	}
 
+	/// fires when blocks of the canonical chain are finalized by the engine, oldest first.
+	fn finalized_blocks(&self, _finalized: Vec<H256>) {
+		// does nothing by default
+	}
+
 	/// fires when chain achieves active mode
 	fn start(&self) {
 		// does nothing by default
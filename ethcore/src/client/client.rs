@@ -69,11 +69,12 @@ use spec::Spec;
 use state_db::StateDB;
 use state::{self, State};
 use trace;
-use trace::{TraceDB, ImportRequest as TraceImportRequest, LocalizedTrace, Database as TraceDatabase};
+use trace::{TraceDB, ImportRequest as TraceImportRequest, LocalizedTrace, Database as TraceDatabase, FlatTrace, StructLog, StructLoggerConfig};
 use transaction::{self, LocalizedTransaction, UnverifiedTransaction, SignedTransaction, Transaction, Action};
 use types::filter::Filter;
 use types::mode::Mode as IpcMode;
 use types::ancestry_action::AncestryAction;
+use types::validator_report::ValidatorReport;
 use verification;
 use verification::{PreverifiedBlock, Verifier};
 use verification::queue::BlockQueue;
@@ -92,6 +93,8 @@ const MAX_TX_QUEUE_SIZE: usize = 4096;
 const MAX_ANCIENT_BLOCKS_QUEUE_SIZE: usize = 4096;
 const MAX_QUEUE_SIZE_TO_SLEEP_ON: usize = 2;
 const MIN_HISTORY_SIZE: u64 = 8;
+/// Number of most recent blocks searched for the latest finalized block on startup.
+const MAX_FINALITY_LOOKBACK: usize = 1024;
 
 /// Report on the status of a client.
 #[derive(Default, Clone, Debug, Eq, PartialEq)]
@@ -229,6 +232,9 @@ pub struct Client {
 	/// A closure to call when we want to restart the client
 	exit_handler: Mutex<Option<Box<Fn(String) + 'static + Send>>>,
 
+	/// Latest canonical block finalized by the engine.
+	finalized_block: RwLock<Option<H256>>,
+
 	importer: Importer,
 }
 
@@ -260,11 +266,12 @@ impl Importer {
 		}
 
 		let max_blocks_to_import = 4;
-		let (imported_blocks, import_results, invalid_blocks, imported, proposed_blocks, duration, is_empty) = {
+		let (imported_blocks, import_results, invalid_blocks, imported, proposed_blocks, finalized_blocks, duration, is_empty) = {
 			let mut imported_blocks = Vec::with_capacity(max_blocks_to_import);
 			let mut invalid_blocks = HashSet::new();
 			let mut proposed_blocks = Vec::with_capacity(max_blocks_to_import);
 			let mut import_results = Vec::with_capacity(max_blocks_to_import);
+			let mut finalized_blocks = Vec::new();
 
 			let _import_lock = self.import_lock.lock();
 			let blocks = self.block_queue.drain(max_blocks_to_import);
@@ -294,8 +301,9 @@ impl Importer {
 
 						let transactions_len = closed_block.transactions().len();
 
-						let route = self.commit_block(closed_block, &header, &bytes, client);
+						let (route, finalized) = self.commit_block(closed_block, &header, &bytes, client);
 						import_results.push(route);
+						finalized_blocks.extend(finalized);
 
 						client.report.write().accrue_block(&header, transactions_len);
 					}
@@ -311,7 +319,7 @@ impl Importer {
 				self.block_queue.mark_as_bad(&invalid_blocks);
 			}
 			let is_empty = self.block_queue.mark_as_good(&imported_blocks);
-			(imported_blocks, import_results, invalid_blocks, imported, proposed_blocks, start.elapsed(), is_empty)
+			(imported_blocks, import_results, invalid_blocks, imported, proposed_blocks, finalized_blocks, start.elapsed(), is_empty)
 		};
 
 		{
@@ -333,6 +341,10 @@ impl Importer {
 					);
 				});
 			}
+
+			if !finalized_blocks.is_empty() {
+				client.notify(|notify| notify.finalized_blocks(finalized_blocks.clone()));
+			}
 		}
 
 		client.db.read().flush().expect("DB flush failed.");
@@ -454,7 +466,9 @@ impl Importer {
 	// it is for reconstructing the state transition.
 	//
 	// The header passed is from the original block data and is sealed.
-	fn commit_block<B>(&self, block: B, header: &Header, block_data: &[u8], client: &Client) -> ImportRoute where B: IsBlock + Drain {
+	// Commit the block and apply the engine's ancestry actions. Returns the import route
+	// and the canonical blocks finalized by the engine, oldest first.
+	fn commit_block<B>(&self, block: B, header: &Header, block_data: &[u8], client: &Client) -> (ImportRoute, Vec<H256>) where B: IsBlock + Drain {
 		let hash = &header.hash();
 		let number = header.number();
 		let parent = header.parent_hash();
@@ -470,11 +484,13 @@ impl Importer {
 
 		let mut batch = DBTransaction::new();
 
-		let ancestry_actions = self.engine.ancestry_actions(block.block(), &mut chain.ancestry_with_metadata_iter(*parent));
+		let ancestry_actions = self.engine.ancestry_actions(header, &mut chain.ancestry_with_metadata_iter(*parent));
 
 		let best_hash = chain.best_block_hash();
 		let metadata = block.block().metadata().map(Into::into);
-		let is_finalized = block.block().is_finalized();
+		// the engine may finalize the block itself, e.g. when there is a single validator.
+		let is_finalized = block.block().is_finalized() ||
+			ancestry_actions.iter().any(|&AncestryAction::MarkFinalized(ref ancestry)| ancestry == hash);
 
 		let new = ExtendedHeader {
 			header: header.clone(),
@@ -527,9 +543,13 @@ impl Importer {
 
 		state.journal_under(&mut batch, number, hash).expect("DB commit failed");
 
+		let mut finalized = Vec::new();
 		for ancestry_action in ancestry_actions {
 			let AncestryAction::MarkFinalized(ancestry) = ancestry_action;
-			chain.mark_finalized(&mut batch, ancestry).expect("Engine's ancestry action must be known blocks; qed");
+			if ancestry != *hash {
+				chain.mark_finalized(&mut batch, ancestry).expect("Engine's ancestry action must be known blocks; qed");
+			}
+			finalized.push(ancestry);
 		}
 
 		let route = chain.insert_block(&mut batch, block_data, receipts.clone(), ExtrasInsert {
@@ -552,7 +572,7 @@ impl Importer {
 		client.db.read().write_buffered(batch);
 		chain.commit();
 
-		self.check_epoch_end(&header, &chain, client);
+		self.check_epoch_end(&header, &finalized, &chain, client);
 
 		client.update_last_hashes(&parent, hash);
 
@@ -560,7 +580,13 @@ impl Importer {
 			warn!("Failed to prune ancient state data: {}", e);
 		}
 
-		route
+		// only blocks of the canonical chain are reported as finalized.
+		finalized.retain(|h| chain.block_number(h).and_then(|n| chain.block_hash(n)) == Some(*h));
+		if let Some(latest) = finalized.last() {
+			*client.finalized_block.write() = Some(*latest);
+		}
+
+		(route, finalized)
 	}
 
 	// check for epoch end signal and write pending transition if it occurs.
@@ -657,9 +683,10 @@ impl Importer {
 	}
 
 	// check for ending of epoch and write transition if it occurs.
-	fn check_epoch_end<'a>(&self, header: &'a Header, chain: &BlockChain, client: &Client) {
+	fn check_epoch_end<'a>(&self, header: &'a Header, finalized: &[H256], chain: &BlockChain, client: &Client) {
 		let is_epoch_end = self.engine.is_epoch_end(
 			header,
+			finalized,
 			&(|hash| client.block_header_decoded(BlockId::Hash(hash))),
 			&(|hash| chain.get_pending_transition(hash)), // TODO: limit to current epoch.
 		);
@@ -769,9 +796,19 @@ impl Client {
 			registrar: registry::Registry::default(),
 			registrar_address,
 			exit_handler: Mutex::new(None),
+			finalized_block: RwLock::new(None),
 			importer,
 		});
 
+		// find the latest finalized block.
+		{
+			let chain = client.chain.read();
+			*client.finalized_block.write() = chain.ancestry_with_metadata_iter(chain.best_block_hash())
+				.take(MAX_FINALITY_LOOKBACK)
+				.find(|header| header.is_finalized)
+				.map(|header| header.header.hash());
+		}
+
 		// prune old states.
 		{
 			let state_db = client.state_db.read().boxed_clone();
@@ -1214,24 +1251,41 @@ impl Client {
 		state: &mut State<StateDB>,
 		t: &SignedTransaction,
 		analytics: CallAnalytics,
+	) -> Result<Executed, CallError> {
+		Self::do_call(machine, env_info, state, t, analytics, true)
+	}
+
+	fn do_call(
+		machine: &::machine::EthereumMachine,
+		env_info: &EnvInfo,
+		state: &mut State<StateDB>,
+		t: &SignedTransaction,
+		analytics: CallAnalytics,
+		virtual_call: bool,
 	) -> Result<Executed, CallError> {
 		fn call<V, T>(
 			state: &mut State<StateDB>,
 			env_info: &EnvInfo,
 			machine: &::machine::EthereumMachine,
 			state_diff: bool,
+			virtual_call: bool,
 			transaction: &SignedTransaction,
 			options: TransactOptions<T, V>,
 		) -> Result<Executed<T::Output, V::Output>, CallError> where
 			T: trace::Tracer,
 			V: trace::VMTracer,
 		{
-			let options = options
-				.dont_check_nonce()
-				.save_output_from_contract();
+			let options = options.save_output_from_contract();
 			let original_state = if state_diff { Some(state.clone()) } else { None };
 
-			let mut ret = Executive::new(state, env_info, machine).transact_virtual(transaction, options)?;
+			let mut ret = {
+				let mut executive = Executive::new(state, env_info, machine);
+				if virtual_call {
+					executive.transact_virtual(transaction, options.dont_check_nonce())?
+				} else {
+					executive.transact(transaction, options)?
+				}
+			};
 
 			if let Some(original) = original_state {
 				ret.state_diff = Some(state.diff_from(original).map_err(ExecutionError::from)?);
@@ -1242,13 +1296,27 @@ impl Client {
 		let state_diff = analytics.state_diffing;
 
 		match (analytics.transaction_tracing, analytics.vm_tracing) {
-			(true, true) => call(state, env_info, machine, state_diff, t, TransactOptions::with_tracing_and_vm_tracing()),
-			(true, false) => call(state, env_info, machine, state_diff, t, TransactOptions::with_tracing()),
-			(false, true) => call(state, env_info, machine, state_diff, t, TransactOptions::with_vm_tracing()),
-			(false, false) => call(state, env_info, machine, state_diff, t, TransactOptions::with_no_tracing()),
+			(true, true) => call(state, env_info, machine, state_diff, virtual_call, t, TransactOptions::with_tracing_and_vm_tracing()),
+			(true, false) => call(state, env_info, machine, state_diff, virtual_call, t, TransactOptions::with_tracing()),
+			(false, true) => call(state, env_info, machine, state_diff, virtual_call, t, TransactOptions::with_vm_tracing()),
+			(false, false) => call(state, env_info, machine, state_diff, virtual_call, t, TransactOptions::with_no_tracing()),
 		}
 	}
 
+	fn do_struct_logged_call(
+		machine: &::machine::EthereumMachine,
+		env_info: &EnvInfo,
+		state: &mut State<StateDB>,
+		t: &SignedTransaction,
+		config: StructLoggerConfig,
+	) -> Result<Executed<FlatTrace, Vec<StructLog>>, CallError> {
+		let options = TransactOptions::new(trace::NoopTracer, trace::StructLogger::new(config))
+			.dont_check_nonce()
+			.save_output_from_contract();
+
+		Ok(Executive::new(state, env_info, machine).transact_virtual(t, options)?)
+	}
+
 	fn block_number_ref(&self, id: &BlockId) -> Option<BlockNumber> {
 		match *id {
 			BlockId::Number(number) => Some(number),
@@ -1273,6 +1341,26 @@ impl Client {
 			_   => self.block_header(id).and_then(|h| h.decode().ok())
 		}
 	}
+
+	/// Sign a call to the given contract with the engine signer and import it into the queue.
+	fn transact_with_gas_price(&self, address: Address, data: Bytes, gas_price: U256) -> Result<H256, transaction::Error> {
+		let authoring_params = self.importer.miner.authoring_params();
+		let transaction = Transaction {
+			nonce: self.latest_nonce(&authoring_params.author),
+			action: Action::Call(address),
+			gas: self.importer.miner.sensible_gas_limit(),
+			gas_price: gas_price,
+			value: U256::zero(),
+			data: data,
+		};
+		let chain_id = self.engine.signing_chain_id(&self.latest_env_info());
+		let signature = self.engine.sign(transaction.hash(chain_id))
+			.map_err(|e| transaction::Error::InvalidSignature(e.to_string()))?;
+		let signed = SignedTransaction::new(transaction.with_signature(signature, chain_id))?;
+		let hash = signed.hash();
+		self.importer.miner.import_own_transaction(self, signed.into())?;
+		Ok(hash)
+	}
 }
 
 impl snapshot::DatabaseRestore for Client {
@@ -1432,6 +1520,21 @@ impl Call for Client {
 		Self::do_virtual_call(&machine, &env_info, state, transaction, analytics)
 	}
 
+	fn call_with_struct_logs(&self, transaction: &SignedTransaction, config: StructLoggerConfig, state: &mut Self::State, header: &Header) -> Result<Executed<FlatTrace, Vec<StructLog>>, CallError> {
+		let env_info = EnvInfo {
+			number: header.number(),
+			author: header.author().clone(),
+			timestamp: header.timestamp(),
+			difficulty: header.difficulty().clone(),
+			last_hashes: self.build_last_hashes(header.parent_hash()),
+			gas_used: U256::default(),
+			gas_limit: U256::max_value(),
+		};
+		let machine = self.engine.machine();
+
+		Self::do_struct_logged_call(&machine, &env_info, state, transaction, config)
+	}
+
 	fn call_many(&self, transactions: &[(SignedTransaction, CallAnalytics)], state: &mut Self::State, header: &Header) -> Result<Vec<Executed>, CallError> {
 		let mut env_info = EnvInfo {
 			number: header.number(),
@@ -1455,6 +1558,44 @@ impl Call for Client {
 		Ok(results)
 	}
 
+	fn simulate(&self, transactions: &[(SignedTransaction, CallAnalytics)], state: &mut Self::State, header: &Header) -> Result<Vec<Executed>, CallError> {
+		let hash = header.hash();
+		let mut env_info = if self.chain.read().is_known(&hash) {
+			// on top of an imported block, so execute as in its child.
+			EnvInfo {
+				number: header.number() + 1,
+				author: self.importer.miner.authoring_params().author,
+				timestamp: self.engine.open_block_header_timestamp(header.timestamp()),
+				difficulty: header.difficulty().clone(),
+				last_hashes: self.build_last_hashes(&hash),
+				gas_used: U256::zero(),
+				gas_limit: *header.gas_limit(),
+			}
+		} else {
+			// pending block, append to its transactions.
+			EnvInfo {
+				number: header.number(),
+				author: header.author().clone(),
+				timestamp: header.timestamp(),
+				difficulty: header.difficulty().clone(),
+				last_hashes: self.build_last_hashes(header.parent_hash()),
+				gas_used: *header.gas_used(),
+				gas_limit: *header.gas_limit(),
+			}
+		};
+
+		let mut results = Vec::with_capacity(transactions.len());
+		let machine = self.engine.machine();
+
+		for &(ref t, analytics) in transactions {
+			let ret = Self::do_call(machine, &env_info, state, t, analytics, false)?;
+			env_info.gas_used = ret.cumulative_gas_used;
+			results.push(ret);
+		}
+
+		Ok(results)
+	}
+
 	fn estimate_gas(&self, t: &SignedTransaction, state: &Self::State, header: &Header) -> Result<U256, CallError> {
 		let (mut upper, max_upper, env_info) = {
 			let init = *header.gas_limit();
@@ -1562,6 +1703,32 @@ impl BlockChainClient for Client {
 			})))
 	}
 
+	fn replay_with_struct_logs(&self, id: TransactionId, config: StructLoggerConfig) -> Result<Executed<FlatTrace, Vec<StructLog>>, CallError> {
+		let address = self.transaction_address(id).ok_or(CallError::TransactionNotFound)?;
+		let block = BlockId::Hash(address.block_hash);
+
+		let mut env_info = self.env_info(block).ok_or(CallError::StatePruned)?;
+		let body = self.block_body(block).ok_or(CallError::StatePruned)?;
+		let mut state = self.state_at_beginning(block).ok_or(CallError::StatePruned)?;
+		let mut txs = body.transactions();
+		let machine = self.engine.machine();
+
+		const PROOF: &'static str = "Transactions fetched from blockchain; blockchain transactions are valid; qed";
+		const INDEX_PROOF: &'static str = "The transaction address contains a valid index within block; qed";
+
+		txs.truncate(address.index + 1);
+		let target = SignedTransaction::new(txs.pop().expect(INDEX_PROOF)).expect(PROOF);
+
+		// bring the state up to the replayed transaction.
+		for t in txs {
+			let t = SignedTransaction::new(t).expect(PROOF);
+			let x = Self::do_virtual_call(machine, &env_info, &mut state, &t, Default::default())?;
+			env_info.gas_used = env_info.gas_used + x.gas_used;
+		}
+
+		Self::do_struct_logged_call(machine, &env_info, &mut state, &target, config)
+	}
+
 
 	fn mode(&self) -> IpcMode {
 		let r = self.mode.lock().clone().into();
@@ -1979,21 +2146,31 @@ impl BlockChainClient for Client {
 		}
 	}
 
-	fn transact_contract(&self, address: Address, data: Bytes) -> Result<(), transaction::Error> {
-		let authoring_params = self.importer.miner.authoring_params();
-		let transaction = Transaction {
-			nonce: self.latest_nonce(&authoring_params.author),
-			action: Action::Call(address),
-			gas: self.importer.miner.sensible_gas_limit(),
-			gas_price: self.importer.miner.sensible_gas_price(),
-			value: U256::zero(),
-			data: data,
-		};
-		let chain_id = self.engine.signing_chain_id(&self.latest_env_info());
-		let signature = self.engine.sign(transaction.hash(chain_id))
-			.map_err(|e| transaction::Error::InvalidSignature(e.to_string()))?;
-		let signed = SignedTransaction::new(transaction.with_signature(signature, chain_id))?;
-		self.importer.miner.import_own_transaction(self, signed.into())
+	fn transact_contract(&self, address: Address, data: Bytes) -> Result<H256, transaction::Error> {
+		let gas_price = self.importer.miner.sensible_gas_price();
+		self.transact_with_gas_price(address, data, gas_price)
+	}
+
+	fn transact_service_contract(&self, address: Address, data: Bytes) -> Result<H256, transaction::Error> {
+		self.transact_with_gas_price(address, data, U256::zero())
+	}
+
+	fn validator_reports(&self) -> Vec<ValidatorReport> {
+		self.engine.validator_reports()
+	}
+
+	fn finalized_block(&self) -> Option<H256> {
+		let chain = self.chain.read();
+		let is_canon = |hash: &H256| chain.block_number(hash).and_then(|n| chain.block_hash(n)) == Some(*hash);
+
+		match *self.finalized_block.read() {
+			Some(hash) if is_canon(&hash) => return Some(hash),
+			_ => {},
+		}
+
+		// fall back to a confirmation depth for engines without finality.
+		let confirmations = self.config.finality_confirmations?;
+		chain.best_block_number().checked_sub(confirmations).and_then(|n| chain.block_hash(n))
 	}
 
 	fn registrar_address(&self) -> Option<Address> {
@@ -2153,7 +2330,7 @@ impl ImportSealedBlock for Client {
 	fn import_sealed_block(&self, block: SealedBlock) -> ImportResult {
 		let h = block.header().hash();
 		let start = Instant::now();
-		let route = {
+		let (route, finalized) = {
 			// scope for self.import_lock
 			let _import_lock = self.importer.import_lock.lock();
 			trace_time!("import_sealed_block");
@@ -2162,10 +2339,10 @@ impl ImportSealedBlock for Client {
 			let block_data = block.rlp_bytes();
 			let header = block.header().clone();
 
-			let route = self.importer.commit_block(block, &header, &block_data, self);
+			let (route, finalized) = self.importer.commit_block(block, &header, &block_data, self);
 			trace!(target: "client", "Imported sealed block #{} ({})", number, h);
 			self.state_db.write().sync_cache(&route.enacted, &route.retracted, false);
-			route
+			(route, finalized)
 		};
 		let route = ChainRoute::from([route].as_ref());
 		self.importer.miner.chain_new_blocks(self, &[h.clone()], &[], route.enacted(), route.retracted(), true);
@@ -2179,6 +2356,9 @@ impl ImportSealedBlock for Client {
 				start.elapsed(),
 			);
 		});
+		if !finalized.is_empty() {
+			self.notify(|notify| notify.finalized_blocks(finalized.clone()));
+		}
 		self.db.read().flush().expect("DB flush failed.");
 		Ok(h)
 	}
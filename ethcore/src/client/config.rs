@@ -56,6 +56,33 @@ impl FromStr for DatabaseCompactionProfile {
 	}
 }
 
+/// Key-value store backing the client database.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DatabaseBackend {
+	/// RocksDB (LSM tree).
+	RocksDB,
+	/// LMDB (copy-on-write B+tree).
+	Lmdb,
+}
+
+impl Default for DatabaseBackend {
+	fn default() -> Self {
+		DatabaseBackend::RocksDB
+	}
+}
+
+impl FromStr for DatabaseBackend {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"rocksdb" => Ok(DatabaseBackend::RocksDB),
+			"lmdb" => Ok(DatabaseBackend::Lmdb),
+			_ => Err("Invalid database backend given. Expected rocksdb/lmdb.".into()),
+		}
+	}
+}
+
 /// Operating mode for the client.
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub enum Mode {
@@ -132,6 +159,8 @@ pub struct ClientConfig {
 	pub db_cache_size: Option<usize>,
 	/// State db compaction profile
 	pub db_compaction: DatabaseCompactionProfile,
+	/// Key-value store backing the database
+	pub db_backend: DatabaseBackend,
 	/// Should db have WAL enabled?
 	pub db_wal: bool,
 	/// Operating mode
@@ -150,11 +179,14 @@ pub struct ClientConfig {
 	pub history_mem: usize,
 	/// Check seal valididity on block import
 	pub check_seal: bool,
+	/// Number of confirmations after which a block is considered final
+	/// when the engine hasn't finalized any block.
+	pub finality_confirmations: Option<u64>,
 }
 
 #[cfg(test)]
 mod test {
-	use super::{DatabaseCompactionProfile, Mode};
+	use super::{DatabaseBackend, DatabaseCompactionProfile, Mode};
 
 	#[test]
 	fn test_default_compaction_profile() {
@@ -168,6 +200,14 @@ mod test {
 		assert_eq!(DatabaseCompactionProfile::HDD, "hdd".parse().unwrap());
 	}
 
+	#[test]
+	fn test_parsing_database_backend() {
+		assert_eq!(DatabaseBackend::default(), DatabaseBackend::RocksDB);
+		assert_eq!(DatabaseBackend::RocksDB, "rocksdb".parse().unwrap());
+		assert_eq!(DatabaseBackend::Lmdb, "lmdb".parse().unwrap());
+		assert!("sled".parse::<DatabaseBackend>().is_err());
+	}
+
 	#[test]
 	fn test_mode_default() {
 		assert_eq!(Mode::default(), Mode::Active);
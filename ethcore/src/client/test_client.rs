@@ -53,12 +53,13 @@ use spec::Spec;
 use types::basic_account::BasicAccount;
 use types::mode::Mode;
 use types::pruning_info::PruningInfo;
+use types::validator_report::ValidatorReport;
 
 use verification::queue::QueueInfo;
 use block::{OpenBlock, SealedBlock, ClosedBlock};
 use executive::Executed;
 use error::CallError;
-use trace::LocalizedTrace;
+use trace::{LocalizedTrace, FlatTrace, StructLog, StructLoggerConfig};
 use state_db::StateDB;
 use header::Header;
 use encoded;
@@ -113,6 +114,14 @@ pub struct TestBlockChainClient {
 	pub history: RwLock<Option<u64>>,
 	/// Is disabled
 	pub disabled: AtomicBool,
+	/// Validator reports to return.
+	pub validator_reports: RwLock<Vec<ValidatorReport>>,
+	/// Latest finalized block to return.
+	pub finalized_block: RwLock<Option<H256>>,
+	/// Account proofs to return, by address hash.
+	pub account_proofs: RwLock<HashMap<H256, (Vec<Bytes>, BasicAccount)>>,
+	/// Storage proofs to return, by address hash and storage key hash.
+	pub storage_proofs: RwLock<HashMap<(H256, H256), (Vec<Bytes>, H256)>>,
 }
 
 /// Used for generating test client blocks.
@@ -179,6 +188,10 @@ impl TestBlockChainClient {
 			traces: RwLock::new(None),
 			history: RwLock::new(None),
 			disabled: AtomicBool::new(false),
+			validator_reports: RwLock::new(Vec::new()),
+			finalized_block: RwLock::new(None),
+			account_proofs: RwLock::new(HashMap::new()),
+			storage_proofs: RwLock::new(HashMap::new()),
 		};
 
 		// insert genesis hash.
@@ -199,6 +212,23 @@ impl TestBlockChainClient {
 		*self.execution_result.write() = Some(result);
 	}
 
+	// the execution result with an empty struct log in place of the VM trace.
+	fn struct_logged_execution_result(&self) -> Result<Executed<FlatTrace, Vec<StructLog>>, CallError> {
+		self.execution_result.read().clone().unwrap().map(|executed| Executed {
+			exception: executed.exception,
+			gas: executed.gas,
+			gas_used: executed.gas_used,
+			refunded: executed.refunded,
+			cumulative_gas_used: executed.cumulative_gas_used,
+			logs: executed.logs,
+			contracts_created: executed.contracts_created,
+			output: executed.output,
+			trace: executed.trace,
+			vm_trace: Some(Vec::new()),
+			state_diff: executed.state_diff,
+		})
+	}
+
 	/// Set the balance of account `address` to `balance`.
 	pub fn set_balance(&self, address: Address, balance: U256) {
 		self.balances.write().insert(address, balance);
@@ -568,6 +598,10 @@ impl Call for TestBlockChainClient {
 		self.execution_result.read().clone().unwrap()
 	}
 
+	fn call_with_struct_logs(&self, _t: &SignedTransaction, _config: StructLoggerConfig, _state: &mut Self::State, _header: &Header) -> Result<Executed<FlatTrace, Vec<StructLog>>, CallError> {
+		self.struct_logged_execution_result()
+	}
+
 	fn call_many(&self, txs: &[(SignedTransaction, CallAnalytics)], state: &mut Self::State, header: &Header) -> Result<Vec<Executed>, CallError> {
 		let mut res = Vec::with_capacity(txs.len());
 		for &(ref tx, analytics) in txs {
@@ -576,6 +610,10 @@ impl Call for TestBlockChainClient {
 		Ok(res)
 	}
 
+	fn simulate(&self, txs: &[(SignedTransaction, CallAnalytics)], state: &mut Self::State, header: &Header) -> Result<Vec<Executed>, CallError> {
+		self.call_many(txs, state, header)
+	}
+
 	fn estimate_gas(&self, _t: &SignedTransaction, _state: &Self::State, _header: &Header) -> Result<U256, CallError> {
 		Ok(21000.into())
 	}
@@ -616,6 +654,10 @@ impl BlockChainClient for TestBlockChainClient {
 		Ok(Box::new(self.execution_result.read().clone().unwrap().into_iter()))
 	}
 
+	fn replay_with_struct_logs(&self, _id: TransactionId, _config: StructLoggerConfig) -> Result<Executed<FlatTrace, Vec<StructLog>>, CallError> {
+		self.struct_logged_execution_result()
+	}
+
 	fn block_total_difficulty(&self, _id: BlockId) -> Option<U256> {
 		Some(U256::zero())
 	}
@@ -832,7 +874,11 @@ impl BlockChainClient for TestBlockChainClient {
 		}
 	}
 
-	fn transact_contract(&self, address: Address, data: Bytes) -> Result<(), transaction::Error> {
+	fn transact_contract(&self, address: Address, data: Bytes) -> Result<H256, transaction::Error> {
+		self.transact_service_contract(address, data)
+	}
+
+	fn transact_service_contract(&self, address: Address, data: Bytes) -> Result<H256, transaction::Error> {
 		let transaction = Transaction {
 			nonce: self.latest_nonce(&self.miner.authoring_params().author),
 			action: Action::Call(address),
@@ -844,7 +890,16 @@ impl BlockChainClient for TestBlockChainClient {
 		let chain_id = Some(self.spec.chain_id());
 		let sig = self.spec.engine.sign(transaction.hash(chain_id)).unwrap();
 		let signed = SignedTransaction::new(transaction.with_signature(sig, chain_id)).unwrap();
-		self.miner.import_own_transaction(self, signed.into())
+		let hash = signed.hash();
+		self.miner.import_own_transaction(self, signed.into()).map(|_| hash)
+	}
+
+	fn validator_reports(&self) -> Vec<ValidatorReport> {
+		self.validator_reports.read().clone()
+	}
+
+	fn finalized_block(&self) -> Option<H256> {
+		self.finalized_block.read().clone()
 	}
 
 	fn registrar_address(&self) -> Option<Address> { None }
@@ -869,12 +924,18 @@ impl IoClient for TestBlockChainClient {
 }
 
 impl ProvingBlockChainClient for TestBlockChainClient {
-	fn prove_storage(&self, _: H256, _: H256, _: BlockId) -> Option<(Vec<Bytes>, H256)> {
-		None
+	fn prove_storage(&self, key1: H256, key2: H256, id: BlockId) -> Option<(Vec<Bytes>, H256)> {
+		match id {
+			BlockId::Latest => self.storage_proofs.read().get(&(key1, key2)).cloned(),
+			_ => None,
+		}
 	}
 
-	fn prove_account(&self, _: H256, _: BlockId) -> Option<(Vec<Bytes>, BasicAccount)> {
-		None
+	fn prove_account(&self, key1: H256, id: BlockId) -> Option<(Vec<Bytes>, BasicAccount)> {
+		match id {
+			BlockId::Latest => self.account_proofs.read().get(&key1).cloned(),
+			_ => None,
+		}
 	}
 
 	fn prove_transaction(&self, _: SignedTransaction, _: BlockId) -> Option<(Bytes, Vec<DBValue>)> {
@@ -30,7 +30,7 @@ use filter::Filter;
 use header::{BlockNumber};
 use log_entry::LocalizedLogEntry;
 use receipt::LocalizedReceipt;
-use trace::LocalizedTrace;
+use trace::{LocalizedTrace, FlatTrace, StructLog, StructLoggerConfig};
 use transaction::{self, LocalizedTransaction, SignedTransaction};
 use verification::queue::QueueInfo as BlockQueueInfo;
 use state::StateInfo;
@@ -50,6 +50,7 @@ use types::blockchain_info::BlockChainInfo;
 use types::block_status::BlockStatus;
 use types::mode::Mode;
 use types::pruning_info::PruningInfo;
+use types::validator_report::ValidatorReport;
 
 /// State information to be used during client query
 pub enum StateOrBlock {
@@ -184,10 +185,19 @@ pub trait Call {
 	/// Makes a non-persistent transaction call.
 	fn call(&self, tx: &SignedTransaction, analytics: CallAnalytics, state: &mut Self::State, header: &Header) -> Result<Executed, CallError>;
 
+	/// Makes a non-persistent transaction call, recording a struct log of every executed instruction.
+	fn call_with_struct_logs(&self, tx: &SignedTransaction, config: StructLoggerConfig, state: &mut Self::State, header: &Header) -> Result<Executed<FlatTrace, Vec<StructLog>>, CallError>;
+
 	/// Makes multiple non-persistent but dependent transaction calls.
 	/// Returns a vector of successes or a failure if any of the transaction fails.
 	fn call_many(&self, txs: &[(SignedTransaction, CallAnalytics)], state: &mut Self::State, header: &Header) -> Result<Vec<Executed>, CallError>;
 
+	/// Applies signed transactions sequentially on top of the block with given header, without persisting them.
+	/// Unlike `call_many`, nonces, balances and the block gas limit are checked as during block import.
+	/// On top of an imported block the transactions are executed as in its child block,
+	/// on top of a pending block they are executed after its own transactions.
+	fn simulate(&self, txs: &[(SignedTransaction, CallAnalytics)], state: &mut Self::State, header: &Header) -> Result<Vec<Executed>, CallError>;
+
 	/// Estimates how much gas will be necessary for a call.
 	fn estimate_gas(&self, t: &SignedTransaction, state: &Self::State, header: &Header) -> Result<U256, CallError>;
 }
@@ -305,6 +315,9 @@ pub trait BlockChainClient : Sync + Send + AccountData + BlockChain + CallContra
 	/// Replays all the transactions in a given block for inspection.
 	fn replay_block_transactions(&self, block: BlockId, analytics: CallAnalytics) -> Result<Box<Iterator<Item = Executed>>, CallError>;
 
+	/// Replays a given transaction, recording a struct log of every executed instruction.
+	fn replay_with_struct_logs(&self, t: TransactionId, config: StructLoggerConfig) -> Result<Executed<FlatTrace, Vec<StructLog>>, CallError>;
+
 	/// Returns traces matching given filter.
 	fn filter_traces(&self, filter: TraceFilter) -> Option<Vec<LocalizedTrace>>;
 
@@ -373,7 +386,18 @@ pub trait BlockChainClient : Sync + Send + AccountData + BlockChain + CallContra
 	fn pruning_info(&self) -> PruningInfo;
 
 	/// Schedule state-altering transaction to be executed on the next pending block.
-	fn transact_contract(&self, address: Address, data: Bytes) -> Result<(), transaction::Error>;
+	/// Returns the hash of the transaction.
+	fn transact_contract(&self, address: Address, data: Bytes) -> Result<H256, transaction::Error>;
+
+	/// Schedule a zero gas price (service) transaction to be executed on the next pending block.
+	/// Returns the hash of the transaction.
+	fn transact_service_contract(&self, address: Address, data: Bytes) -> Result<H256, transaction::Error>;
+
+	/// Validator misbehaviour reports sent by this node, oldest first.
+	fn validator_reports(&self) -> Vec<ValidatorReport>;
+
+	/// Get the hash of the latest finalized block of the canonical chain, if any.
+	fn finalized_block(&self) -> Option<H256>;
 
 	/// Get the address of the registry itself.
 	fn registrar_address(&self) -> Option<Address>;
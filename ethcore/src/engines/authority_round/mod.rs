@@ -25,6 +25,7 @@ use std::iter::FromIterator;
 
 use account_provider::AccountProvider;
 use block::*;
+use bytes::Bytes;
 use client::EngineClient;
 use engines::{Engine, Seal, EngineError, ConstructedVerifier};
 use engines::block_reward;
@@ -47,17 +48,20 @@ use rlp::{encode, Decodable, DecoderError, Encodable, RlpStream, Rlp};
 use ethereum_types::{H256, H520, Address, U128, U256};
 use parking_lot::{Mutex, RwLock};
 use unexpected::{Mismatch, OutOfBounds};
+use types::ancestry_action::AncestryAction;
+use types::validator_report::ValidatorReport;
 
 mod finality;
 
 /// `AuthorityRound` params.
 pub struct AuthorityRoundParams {
 	/// Time to wait before next block or authority switching,
-	/// in seconds.
+	/// in seconds, by the step from which it applies.
+	/// The first duration has to apply from step zero.
 	///
 	/// Deliberately typed as u16 as too high of a value leads
 	/// to slow block issuance.
-	pub step_duration: u16,
+	pub step_durations: BTreeMap<u64, u16>,
 	/// Starting step,
 	pub start_step: Option<u64>,
 	/// Valid validators.
@@ -86,15 +90,28 @@ pub struct AuthorityRoundParams {
 
 const U16_MAX: usize = ::std::u16::MAX as usize;
 
+/// Number of past steps for which received headers are kept to detect double proposals.
+const RECEIVED_HEADERS_STEPS: usize = 128;
+
+fn step_duration_u16(step_duration: ethjson::uint::Uint) -> u16 {
+	let mut step_duration_usize: usize = step_duration.into();
+	if step_duration_usize > U16_MAX {
+		step_duration_usize = U16_MAX;
+		warn!(target: "engine", "step_duration is too high ({}), setting it to {}", step_duration_usize, U16_MAX);
+	}
+	step_duration_usize as u16
+}
+
 impl From<ethjson::spec::AuthorityRoundParams> for AuthorityRoundParams {
 	fn from(p: ethjson::spec::AuthorityRoundParams) -> Self {
-		let mut step_duration_usize: usize = p.step_duration.into();
-		if step_duration_usize > U16_MAX {
-			step_duration_usize = U16_MAX;
-			warn!(target: "engine", "step_duration is too high ({}), setting it to {}", step_duration_usize, U16_MAX);
-		}
+		let step_durations = match p.step_duration {
+			ethjson::spec::StepDuration::Single(duration) => vec![(0, step_duration_u16(duration))].into_iter().collect(),
+			ethjson::spec::StepDuration::Transitions(durations) => durations.into_iter()
+				.map(|(step, duration)| (step.into(), step_duration_u16(duration)))
+				.collect(),
+		};
 		AuthorityRoundParams {
-			step_duration: step_duration_usize as u16,
+			step_durations: step_durations,
 			validators: new_validator_set(p.validators),
 			start_step: p.start_step.map(Into::into),
 			validate_score_transition: p.validate_score_transition.map_or(0, Into::into),
@@ -111,21 +128,90 @@ impl From<ethjson::spec::AuthorityRoundParams> for AuthorityRoundParams {
 	}
 }
 
+// A step duration and the step and unix time from which it applies.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct StepDurationInfo {
+	transition_step: u64,
+	transition_timestamp: u64,
+	step_duration: u64,
+}
+
+impl StepDurationInfo {
+	/// Compute the duration transitions from a map of starting steps to durations.
+	/// Each transition starts at the time at which its step starts with the preceding durations.
+	fn from_transitions(durations: &BTreeMap<u64, u16>) -> Result<Vec<Self>, String> {
+		let mut infos: Vec<StepDurationInfo> = Vec::with_capacity(durations.len());
+		for (&step, &duration) in durations {
+			if duration == 0 {
+				return Err("step duration can't be zero".into());
+			}
+
+			let timestamp = match infos.last() {
+				None if step != 0 => return Err("step duration transitions have to start at step zero".into()),
+				None => 0,
+				Some(prev) => (step - prev.transition_step)
+					.checked_mul(prev.step_duration)
+					.and_then(|elapsed| elapsed.checked_add(prev.transition_timestamp))
+					.ok_or_else(|| format!("step duration transition at step {} is too high", step))?,
+			};
+
+			infos.push(StepDurationInfo {
+				transition_step: step,
+				transition_timestamp: timestamp,
+				step_duration: duration as u64,
+			});
+		}
+
+		if infos.is_empty() {
+			return Err("step duration can't be zero".into());
+		}
+
+		Ok(infos)
+	}
+}
+
 // Helper for managing the step.
 #[derive(Debug)]
 struct Step {
 	calibrate: bool, // whether calibration is enabled.
 	inner: AtomicUsize,
-	duration: u16,
+	// step duration transitions, ordered by step. The first one starts at step zero.
+	durations: Vec<StepDurationInfo>,
 }
 
 impl Step {
 	fn load(&self) -> usize { self.inner.load(AtomicOrdering::SeqCst) }
+
+	/// Duration info applying to the given step.
+	fn duration_info(&self, step: u64) -> &StepDurationInfo {
+		self.durations.iter()
+			.rev()
+			.find(|info| info.transition_step <= step)
+			.expect("durations are non-empty and the first one starts at step zero; qed")
+	}
+
+	/// Unix time at which the given step starts, in seconds.
+	fn step_start(&self, step: u64) -> Option<u64> {
+		let info = self.duration_info(step);
+		(step - info.transition_step)
+			.checked_mul(info.step_duration)
+			.and_then(|elapsed| elapsed.checked_add(info.transition_timestamp))
+	}
+
+	/// Step at the given unix time in seconds.
+	fn step_at(&self, timestamp: u64) -> u64 {
+		let info = self.durations.iter()
+			.rev()
+			.find(|info| info.transition_timestamp <= timestamp)
+			.expect("durations are non-empty and the first one starts at time zero; qed");
+		info.transition_step + (timestamp - info.transition_timestamp) / info.step_duration
+	}
+
 	fn duration_remaining(&self) -> Duration {
 		let now = unix_now();
 		let expected_seconds = (self.load() as u64)
 			.checked_add(1)
-			.and_then(|ctr| ctr.checked_mul(self.duration as u64))
+			.and_then(|ctr| self.step_start(ctr))
 			.map(Duration::from_secs);
 
 		match expected_seconds {
@@ -154,7 +240,7 @@ impl Step {
 
 	fn calibrate(&self) {
 		if self.calibrate {
-			let new_step = unix_now().as_secs() / (self.duration as u64);
+			let new_step = self.step_at(unix_now().as_secs());
 			self.inner.store(new_step as usize, AtomicOrdering::SeqCst);
 		}
 	}
@@ -176,11 +262,10 @@ impl Step {
 			Err(None)
 		// wait a bit for blocks in near future
 		} else if given > current {
-			let d = self.duration as u64;
 			Err(Some(OutOfBounds {
 				min: None,
-				max: Some(d * current as u64),
-				found: d * given as u64,
+				max: self.step_start(current as u64),
+				found: self.step_start(given as u64).unwrap_or(u64::max_value()),
 			}))
 		} else {
 			Ok(())
@@ -402,6 +487,7 @@ pub struct AuthorityRound {
 	maximum_uncle_count: usize,
 	empty_steps_transition: u64,
 	maximum_empty_steps: usize,
+	received_step_headers: RwLock<BTreeMap<(usize, Address), Header>>,
 	machine: EthereumMachine,
 }
 
@@ -584,6 +670,14 @@ fn combine_proofs(signal_number: BlockNumber, set_proof: &[u8], finality_proof:
 	stream.out()
 }
 
+/// Proof of a validator signing two different blocks for the same step:
+/// an RLP list of both full headers.
+fn equivocation_proof(first: &Header, second: &Header) -> Bytes {
+	let mut stream = RlpStream::new_list(2);
+	stream.append(first).append(second);
+	stream.out()
+}
+
 fn destructure_proofs(combined: &[u8]) -> Result<(BlockNumber, &[u8], &[u8]), Error> {
 	let rlp = Rlp::new(combined);
 	Ok((
@@ -606,20 +700,25 @@ impl AsMillis for Duration {
 impl AuthorityRound {
 	/// Create a new instance of AuthorityRound engine.
 	pub fn new(our_params: AuthorityRoundParams, machine: EthereumMachine) -> Result<Arc<Self>, Error> {
-		if our_params.step_duration == 0 {
-			error!(target: "engine", "Authority Round step duration can't be zero, aborting");
-			panic!("authority_round: step duration can't be zero")
-		}
+		let durations = match StepDurationInfo::from_transitions(&our_params.step_durations) {
+			Ok(durations) => durations,
+			Err(e) => {
+				error!(target: "engine", "Authority Round {}, aborting", e);
+				panic!("authority_round: {}", e)
+			},
+		};
 		let should_timeout = our_params.start_step.is_none();
-		let initial_step = our_params.start_step.unwrap_or_else(|| (unix_now().as_secs() / (our_params.step_duration as u64))) as usize;
+		let step = Step {
+			inner: AtomicUsize::new(0),
+			calibrate: our_params.start_step.is_none(),
+			durations: durations,
+		};
+		let initial_step = our_params.start_step.unwrap_or_else(|| step.step_at(unix_now().as_secs())) as usize;
+		step.inner.store(initial_step, AtomicOrdering::SeqCst);
 		let engine = Arc::new(
 			AuthorityRound {
 				transition_service: IoService::<()>::start()?,
-				step: Arc::new(Step {
-					inner: AtomicUsize::new(initial_step),
-					calibrate: our_params.start_step.is_none(),
-					duration: our_params.step_duration,
-				}),
+				step: Arc::new(step),
 				can_propose: AtomicBool::new(true),
 				client: RwLock::new(None),
 				signer: Default::default(),
@@ -636,6 +735,7 @@ impl AuthorityRound {
 				maximum_uncle_count: our_params.maximum_uncle_count,
 				empty_steps_transition: our_params.empty_steps_transition,
 				maximum_empty_steps: our_params.maximum_empty_steps,
+				received_step_headers: RwLock::new(BTreeMap::new()),
 				machine: machine,
 			});
 
@@ -656,6 +756,101 @@ impl AuthorityRound {
 	}
 
 
+	/// Push the chain head into the finality checker, returning the hashes of the blocks
+	/// it finalizes, oldest first. `ancestry` yields the unfinalized ancestors of the chain
+	/// head, starting with its parent, and is used to rebuild the finality checker when
+	/// the chain head doesn't extend its subchain.
+	fn build_finality(&self, chain_head: &Header, ancestry: &mut Iterator<Item=Header>) -> Vec<H256> {
+		if self.immediate_transitions { return Vec::new() }
+
+		let client = match self.client.read().as_ref().and_then(|weak| weak.upgrade()) {
+			Some(client) => client,
+			None => {
+				warn!(target: "engine", "Unable to apply ancestry actions: missing client ref.");
+				return Vec::new();
+			}
+		};
+
+		// find most recently finalized blocks.
+		let mut epoch_manager = self.epoch_manager.lock();
+		if !epoch_manager.zoom_to(&*client, &self.machine, &*self.validators, chain_head) {
+			return Vec::new();
+		}
+
+		if epoch_manager.finality_checker.subchain_head() != Some(*chain_head.parent_hash()) {
+			// build new finality checker from unfinalized ancestry of chain head,
+			// not including chain head itself yet.
+			trace!(target: "finality", "Building finality up to parent of {} ({})",
+				chain_head.hash(), chain_head.parent_hash());
+
+			let mut parent_empty_steps_signers = match header_empty_steps_signers(&chain_head, self.empty_steps_transition) {
+				Ok(empty_step_signers) => empty_step_signers,
+				Err(_) => {
+					warn!(target: "finality", "Failed to get empty step signatures from block {}", chain_head.hash());
+					return Vec::new();
+				}
+			};
+
+			let epoch_transition_hash = epoch_manager.epoch_transition_hash;
+			let empty_steps_transition = self.empty_steps_transition;
+
+			// walk the chain within current epoch backwards.
+			// author == ec_recover(sig) known since the blocks are in the DB.
+			// the empty steps messages in a header signal approval of the parent header.
+			let ancestry_iter = ancestry
+				.take_while(|header| header.number() != 0)
+				.map(|header| {
+					let mut signers = vec![*header.author()];
+					signers.extend(parent_empty_steps_signers.drain(..));
+
+					if let Ok(empty_step_signers) = header_empty_steps_signers(&header, empty_steps_transition) {
+						let res = (header.hash(), signers);
+						trace!(target: "finality", "Ancestry iteration: yielding {:?}", res);
+
+						parent_empty_steps_signers = empty_step_signers;
+
+						Some(res)
+					} else {
+						warn!(target: "finality", "Failed to get empty step signatures from block {}", header.hash());
+						None
+					}
+				})
+				.while_some()
+				.take_while(|&(h, _)| h != epoch_transition_hash);
+
+			if let Err(_) = epoch_manager.finality_checker.build_ancestry_subchain(ancestry_iter) {
+				debug!(target: "engine", "inconsistent validator set within epoch");
+				return Vec::new();
+			}
+		}
+
+		epoch_manager.finality_checker.push_hash(chain_head.hash(), vec![*chain_head.author()])
+			.unwrap_or_else(|_| Vec::new())
+	}
+
+	/// Remember a verified header by its step and author. Returns the previously
+	/// received header if the author already proposed a different block for that step.
+	fn check_double_proposal(&self, step: usize, header: &Header) -> Option<Header> {
+		let mut received = self.received_step_headers.write();
+
+		// drop headers of steps too old to be of interest.
+		let oldest_step = self.step.load().saturating_sub(RECEIVED_HEADERS_STEPS);
+		let recent = received.split_off(&(oldest_step, Address::zero()));
+		*received = recent;
+		if step < oldest_step {
+			return None;
+		}
+
+		match received.get(&(step, *header.author())) {
+			Some(first) if first.hash() != header.hash() => return Some(first.clone()),
+			Some(_) => return None,
+			None => {},
+		}
+
+		received.insert((step, *header.author()), header.clone());
+		None
+	}
+
 	fn clear_empty_steps(&self, step: U256) {
 		// clear old `empty_steps` messages
 		self.empty_steps.lock().retain(|e| U256::from(e.step) > step);
@@ -1018,20 +1213,7 @@ impl Engine<EthereumMachine> for AuthorityRound {
 
 		let rewards: Vec<_> = match self.block_reward_contract {
 			Some(ref c) if block.header().number() >= self.block_reward_contract_transition => {
-				// NOTE: this logic should be moved to a function when another
-				//       engine needs support for block reward contract.
-				let mut call = |to, data| {
-					let result = self.machine.execute_as_system(
-						block,
-						to,
-						U256::max_value(), // unbounded gas? maybe make configurable.
-						Some(data),
-					);
-					result.map_err(|e| format!("{}", e))
-				};
-
-				let rewards = c.reward(&benefactors, &mut call)?;
-				rewards.into_iter().map(|(author, amount)| (author, RewardKind::External, amount)).collect()
+				block_reward::contract_rewards(c, &benefactors, block, &self.machine)?
 			},
 			_ => {
 				benefactors.into_iter().map(|(author, reward_kind)| (author, reward_kind, self.block_reward)).collect()
@@ -1075,7 +1257,12 @@ impl Engine<EthereumMachine> for AuthorityRound {
 			|| (header.number() >= self.validate_step_transition && step <= parent_step) {
 			trace!(target: "engine", "Multiple blocks proposed for step {}.", parent_step);
 
-			self.validators.report_malicious(header.author(), set_number, header.number(), Default::default());
+			let proof = if step == parent_step {
+				equivocation_proof(parent, header)
+			} else {
+				Default::default()
+			};
+			self.validators.report_malicious(header.author(), set_number, header.number(), proof);
 			Err(EngineError::DoubleVote(header.author().clone()))?;
 		}
 
@@ -1162,6 +1349,13 @@ impl Engine<EthereumMachine> for AuthorityRound {
 		if res.is_ok() {
 			let header_step = header_step(header, self.empty_steps_transition)?;
 			self.clear_empty_steps(header_step.into());
+
+			// both headers carry a valid signature of the same author for the same step.
+			if let Some(first) = self.check_double_proposal(header_step, header) {
+				trace!(target: "engine", "Author {} proposed multiple blocks for step {}.", header.author(), header_step);
+				let proof = equivocation_proof(&first, header);
+				self.validators.report_malicious(header.author(), header.number(), header.number(), proof);
+			}
 		}
 		res
 	}
@@ -1180,9 +1374,33 @@ impl Engine<EthereumMachine> for AuthorityRound {
 		self.validators.signals_epoch_end(first, header, aux)
 	}
 
+	fn is_epoch_end_light(
+		&self,
+		chain_head: &Header,
+		chain: &super::Headers<Header>,
+		transition_store: &super::PendingTransitionStore,
+	) -> Option<Vec<u8>> {
+		// epochs only matter if we want to support light clients.
+		if self.immediate_transitions { return None }
+
+		// walk the chain within current epoch backwards.
+		let mut hash = *chain_head.parent_hash();
+		let mut ancestry = itertools::repeat_call(move || {
+			chain(hash).map(|header| {
+				hash = *header.parent_hash();
+				header
+			})
+		}).while_some();
+
+		let finalized = self.build_finality(chain_head, &mut ancestry);
+
+		self.is_epoch_end(chain_head, &finalized, chain, transition_store)
+	}
+
 	fn is_epoch_end(
 		&self,
 		chain_head: &Header,
+		finalized: &[H256],
 		chain: &super::Headers<Header>,
 		transition_store: &super::PendingTransitionStore,
 	) -> Option<Vec<u8>> {
@@ -1197,108 +1415,44 @@ impl Engine<EthereumMachine> for AuthorityRound {
 			return Some(change)
 		}
 
-		let client = match self.client.read().as_ref().and_then(|weak| weak.upgrade()) {
-			Some(client) => client,
-			None => {
-				warn!(target: "engine", "Unable to check for epoch end: missing client ref.");
-				return None;
-			}
-		};
-
-		// find most recently finalized blocks, then check transition store for pending transitions.
-		let mut epoch_manager = self.epoch_manager.lock();
-		if !epoch_manager.zoom_to(&*client, &self.machine, &*self.validators, chain_head) {
-			return None;
-		}
-
-		if epoch_manager.finality_checker.subchain_head() != Some(*chain_head.parent_hash()) {
-			// build new finality checker from ancestry of chain head,
-			// not including chain head itself yet.
-			trace!(target: "finality", "Building finality up to parent of {} ({})",
-				chain_head.hash(), chain_head.parent_hash());
-
-			let mut hash = chain_head.parent_hash().clone();
-			let mut parent_empty_steps_signers = match header_empty_steps_signers(&chain_head, self.empty_steps_transition) {
-				Ok(empty_step_signers) => empty_step_signers,
-				Err(_) => {
-					warn!(target: "finality", "Failed to get empty step signatures from block {}", chain_head.hash());
-					return None;
-				}
-			};
-
-			let epoch_transition_hash = epoch_manager.epoch_transition_hash;
-
-			// walk the chain within current epoch backwards.
-			// author == ec_recover(sig) known since the blocks are in the DB.
-			// the empty steps messages in a header signal approval of the parent header.
-			let ancestry_iter = itertools::repeat_call(move || {
-				chain(hash).and_then(|header| {
-					if header.number() == 0 { return None }
-
-					let mut signers = vec![header.author().clone()];
-					signers.extend(parent_empty_steps_signers.drain(..));
-
-					if let Ok(empty_step_signers) = header_empty_steps_signers(&header, self.empty_steps_transition) {
-						let res = (hash, signers);
-						trace!(target: "finality", "Ancestry iteration: yielding {:?}", res);
-
-						hash = header.parent_hash().clone();
-						parent_empty_steps_signers = empty_step_signers;
-
-						Some(res)
-
-					} else {
-						warn!(target: "finality", "Failed to get empty step signatures from block {}", header.hash());
-						None
-					}
-				})
-			})
-				.while_some()
-				.take_while(|&(h, _)| h != epoch_transition_hash);
-
-			if let Err(_) = epoch_manager.finality_checker.build_ancestry_subchain(ancestry_iter) {
-				debug!(target: "engine", "inconsistent validator set within epoch");
-				return None;
-			}
-		}
-
-		{
-			if let Ok(finalized) = epoch_manager.finality_checker.push_hash(chain_head.hash(), vec![chain_head.author().clone()]) {
-				let mut finalized = finalized.into_iter();
-				while let Some(finalized_hash) = finalized.next() {
-					if let Some(pending) = transition_store(finalized_hash) {
-						let finality_proof = ::std::iter::once(finalized_hash)
-							.chain(finalized)
-							.chain(epoch_manager.finality_checker.unfinalized_hashes())
-							.map(|h| if h == chain_head.hash() {
-								// chain closure only stores ancestry, but the chain head is also
-								// unfinalized.
-								chain_head.clone()
-							} else {
-								chain(h).expect("these headers fetched before when constructing finality checker; qed")
-							})
-							.collect::<Vec<Header>>();
-
-						// this gives us the block number for `hash`, assuming it's ancestry.
-						let signal_number = chain_head.number()
-							- finality_proof.len() as BlockNumber
-							+ 1;
-						let finality_proof = ::rlp::encode_list(&finality_proof);
-						epoch_manager.note_new_epoch();
-
-						info!(target: "engine", "Applying validator set change signalled at block {}", signal_number);
-
-						// We turn off can_propose here because upon validator set change there can
-						// be two valid proposers for a single step: one from the old set and
-						// one from the new.
-						//
-						// This way, upon encountering an epoch change, the proposer from the
-						// new set will be forced to wait until the next step to avoid sealing a
-						// block that breaks the invariant that the parent's step < the block's step.
-						self.can_propose.store(false, AtomicOrdering::SeqCst);
-						return Some(combine_proofs(signal_number, &pending.proof, &*finality_proof));
-					}
+		// check transition store for pending transitions against recently finalized blocks.
+		for finalized_hash in finalized {
+			if let Some(pending) = transition_store(*finalized_hash) {
+				// walk the chain backwards from the chain head to the finalized block.
+				// author == ec_recover(sig) known since the blocks are in the DB.
+				let mut finality_proof = Vec::new();
+				let mut header = chain_head.clone();
+				loop {
+					let parent_hash = *header.parent_hash();
+					let is_signal = header.hash() == *finalized_hash;
+					finality_proof.push(header);
+					if is_signal { break }
+
+					header = match chain(parent_hash) {
+						Some(header) => header,
+						None => {
+							warn!(target: "engine", "Unable to build finality proof for block {}", finalized_hash);
+							return None;
+						}
+					};
 				}
+				finality_proof.reverse();
+
+				let signal_number = finality_proof[0].number();
+				let finality_proof = ::rlp::encode_list(&finality_proof);
+				self.epoch_manager.lock().note_new_epoch();
+
+				info!(target: "engine", "Applying validator set change signalled at block {}", signal_number);
+
+				// We turn off can_propose here because upon validator set change there can
+				// be two valid proposers for a single step: one from the old set and
+				// one from the new.
+				//
+				// This way, upon encountering an epoch change, the proposer from the
+				// new set will be forced to wait until the next step to avoid sealing a
+				// block that breaks the invariant that the parent's step < the block's step.
+				self.can_propose.store(false, AtomicOrdering::SeqCst);
+				return Some(combine_proofs(signal_number, &pending.proof, &*finality_proof));
 			}
 		}
 
@@ -1329,6 +1483,10 @@ impl Engine<EthereumMachine> for AuthorityRound {
 		}
 	}
 
+	fn validator_reports(&self) -> Vec<ValidatorReport> {
+		self.validators.reports()
+	}
+
 	fn register_client(&self, client: Weak<EngineClient>) {
 		*self.client.write() = Some(client.clone());
 		self.validators.register_client(client);
@@ -1350,6 +1508,19 @@ impl Engine<EthereumMachine> for AuthorityRound {
 		}
 	}
 
+	fn ancestry_actions(&self, header: &Header, ancestry: &mut Iterator<Item=ExtendedHeader>) -> Vec<AncestryAction> {
+		let finalized = self.build_finality(
+			header,
+			&mut ancestry.take_while(|e| !e.is_finalized).map(|e| e.header),
+		);
+
+		if !finalized.is_empty() {
+			debug!(target: "finality", "Finalizing blocks: {:?}", finalized);
+		}
+
+		finalized.into_iter().map(AncestryAction::MarkFinalized).collect()
+	}
+
 	fn fork_choice(&self, new: &ExtendedHeader, current: &ExtendedHeader) -> super::ForkChoice {
 		super::total_difficulty_fork_choice(new, current)
 	}
@@ -1362,7 +1533,8 @@ mod tests {
 	use hash::keccak;
 	use ethereum_types::{Address, H520, H256, U256};
 	use header::Header;
-	use rlp::encode;
+	use parking_lot::RwLock;
+	use rlp::{encode, Rlp};
 	use block::*;
 	use test_helpers::{
 		generate_dummy_client_with_spec_and_accounts, get_temp_state_db, generate_dummy_client,
@@ -1374,6 +1546,8 @@ mod tests {
 	use engines::{Seal, Engine, EngineError, EthEngine};
 	use engines::validator_set::TestSet;
 	use error::{Error, ErrorKind};
+	use ethkey;
+	use client::{BlockChainClient, BlockInfo, ChainInfo, EngineInfo, ImportSealedBlock, PrepareOpenBlock};
 	use super::{AuthorityRoundParams, AuthorityRound, EmptyStep, SealedEmptyStep};
 
 	#[test]
@@ -1544,7 +1718,7 @@ mod tests {
 	fn reports_skipped() {
 		let last_benign = Arc::new(AtomicUsize::new(0));
 		let params = AuthorityRoundParams {
-			step_duration: 1,
+			step_durations: vec![(0, 1)].into_iter().collect(),
 			start_step: Some(1),
 			validators: Box::new(TestSet::new(Default::default(), last_benign.clone())),
 			validate_score_transition: 0,
@@ -1584,11 +1758,68 @@ mod tests {
 		assert_eq!(last_benign.load(AtomicOrdering::SeqCst), 1);
 	}
 
+	#[test]
+	fn reports_double_proposals() {
+		let last_malicious = Arc::new(AtomicUsize::new(0));
+		let last_proof = Arc::new(RwLock::new(Vec::new()));
+		let params = AuthorityRoundParams {
+			step_durations: vec![(0, 1)].into_iter().collect(),
+			start_step: Some(1),
+			validators: Box::new(TestSet::new_with_proof(last_malicious.clone(), Default::default(), last_proof.clone())),
+			validate_score_transition: 0,
+			validate_step_transition: 0,
+			immediate_transitions: true,
+			maximum_uncle_count_transition: 0,
+			maximum_uncle_count: 0,
+			empty_steps_transition: u64::max_value(),
+			maximum_empty_steps: 0,
+			block_reward: Default::default(),
+			block_reward_contract_transition: 0,
+			block_reward_contract: Default::default(),
+		};
+
+		let aura = {
+			let machine = ::machine::EthereumMachine::regular(Default::default(), Default::default());
+			AuthorityRound::new(params, machine).unwrap()
+		};
+
+		let secret: ethkey::Secret = keccak("1").into();
+		let validator = ethkey::KeyPair::from_secret(secret.clone()).unwrap().address();
+		let signed_header = |gas_limit: u64| {
+			let mut header = Header::default();
+			header.set_number(1);
+			header.set_author(validator);
+			header.set_gas_limit(gas_limit.into());
+			let signature: H520 = ethkey::sign(&secret, &header.bare_hash()).unwrap().into();
+			header.set_seal(vec![encode(&1usize).into_vec(), encode(&(&*signature as &[u8])).into_vec()]);
+			header
+		};
+
+		let first = signed_header(1_000_000);
+		let second = signed_header(2_000_000);
+
+		// Re-importing the same block is fine.
+		assert!(aura.verify_block_external(&first).is_ok());
+		assert!(aura.verify_block_external(&first).is_ok());
+		assert_eq!(last_malicious.load(AtomicOrdering::SeqCst), 0);
+
+		// A different block signed for the same step is reported.
+		assert!(aura.verify_block_external(&second).is_ok());
+		assert_eq!(last_malicious.load(AtomicOrdering::SeqCst), 1);
+
+		// The proof holds both signed headers, first seen first.
+		let proof = last_proof.read().clone();
+		let headers: Vec<Header> = Rlp::new(&proof).as_list().unwrap();
+		assert_eq!(headers.len(), 2);
+		assert_eq!(headers[0].hash(), first.hash());
+		assert_eq!(headers[1].hash(), second.hash());
+	}
+
 	#[test]
 	fn test_uncles_transition() {
 		let last_benign = Arc::new(AtomicUsize::new(0));
 		let params = AuthorityRoundParams {
-			step_duration: 1,
+			step_durations: vec![(0, 1)].into_iter().collect(),
 			start_step: Some(1),
 			validators: Box::new(TestSet::new(Default::default(), last_benign.clone())),
 			validate_score_transition: 0,
@@ -1618,11 +1849,11 @@ mod tests {
     #[test]
     #[should_panic(expected="counter is too high")]
     fn test_counter_increment_too_high() {
-        use super::Step;
+        use super::{Step, StepDurationInfo};
         let step = Step {
             calibrate: false,
             inner: AtomicUsize::new(::std::usize::MAX),
-            duration: 1,
+            durations: vec![StepDurationInfo { transition_step: 0, transition_timestamp: 0, step_duration: 1 }],
         };
         step.increment();
 	}
@@ -1630,21 +1861,53 @@ mod tests {
 	#[test]
 	#[should_panic(expected="counter is too high")]
 	fn test_counter_duration_remaining_too_high() {
-		use super::Step;
+		use super::{Step, StepDurationInfo};
 		let step = Step {
 			calibrate: false,
 			inner: AtomicUsize::new(::std::usize::MAX),
-			duration: 1,
+			durations: vec![StepDurationInfo { transition_step: 0, transition_timestamp: 0, step_duration: 1 }],
 		};
 		step.duration_remaining();
 	}
 
+	#[test]
+	fn test_step_duration_transitions() {
+		use super::{Step, StepDurationInfo};
+		let durations = vec![(0, 5), (100, 2), (200, 10)].into_iter().collect();
+		let step = Step {
+			calibrate: false,
+			inner: AtomicUsize::new(0),
+			durations: StepDurationInfo::from_transitions(&durations).unwrap(),
+		};
+
+		// step 100 starts after 100 steps of 5 seconds, step 200 after another 100 of 2 seconds.
+		assert_eq!(step.step_start(99), Some(495));
+		assert_eq!(step.step_start(100), Some(500));
+		assert_eq!(step.step_start(101), Some(502));
+		assert_eq!(step.step_start(201), Some(710));
+		assert_eq!(step.step_at(499), 99);
+		assert_eq!(step.step_at(500), 100);
+		assert_eq!(step.step_at(709), 200);
+		assert_eq!(step.step_at(710), 201);
+
+		// the step is monotonic in time across transitions.
+		let steps: Vec<u64> = (0..1000).map(|t| step.step_at(t)).collect();
+		assert!(steps.windows(2).all(|w| w[0] <= w[1] && w[1] - w[0] <= 1));
+	}
+
+	#[test]
+	fn test_step_duration_transitions_start_at_zero() {
+		use super::StepDurationInfo;
+		let durations = vec![(10, 5)].into_iter().collect();
+		assert!(StepDurationInfo::from_transitions(&durations).is_err());
+	}
+
 	#[test]
 	#[should_panic(expected="authority_round: step duration can't be zero")]
 	fn test_step_duration_zero() {
 		let last_benign = Arc::new(AtomicUsize::new(0));
 		let params = AuthorityRoundParams {
-			step_duration: 0,
+			step_durations: vec![(0, 0)].into_iter().collect(),
 			start_step: Some(1),
 			validators: Box::new(TestSet::new(Default::default(), last_benign.clone())),
 			validate_score_transition: 0,
@@ -2003,4 +2266,39 @@ mod tests {
 			addr1_balance + (1000 + 0).into() + (1000 + 2).into(),
 		)
 	}
+
+	#[test]
+	fn finalizes_imported_blocks_of_single_validator() {
+		let tap = Arc::new(AccountProvider::transient_provider());
+		let validator = tap.insert_account(keccak("1").into(), "").unwrap();
+
+		let client = generate_dummy_client_with_spec_and_accounts(Spec::new_test_round_single_validator, None);
+		let engine = client.engine();
+		engine.register_client(Arc::downgrade(&client) as _);
+		engine.set_signer(tap.clone(), validator, "".into());
+		let genesis_header = client.best_block_header();
+
+		// empty steps are broadcast at steps 2 and 3, at step 4 the maximum is reached
+		// and the empty block is sealed with both of them.
+		let mut sealed = None;
+		for _ in 0..3 {
+			let b = client.prepare_open_block(validator, (3141562.into(), 31415620.into()), vec![]).close_and_lock();
+			if let Seal::Regular(seal) = engine.generate_seal(b.block(), &genesis_header) {
+				sealed = Some((b, seal));
+				break;
+			}
+			engine.step();
+		}
+		let (b, seal) = sealed.expect("the validator seals once enough empty steps are collected");
+		assert_eq!(seal[0], encode(&4usize).into_vec());
+		assert_eq!(Rlp::new(&seal[2]).item_count().unwrap(), 2);
+
+		let sealed = b.try_seal(engine, seal).unwrap_or_else(|_| panic!("seal should be valid"));
+		let hash = sealed.header().hash();
+		client.import_sealed_block(sealed).unwrap();
+
+		// the signature of the only validator finalizes the block right away.
+		assert_eq!(client.chain_info().best_block_hash, hash);
+		assert_eq!(client.finalized_block(), Some(hash));
+	}
 }
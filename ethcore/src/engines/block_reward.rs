@@ -21,8 +21,9 @@ use ethabi;
 use ethabi::ParamType;
 use ethereum_types::{H160, Address, U256};
 
+use block::ExecutedBlock;
 use error::Error;
-use machine::WithRewards;
+use machine::{EthereumMachine, WithRewards};
 use parity_machine::{Machine, WithBalances};
 use trace;
 use super::SystemCall;
@@ -63,19 +64,16 @@ impl Into<trace::RewardType> for RewardKind {
 }
 
 /// A client for the block reward contract.
+#[derive(PartialEq, Debug)]
 pub struct BlockRewardContract {
 	/// Address of the contract.
 	address: Address,
-	block_reward_contract: block_reward_contract::BlockReward,
 }
 
 impl BlockRewardContract {
 	/// Create a new block reward contract client targeting the given address.
 	pub fn new(address: Address) -> BlockRewardContract {
-		BlockRewardContract {
-			address,
-			block_reward_contract: block_reward_contract::BlockReward::default(),
-		}
+		BlockRewardContract { address }
 	}
 
 	/// Calls the block reward contract with the given benefactors list (and associated reward kind)
@@ -87,7 +85,8 @@ impl BlockRewardContract {
 		benefactors: &[(Address, RewardKind)],
 		caller: &mut SystemCall,
 	) -> Result<Vec<(Address, U256)>, Error> {
-		let reward = self.block_reward_contract.functions().reward();
+		let block_reward_contract = block_reward_contract::BlockReward::default();
+		let reward = block_reward_contract.functions().reward();
 
 		let input = reward.input(
 			benefactors.iter().map(|&(address, _)| H160::from(address)),
@@ -127,6 +126,28 @@ impl BlockRewardContract {
 	}
 }
 
+/// Calls the block reward contract as the system address (with unbounded gas) on the given
+/// block and returns the resulting reward allocation, attributed as external rewards.
+pub fn contract_rewards(
+	contract: &BlockRewardContract,
+	benefactors: &[(Address, RewardKind)],
+	block: &mut ExecutedBlock,
+	machine: &EthereumMachine,
+) -> Result<Vec<(Address, RewardKind, U256)>, Error> {
+	let mut call = |to, data| {
+		let result = machine.execute_as_system(
+			block,
+			to,
+			U256::max_value(), // unbounded gas? maybe make configurable.
+			Some(data),
+		);
+		result.map_err(|e| format!("{}", e))
+	};
+
+	let rewards = contract.reward(benefactors, &mut call)?;
+	Ok(rewards.into_iter().map(|(author, amount)| (author, RewardKind::External, amount)).collect())
+}
+
 /// Applies the given block rewards, i.e. adds the given balance to each benefactors' address.
 /// If tracing is enabled the operations are recorded.
 pub fn apply_block_rewards<M: Machine + WithBalances + WithRewards>(
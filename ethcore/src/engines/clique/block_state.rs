@@ -0,0 +1,269 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Clique signer set and pending votes at a given block.
+
+use std::collections::{BTreeSet, VecDeque};
+
+use ethereum_types::{H64, U256, Address};
+use engines::EngineError;
+use error::Error;
+use header::Header;
+
+use super::{NONCE_AUTH_VOTE, NONCE_DROP_VOTE, DIFF_INTURN, DIFF_NOTURN};
+
+/// Kind of a signer vote.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoteType {
+	/// Vote to add the beneficiary to the signers.
+	Add,
+	/// Vote to remove the beneficiary from the signers.
+	Remove,
+}
+
+impl VoteType {
+	/// Decode the vote kind from the block's nonce.
+	pub fn from_nonce(nonce: H64) -> Result<Self, Error> {
+		if nonce == NONCE_AUTH_VOTE.into() {
+			Ok(VoteType::Add)
+		} else if nonce == NONCE_DROP_VOTE.into() {
+			Ok(VoteType::Remove)
+		} else {
+			Err(EngineError::CliqueInvalidNonce(nonce).into())
+		}
+	}
+
+	/// Encode the vote kind as the block's nonce.
+	pub fn as_nonce(&self) -> H64 {
+		match *self {
+			VoteType::Add => NONCE_AUTH_VOTE.into(),
+			VoteType::Remove => NONCE_DROP_VOTE.into(),
+		}
+	}
+}
+
+/// A vote cast by a signer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Vote {
+	signer: Address,
+	beneficiary: Address,
+	kind: VoteType,
+}
+
+/// Clique state after applying a block: the authorized signers,
+/// the votes cast since the last checkpoint and the recent signers.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CliqueBlockState {
+	/// Authorized signers.
+	signers: BTreeSet<Address>,
+	/// Votes cast since the last checkpoint, in order.
+	votes: Vec<Vote>,
+	/// Signers of the most recent blocks, newest first.
+	recent_signers: VecDeque<Address>,
+}
+
+impl CliqueBlockState {
+	/// Create the state at a checkpoint block with given signers.
+	pub fn new(signers: BTreeSet<Address>) -> Self {
+		CliqueBlockState {
+			signers: signers,
+			votes: Vec::new(),
+			recent_signers: VecDeque::new(),
+		}
+	}
+
+	/// Authorized signers, sorted by address.
+	pub fn signers(&self) -> &BTreeSet<Address> {
+		&self.signers
+	}
+
+	/// Whether the address is an authorized signer.
+	pub fn is_authorized(&self, signer: &Address) -> bool {
+		self.signers.contains(signer)
+	}
+
+	/// Whether the signer may sign the next block, i.e. it is authorized
+	/// and hasn't signed any of the last `signers / 2` blocks.
+	pub fn can_sign(&self, signer: &Address) -> bool {
+		self.is_authorized(signer) && !self.recent_signers.iter().take(self.recent_limit() - 1).any(|s| s == signer)
+	}
+
+	/// Whether it is the signer's turn to sign the block with given number.
+	pub fn is_inturn(&self, number: u64, signer: &Address) -> bool {
+		self.turn_distance(number, signer) == Some(0)
+	}
+
+	/// Number of signers whose turn to sign the block with given number comes
+	/// before `signer`'s, zero for the in-turn signer.
+	pub fn turn_distance(&self, number: u64, signer: &Address) -> Option<u64> {
+		let offset = self.signers.iter().position(|s| s == signer)? as u64;
+		let len = self.signers.len() as u64;
+		Some((offset + len - number % len) % len)
+	}
+
+	/// Expected difficulty of the block with given number signed by `signer`.
+	pub fn difficulty(&self, number: u64, signer: &Address) -> U256 {
+		if self.is_inturn(number, signer) { DIFF_INTURN } else { DIFF_NOTURN }
+	}
+
+	/// Record the signer of the checkpoint the state was created at or of a block preceding it.
+	/// Signers have to be given newest first.
+	pub fn push_recent_signer(&mut self, signer: Address) {
+		if self.recent_signers.len() < self.recent_limit() {
+			self.recent_signers.push_back(signer);
+		}
+	}
+
+	/// Number of recent blocks (including the current one) a signer may sign at most one of.
+	pub fn recent_limit(&self) -> usize {
+		self.signers.len() / 2 + 1
+	}
+
+	/// Apply the block signed by `creator` to the state. Checkpoint blocks reset the
+	/// pending votes, other blocks cast a vote on their beneficiary.
+	pub fn apply(&mut self, header: &Header, creator: Address, is_checkpoint: bool) -> Result<(), Error> {
+		if !self.can_sign(&creator) {
+			return Err(match self.is_authorized(&creator) {
+				true => EngineError::CliqueTooRecentlySigned(creator),
+				false => EngineError::NotAuthorized(creator),
+			}.into());
+		}
+
+		if is_checkpoint {
+			self.votes.clear();
+		} else {
+			let nonce = ::rlp::Rlp::new(&header.seal()[1]).as_val::<H64>()?;
+			self.cast_vote(creator, *header.author(), VoteType::from_nonce(nonce)?);
+		}
+
+		self.recent_signers.push_front(creator);
+		let limit = self.recent_limit();
+		self.recent_signers.truncate(limit);
+
+		Ok(())
+	}
+
+	fn cast_vote(&mut self, signer: Address, beneficiary: Address, kind: VoteType) {
+		// a new vote replaces the signer's previous vote on the beneficiary.
+		self.votes.retain(|v| v.signer != signer || v.beneficiary != beneficiary);
+
+		// votes which wouldn't change the signer set are ignored.
+		let is_signer = self.signers.contains(&beneficiary);
+		match (kind, is_signer) {
+			(VoteType::Add, false) | (VoteType::Remove, true) => {},
+			_ => return,
+		}
+
+		self.votes.push(Vote { signer, beneficiary, kind });
+
+		let tally = self.votes.iter().filter(|v| v.beneficiary == beneficiary && v.kind == kind).count();
+		if tally <= self.signers.len() / 2 {
+			return;
+		}
+
+		trace!(target: "engine", "Clique vote passed: {:?} {}", kind, beneficiary);
+		match kind {
+			VoteType::Add => {
+				self.signers.insert(beneficiary);
+			},
+			VoteType::Remove => {
+				self.signers.remove(&beneficiary);
+				self.votes.retain(|v| v.signer != beneficiary);
+			},
+		}
+		self.votes.retain(|v| v.beneficiary != beneficiary);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::collections::BTreeSet;
+	use ethereum_types::{H64, H256, Address};
+	use header::Header;
+	use super::CliqueBlockState;
+	use super::super::{NONCE_AUTH_VOTE, NONCE_DROP_VOTE, DIFF_INTURN, DIFF_NOTURN};
+
+	fn vote(beneficiary: Address, nonce: [u8; 8]) -> Header {
+		let mut header = Header::default();
+		header.set_author(beneficiary);
+		header.set_seal(vec![::rlp::encode(&H256::zero()).into_vec(), ::rlp::encode(&H64::from(nonce)).into_vec()]);
+		header
+	}
+
+	fn state(signers: &[u64]) -> CliqueBlockState {
+		CliqueBlockState::new(signers.iter().map(|s| Address::from(*s)).collect::<BTreeSet<_>>())
+	}
+
+	#[test]
+	fn should_add_signer_after_majority_vote() {
+		let mut state = state(&[1, 2, 3]);
+		let new_signer = Address::from(4);
+
+		state.apply(&vote(new_signer, NONCE_AUTH_VOTE), Address::from(1), false).unwrap();
+		assert!(!state.is_authorized(&new_signer));
+		state.apply(&vote(new_signer, NONCE_AUTH_VOTE), Address::from(2), false).unwrap();
+		assert!(state.is_authorized(&new_signer));
+	}
+
+	#[test]
+	fn should_drop_signer_and_discard_its_votes() {
+		let mut state = state(&[1, 2, 3, 4]);
+
+		// signer 4 votes to add 5, the others drop signer 4.
+		state.apply(&vote(Address::from(5), NONCE_AUTH_VOTE), Address::from(4), false).unwrap();
+		state.apply(&vote(Address::from(4), NONCE_DROP_VOTE), Address::from(1), false).unwrap();
+		state.apply(&vote(Address::from(4), NONCE_DROP_VOTE), Address::from(2), false).unwrap();
+		assert!(state.is_authorized(&Address::from(4)));
+		state.apply(&vote(Address::from(4), NONCE_DROP_VOTE), Address::from(3), false).unwrap();
+		assert!(!state.is_authorized(&Address::from(4)));
+
+		// the vote of the dropped signer doesn't count anymore.
+		state.apply(&vote(Address::from(5), NONCE_AUTH_VOTE), Address::from(1), false).unwrap();
+		assert!(!state.is_authorized(&Address::from(5)));
+	}
+
+	#[test]
+	fn should_discard_votes_at_checkpoint() {
+		let mut state = state(&[1, 2, 3]);
+
+		state.apply(&vote(Address::from(4), NONCE_AUTH_VOTE), Address::from(1), false).unwrap();
+		state.apply(&vote(Address::zero(), NONCE_DROP_VOTE), Address::from(2), true).unwrap();
+		state.apply(&vote(Address::from(4), NONCE_AUTH_VOTE), Address::from(3), false).unwrap();
+
+		assert!(!state.is_authorized(&Address::from(4)));
+	}
+
+	#[test]
+	fn should_reject_recent_and_unauthorized_signers() {
+		let mut state = state(&[1, 2, 3]);
+
+		state.apply(&vote(Address::zero(), NONCE_DROP_VOTE), Address::from(1), false).unwrap();
+		assert!(state.apply(&vote(Address::zero(), NONCE_DROP_VOTE), Address::from(1), false).is_err());
+		assert!(state.apply(&vote(Address::zero(), NONCE_DROP_VOTE), Address::from(4), false).is_err());
+		state.apply(&vote(Address::zero(), NONCE_DROP_VOTE), Address::from(2), false).unwrap();
+		state.apply(&vote(Address::zero(), NONCE_DROP_VOTE), Address::from(1), false).unwrap();
+	}
+
+	#[test]
+	fn should_compute_difficulty_by_turn() {
+		let state = state(&[1, 2, 3]);
+
+		assert_eq!(state.difficulty(4, &Address::from(2)), DIFF_INTURN);
+		assert_eq!(state.difficulty(4, &Address::from(3)), DIFF_NOTURN);
+		assert_eq!(state.turn_distance(4, &Address::from(3)), Some(1));
+		assert_eq!(state.turn_distance(4, &Address::from(4)), None);
+	}
+}
@@ -0,0 +1,806 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Clique (EIP-225) proof-of-authority consensus engine.
+//!
+//! Blocks are signed by one of the authorized signers, with the signature appended
+//! to the header's extra data. Signers vote on adding or removing other signers
+//! through the block's beneficiary and nonce, and every `epoch` blocks a checkpoint
+//! block resets the votes and lists the current signers in its extra data.
+//! Blocks sealed locally vote on one of the pending proposals, see `Clique::propose`.
+
+mod block_state;
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Weak, Arc};
+use std::time::{Duration, UNIX_EPOCH};
+
+use account_provider::AccountProvider;
+use block::*;
+use client::{BlockId, EngineClient};
+use engines::{Engine, Seal, EngineError};
+use error::{BlockError, Error};
+use ethereum_types::{H64, H256, H520, U256, Address};
+use ethjson;
+use ethkey::{self, Signature};
+use hash::{keccak, KECCAK_EMPTY_LIST_RLP};
+use header::{Header, ExtendedHeader};
+use io::{IoContext, IoHandler, IoService, TimerToken};
+use lru_cache::LruCache;
+use machine::EthereumMachine;
+use parking_lot::RwLock;
+use rlp::{Rlp, RlpStream};
+use unexpected::{Mismatch, OutOfBounds};
+use super::signer::EngineSigner;
+
+use self::block_state::CliqueBlockState;
+pub use self::block_state::VoteType;
+
+/// Number of extra data bytes reserved for the signer vanity.
+pub const VANITY_LENGTH: usize = 32;
+/// Number of extra data bytes reserved for the signer's signature.
+pub const SIGNATURE_LENGTH: usize = 65;
+/// Nonce of a vote to add the beneficiary to the signers.
+pub const NONCE_AUTH_VOTE: [u8; 8] = [0xff; 8];
+/// Nonce of a vote to remove the beneficiary from the signers.
+pub const NONCE_DROP_VOTE: [u8; 8] = [0; 8];
+/// Difficulty of a block signed in turn.
+pub const DIFF_INTURN: U256 = U256([2, 0, 0, 0]);
+/// Difficulty of a block signed out of turn.
+pub const DIFF_NOTURN: U256 = U256([1, 0, 0, 0]);
+
+/// Default number of blocks after which a checkpoint is made.
+const DEFAULT_EPOCH_LENGTH: u64 = 30_000;
+/// Default minimal time between blocks in seconds.
+const DEFAULT_PERIOD: u64 = 15;
+/// Number of cached signer sets and recovered signers.
+const STATE_CACHE_NUM: usize = 128;
+/// Delay of an out-of-turn signer per signer whose turn comes before, in milliseconds.
+const OUT_OF_TURN_DELAY_MS: u64 = 500;
+/// Interval of sealing attempts in milliseconds.
+const SEALING_TIMER_INTERVAL_MS: u64 = 500;
+
+/// `Clique` params.
+#[derive(Debug, PartialEq)]
+pub struct CliqueParams {
+	/// Minimal time between blocks in seconds.
+	pub period: u64,
+	/// Number of blocks after which a checkpoint is made.
+	pub epoch: u64,
+}
+
+impl From<ethjson::spec::CliqueParams> for CliqueParams {
+	fn from(p: ethjson::spec::CliqueParams) -> Self {
+		CliqueParams {
+			period: p.period.map_or(DEFAULT_PERIOD, Into::into),
+			epoch: p.epoch.map_or(DEFAULT_EPOCH_LENGTH, Into::into),
+		}
+	}
+}
+
+/// Hash of the header as signed by the block's signer: the header with the
+/// signature stripped from the extra data.
+fn sig_hash(header: &Header) -> Result<H256, Error> {
+	let extra_data = header.extra_data();
+	if extra_data.len() < SIGNATURE_LENGTH {
+		return Err(EngineError::CliqueMissingSignature.into());
+	}
+
+	let mut s = RlpStream::new_list(13 + header.seal().len());
+	s.append(header.parent_hash());
+	s.append(header.uncles_hash());
+	s.append(header.author());
+	s.append(header.state_root());
+	s.append(header.transactions_root());
+	s.append(header.receipts_root());
+	s.append(header.log_bloom());
+	s.append(header.difficulty());
+	s.append(&header.number());
+	s.append(header.gas_limit());
+	s.append(header.gas_used());
+	s.append(&header.timestamp());
+	s.append(&&extra_data[..extra_data.len() - SIGNATURE_LENGTH]);
+	for field in header.seal() {
+		s.append_raw(field, 1);
+	}
+
+	Ok(keccak(s.out()))
+}
+
+/// Recover the signer of the block from the signature in its extra data.
+fn recover_creator(header: &Header) -> Result<Address, Error> {
+	let extra_data = header.extra_data();
+	if extra_data.len() < VANITY_LENGTH {
+		return Err(EngineError::CliqueMissingVanity.into());
+	}
+	if extra_data.len() < VANITY_LENGTH + SIGNATURE_LENGTH {
+		return Err(EngineError::CliqueMissingSignature.into());
+	}
+
+	let signature = H520::from_slice(&extra_data[extra_data.len() - SIGNATURE_LENGTH..]);
+	let public = ethkey::recover(&signature.into(), &sig_hash(header)?)?;
+	Ok(ethkey::public_to_address(&public))
+}
+
+/// Signers listed in the extra data of a checkpoint block.
+fn extract_signers(header: &Header) -> Result<BTreeSet<Address>, Error> {
+	let extra_data = header.extra_data();
+	if extra_data.len() < VANITY_LENGTH + SIGNATURE_LENGTH {
+		return Err(EngineError::CliqueMissingSignature.into());
+	}
+
+	let signers = &extra_data[VANITY_LENGTH..extra_data.len() - SIGNATURE_LENGTH];
+	if signers.is_empty() || signers.len() % 20 != 0 {
+		return Err(EngineError::CliqueCheckpointInvalidSigners(signers.len()).into());
+	}
+
+	Ok(signers.chunks(20).map(Address::from_slice).collect())
+}
+
+fn unix_now() -> Duration {
+	UNIX_EPOCH.elapsed().expect("Valid time has to be set in your system.")
+}
+
+/// Engine using `Clique` proof-of-authority consensus.
+pub struct Clique {
+	epoch_length: u64,
+	period: u64,
+	machine: EthereumMachine,
+	client: RwLock<Option<Weak<EngineClient>>>,
+	signer: RwLock<EngineSigner>,
+	block_state_by_hash: RwLock<LruCache<H256, CliqueBlockState>>,
+	creator_by_hash: RwLock<LruCache<H256, Address>>,
+	proposals: RwLock<BTreeMap<Address, VoteType>>,
+	step_service: IoService<()>,
+}
+
+impl Clique {
+	/// Create a new instance of Clique engine.
+	pub fn new(our_params: CliqueParams, machine: EthereumMachine) -> Result<Arc<Self>, Error> {
+		if our_params.epoch == 0 {
+			error!(target: "engine", "Clique epoch length can't be zero, aborting");
+			panic!("clique: epoch length can't be zero")
+		}
+
+		let engine = Arc::new(Clique {
+			epoch_length: our_params.epoch,
+			period: our_params.period,
+			machine: machine,
+			client: RwLock::new(None),
+			signer: Default::default(),
+			block_state_by_hash: RwLock::new(LruCache::new(STATE_CACHE_NUM)),
+			creator_by_hash: RwLock::new(LruCache::new(STATE_CACHE_NUM)),
+			proposals: RwLock::new(BTreeMap::new()),
+			step_service: IoService::<()>::start()?,
+		});
+
+		let handler = SealingTimer { engine: Arc::downgrade(&engine) };
+		engine.step_service.register_handler(Arc::new(handler))?;
+		Ok(engine)
+	}
+
+	/// Propose to add the address to or remove it from the signers. Blocks sealed
+	/// locally keep voting on the proposal until it is discarded.
+	pub fn propose(&self, address: Address, vote: VoteType) {
+		self.proposals.write().insert(address, vote);
+	}
+
+	/// Stop voting on the proposal for given address.
+	pub fn discard(&self, address: &Address) {
+		self.proposals.write().remove(address);
+	}
+
+	/// Pending proposals.
+	pub fn proposals(&self) -> BTreeMap<Address, VoteType> {
+		self.proposals.read().clone()
+	}
+
+	/// Pick the proposal to vote on in the block with given number. Proposals which
+	/// wouldn't change the signers are skipped, the others take turns.
+	fn proposal_to_vote(&self, number: u64, state: &CliqueBlockState) -> Option<(Address, VoteType)> {
+		let proposals = self.proposals.read();
+		let proposals: Vec<_> = proposals.iter()
+			.filter(|&(address, vote)| match *vote {
+				VoteType::Add => !state.is_authorized(address),
+				VoteType::Remove => state.is_authorized(address),
+			})
+			.collect();
+
+		if proposals.is_empty() {
+			return None;
+		}
+		let (address, vote) = proposals[(number % proposals.len() as u64) as usize];
+		Some((*address, *vote))
+	}
+
+	fn is_checkpoint(&self, number: u64) -> bool {
+		number % self.epoch_length == 0
+	}
+
+	/// Recover the signer of the block, using the cache of recovered signers.
+	fn recover_creator(&self, header: &Header) -> Result<Address, Error> {
+		let hash = header.hash();
+		if let Some(creator) = self.creator_by_hash.write().get_mut(&hash) {
+			return Ok(*creator);
+		}
+
+		let creator = recover_creator(header)?;
+		self.creator_by_hash.write().insert(hash, creator);
+		Ok(creator)
+	}
+
+	fn header(&self, hash: &H256) -> Result<Header, Error> {
+		let client = self.client.read().as_ref().and_then(Weak::upgrade).ok_or(EngineError::RequiresClient)?;
+		let header = client.block_header(BlockId::Hash(*hash)).ok_or(EngineError::CliqueMissingAncestor(*hash))?;
+		Ok(header.decode()?)
+	}
+
+	/// Clique state after applying the block with given hash.
+	fn state(&self, hash: &H256) -> Result<CliqueBlockState, Error> {
+		if let Some(state) = self.block_state_by_hash.write().get_mut(hash) {
+			return Ok(state.clone());
+		}
+
+		let header = self.header(hash)?;
+		self.state_for_header(&header)
+	}
+
+	/// Clique state after applying the given block. Uncached states are rebuilt
+	/// by applying the ancestors of the block down to the last checkpoint.
+	fn state_for_header(&self, header: &Header) -> Result<CliqueBlockState, Error> {
+		// blocks to apply on top of the base state, newest first.
+		let mut chain = Vec::new();
+		let mut current = header.clone();
+		let mut state = loop {
+			if let Some(state) = self.block_state_by_hash.write().get_mut(&current.hash()) {
+				break state.clone();
+			}
+			if self.is_checkpoint(current.number()) {
+				break self.checkpoint_state(&current)?;
+			}
+
+			let parent = self.header(current.parent_hash())?;
+			chain.push(current);
+			current = parent;
+		};
+
+		for header in chain.into_iter().rev() {
+			let creator = self.recover_creator(&header)?;
+			state.apply(&header, creator, false)?;
+			self.block_state_by_hash.write().insert(header.hash(), state.clone());
+		}
+
+		Ok(state)
+	}
+
+	/// Clique state at a checkpoint block, with the signers listed in its extra data.
+	fn checkpoint_state(&self, checkpoint: &Header) -> Result<CliqueBlockState, Error> {
+		let mut state = CliqueBlockState::new(extract_signers(checkpoint)?);
+
+		// the signers of the checkpoint and the blocks right before it are still
+		// recent. The genesis block isn't signed.
+		let recent_limit = state.recent_limit();
+		let mut current = checkpoint.clone();
+		for i in 0..recent_limit {
+			if current.number() == 0 {
+				break;
+			}
+			state.push_recent_signer(self.recover_creator(&current)?);
+			if i + 1 < recent_limit {
+				current = self.header(current.parent_hash())?;
+			}
+		}
+
+		self.block_state_by_hash.write().insert(checkpoint.hash(), state.clone());
+		Ok(state)
+	}
+}
+
+struct SealingTimer {
+	engine: Weak<Clique>,
+}
+
+const ENGINE_TIMEOUT_TOKEN: TimerToken = 23;
+
+impl IoHandler<()> for SealingTimer {
+	fn initialize(&self, io: &IoContext<()>) {
+		io.register_timer(ENGINE_TIMEOUT_TOKEN, Duration::from_millis(SEALING_TIMER_INTERVAL_MS))
+			.unwrap_or_else(|e| warn!(target: "engine", "Failed to start Clique sealing timer: {}.", e))
+	}
+
+	fn timeout(&self, _io: &IoContext<()>, timer: TimerToken) {
+		if timer == ENGINE_TIMEOUT_TOKEN {
+			if let Some(engine) = self.engine.upgrade() {
+				engine.step();
+			}
+		}
+	}
+}
+
+impl Engine<EthereumMachine> for Clique {
+	fn name(&self) -> &str { "Clique" }
+
+	fn machine(&self) -> &EthereumMachine { &self.machine }
+
+	// Two fields - mix hash and nonce (the vote)
+	fn seal_fields(&self, _header: &Header) -> usize { 2 }
+
+	/// Additional engine-specific information for the user/developer concerning `header`.
+	fn extra_info(&self, header: &Header) -> BTreeMap<String, String> {
+		let signer = self.recover_creator(header).map(|s| format!("{:?}", s)).unwrap_or_default();
+		map![
+			"signer".into() => signer
+		]
+	}
+
+	fn seals_internally(&self) -> Option<bool> {
+		Some(self.signer.read().is_some())
+	}
+
+	/// Attempt to seal the block internally once its timestamp is reached.
+	/// Out-of-turn signers wait a bit longer to give the in-turn signer precedence.
+	fn generate_seal(&self, block: &ExecutedBlock, parent: &Header) -> Seal {
+		let header = block.header();
+
+		// without a period, blocks are only sealed when there are transactions.
+		if self.period == 0 && block.transactions().is_empty() {
+			return Seal::None;
+		}
+
+		let signer = match self.signer.read().address() {
+			Some(signer) => signer,
+			None => return Seal::None,
+		};
+		if *header.author() != signer {
+			trace!(target: "engine", "generate_seal: block author {} isn't the engine signer {}", header.author(), signer);
+			return Seal::None;
+		}
+
+		let state = match self.state_for_header(parent) {
+			Ok(state) => state,
+			Err(e) => {
+				warn!(target: "engine", "generate_seal: failed to get signers at block {}: {}", parent.hash(), e);
+				return Seal::None;
+			},
+		};
+		if !state.can_sign(&signer) {
+			trace!(target: "engine", "generate_seal: {} is not authorized or has signed too recently", signer);
+			return Seal::None;
+		}
+
+		let distance = state.turn_distance(header.number(), &signer).unwrap_or(0);
+		let seal_at = Duration::from_secs(header.timestamp()) + Duration::from_millis(distance * OUT_OF_TURN_DELAY_MS);
+		if unix_now() < seal_at {
+			return Seal::None;
+		}
+
+		// the vote is filled in when the block is signed.
+		Seal::Regular(vec![
+			::rlp::encode(&H256::zero()).into_vec(),
+			::rlp::encode(&H64::from(NONCE_DROP_VOTE)).into_vec(),
+		])
+	}
+
+	/// Cast a vote on one of the pending proposals through the beneficiary and nonce,
+	/// then sign the header with the signature appended to its extra data.
+	/// Checkpoint blocks never vote.
+	fn on_seal_block(&self, block: &mut ExecutedBlock) -> Result<(), Error> {
+		let number = block.header().number();
+		let vote = match self.is_checkpoint(number) {
+			true => None,
+			false => {
+				let parent_state = self.state(block.header().parent_hash())?;
+				self.proposal_to_vote(number, &parent_state)
+			},
+		};
+		let (beneficiary, vote) = vote.unwrap_or((Address::zero(), VoteType::Remove));
+
+		let header = block.header_mut();
+		header.set_author(beneficiary);
+		header.set_seal(vec![
+			::rlp::encode(&H256::zero()).into_vec(),
+			::rlp::encode(&vote.as_nonce()).into_vec(),
+		]);
+
+		let signature = self.sign(sig_hash(header)?)?;
+		let mut extra_data = header.extra_data().clone();
+		let len = extra_data.len();
+		if len < VANITY_LENGTH + SIGNATURE_LENGTH {
+			return Err(EngineError::CliqueMissingSignature.into());
+		}
+		extra_data[len - SIGNATURE_LENGTH..].copy_from_slice(&H520::from(signature)[..]);
+		header.set_extra_data(extra_data);
+
+		Ok(())
+	}
+
+	fn verify_local_seal(&self, _header: &Header) -> Result<(), Error> {
+		Ok(())
+	}
+
+	fn verify_block_basic(&self, header: &Header) -> Result<(), Error> {
+		// genesis is never verified, and its extra data only needs to list the signers.
+		if header.number() == 0 {
+			return Ok(());
+		}
+
+		if header.seal().len() != 2 {
+			return Err(EngineError::BadSealFieldSize(OutOfBounds {
+				min: Some(2),
+				max: Some(2),
+				found: header.seal().len(),
+			}).into());
+		}
+
+		let extra_data = header.extra_data();
+		if extra_data.len() < VANITY_LENGTH {
+			return Err(EngineError::CliqueMissingVanity.into());
+		}
+		if extra_data.len() < VANITY_LENGTH + SIGNATURE_LENGTH {
+			return Err(EngineError::CliqueMissingSignature.into());
+		}
+
+		let signers_len = extra_data.len() - VANITY_LENGTH - SIGNATURE_LENGTH;
+		let is_checkpoint = self.is_checkpoint(header.number());
+		if is_checkpoint && (signers_len == 0 || signers_len % 20 != 0) {
+			return Err(EngineError::CliqueCheckpointInvalidSigners(signers_len).into());
+		}
+		if !is_checkpoint && signers_len != 0 {
+			return Err(EngineError::CliqueExtraSigners.into());
+		}
+
+		let nonce = Rlp::new(&header.seal()[1]).as_val::<H64>()?;
+		let vote = VoteType::from_nonce(nonce)?;
+		if is_checkpoint && (!header.author().is_zero() || vote != VoteType::Remove) {
+			return Err(EngineError::CliqueCheckpointVote.into());
+		}
+
+		let mix_hash = Rlp::new(&header.seal()[0]).as_val::<H256>()?;
+		if !mix_hash.is_zero() {
+			return Err(BlockError::MismatchedH256SealElement(Mismatch { expected: H256::zero(), found: mix_hash }).into());
+		}
+
+		if *header.uncles_hash() != KECCAK_EMPTY_LIST_RLP {
+			return Err(BlockError::InvalidUnclesHash(Mismatch { expected: KECCAK_EMPTY_LIST_RLP, found: *header.uncles_hash() }).into());
+		}
+
+		if *header.difficulty() != DIFF_INTURN && *header.difficulty() != DIFF_NOTURN {
+			return Err(BlockError::DifficultyOutOfBounds(OutOfBounds {
+				min: Some(DIFF_NOTURN),
+				max: Some(DIFF_INTURN),
+				found: *header.difficulty(),
+			}).into());
+		}
+
+		Ok(())
+	}
+
+	fn verify_block_unordered(&self, header: &Header) -> Result<(), Error> {
+		if header.number() == 0 {
+			return Ok(());
+		}
+
+		self.recover_creator(header).map(|_| ())
+	}
+
+	fn verify_block_external(&self, header: &Header) -> Result<(), Error> {
+		if header.number() == 0 {
+			return Ok(());
+		}
+
+		let parent_state = self.state(header.parent_hash())?;
+		let creator = self.recover_creator(header)?;
+		let is_checkpoint = self.is_checkpoint(header.number());
+
+		let expected_difficulty = parent_state.difficulty(header.number(), &creator);
+		if *header.difficulty() != expected_difficulty {
+			return Err(BlockError::InvalidDifficulty(Mismatch { expected: expected_difficulty, found: *header.difficulty() }).into());
+		}
+
+		if is_checkpoint && extract_signers(header)? != *parent_state.signers() {
+			return Err(EngineError::CliqueFaultyCheckpointSigners.into());
+		}
+
+		// checks that the creator is allowed to sign and tallies the vote.
+		let mut state = parent_state;
+		state.apply(header, creator, is_checkpoint)?;
+		self.block_state_by_hash.write().insert(header.hash(), state);
+
+		Ok(())
+	}
+
+	/// Set the difficulty according to the author's turn and reserve room for the
+	/// signer list and signature in the extra data.
+	fn populate_from_parent(&self, header: &mut Header, parent: &Header) {
+		let state = match self.state_for_header(parent) {
+			Ok(state) => state,
+			Err(e) => {
+				warn!(target: "engine", "Failed to get signers at block {}: {}", parent.hash(), e);
+				return;
+			},
+		};
+
+		let difficulty = state.difficulty(header.number(), header.author());
+		header.set_difficulty(difficulty);
+
+		let mut extra_data = header.extra_data().clone();
+		extra_data.resize(VANITY_LENGTH, 0);
+		if self.is_checkpoint(header.number()) {
+			for signer in state.signers() {
+				extra_data.extend_from_slice(&signer[..]);
+			}
+		}
+		extra_data.extend_from_slice(&[0; SIGNATURE_LENGTH]);
+		header.set_extra_data(extra_data);
+	}
+
+	/// Transaction fees go to the block's signer, not to the beneficiary of its vote.
+	fn executive_author(&self, header: &Header) -> Result<Address, Error> {
+		self.recover_creator(header)
+	}
+
+	fn register_client(&self, client: Weak<EngineClient>) {
+		*self.client.write() = Some(client);
+	}
+
+	fn set_signer(&self, ap: Arc<AccountProvider>, address: Address, password: String) {
+		self.signer.write().set(ap, address, password);
+	}
+
+	fn sign(&self, hash: H256) -> Result<Signature, Error> {
+		Ok(self.signer.read().sign(hash)?)
+	}
+
+	fn step(&self) {
+		if !self.signer.read().is_some() {
+			return;
+		}
+
+		if let Some(ref weak) = *self.client.read() {
+			if let Some(c) = weak.upgrade() {
+				c.update_sealing();
+			}
+		}
+	}
+
+	fn stop(&self) {
+		self.step_service.stop()
+	}
+
+	/// The snapshot covers at least the last checkpoint, from which the signers
+	/// of the restored chain are rebuilt.
+	fn snapshot_components(&self) -> Option<Box<::snapshot::SnapshotComponents>> {
+		Some(Box::new(::snapshot::PowSnapshot::new(self.epoch_length, self.epoch_length * 2)))
+	}
+
+	fn open_block_header_timestamp(&self, parent_timestamp: u64) -> u64 {
+		::std::cmp::max(unix_now().as_secs(), parent_timestamp + self.period)
+	}
+
+	fn is_timestamp_valid(&self, header_timestamp: u64, parent_timestamp: u64) -> bool {
+		header_timestamp >= parent_timestamp + self.period
+	}
+
+	fn fork_choice(&self, new: &ExtendedHeader, current: &ExtendedHeader) -> super::ForkChoice {
+		super::total_difficulty_fork_choice(new, current)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::collections::BTreeSet;
+	use std::sync::Arc;
+	use hash::keccak;
+	use ethereum_types::{H64, H256, H520, Address};
+	use ethkey::{self, KeyPair};
+	use block::*;
+	use client::{BlockId, BlockInfo, ImportBlock, TestBlockChainClient};
+	use error::{Error, ErrorKind};
+	use test_helpers::get_temp_state_db;
+	use account_provider::AccountProvider;
+	use header::Header;
+	use rlp::{Rlp, RlpStream};
+	use spec::Spec;
+	use engines::{Engine, EngineError, Seal};
+	use tempdir::TempDir;
+	use super::{Clique, CliqueParams, CliqueBlockState, VoteType, recover_creator, sig_hash};
+	use super::{DIFF_INTURN, NONCE_AUTH_VOTE, NONCE_DROP_VOTE, VANITY_LENGTH, SIGNATURE_LENGTH};
+
+	/// Create a new test chain spec with `Clique` consensus engine.
+	fn new_test_clique() -> Spec {
+		let bytes: &[u8] = include_bytes!("../../../res/clique.json");
+		let tempdir = TempDir::new("").unwrap();
+		Spec::load(&tempdir.path(), bytes).expect("invalid chain spec")
+	}
+
+	/// Block on top of `parent` signed by `signer`. Checkpoint blocks list `signers`.
+	fn signed_header(parent: &Header, signer: &KeyPair, signers: &[Address], is_checkpoint: bool) -> Header {
+		let mut extra_data = vec![0; VANITY_LENGTH];
+		if is_checkpoint {
+			for s in signers {
+				extra_data.extend_from_slice(&s[..]);
+			}
+		}
+		extra_data.extend_from_slice(&[0; SIGNATURE_LENGTH]);
+
+		let mut header = Header::default();
+		header.set_parent_hash(parent.hash());
+		header.set_number(parent.number() + 1);
+		header.set_timestamp(parent.timestamp() + 1);
+		header.set_extra_data(extra_data.clone());
+		header.set_seal(vec![::rlp::encode(&H256::zero()).into_vec(), ::rlp::encode(&H64::from(NONCE_DROP_VOTE)).into_vec()]);
+
+		let signature = ethkey::sign(signer.secret(), &sig_hash(&header).unwrap()).unwrap();
+		let len = extra_data.len();
+		extra_data[len - SIGNATURE_LENGTH..].copy_from_slice(&H520::from(signature)[..]);
+		header.set_extra_data(extra_data);
+		header
+	}
+
+	fn encode_block(header: &Header) -> Vec<u8> {
+		let mut rlp = RlpStream::new_list(3);
+		rlp.append(header);
+		rlp.append_raw(&::rlp::EMPTY_LIST_RLP, 1);
+		rlp.append_raw(&::rlp::EMPTY_LIST_RLP, 1);
+		rlp.out()
+	}
+
+	#[test]
+	fn has_valid_metadata() {
+		let engine = new_test_clique().engine;
+		assert!(!engine.name().is_empty());
+	}
+
+	#[test]
+	fn seals_internally() {
+		let tap = AccountProvider::transient_provider();
+		let signer = tap.insert_account(keccak("").into(), "").unwrap();
+
+		let engine = new_test_clique().engine;
+		assert!(!engine.seals_internally().unwrap());
+		engine.set_signer(Arc::new(tap), signer, "".into());
+		assert!(engine.seals_internally().unwrap());
+	}
+
+	#[test]
+	fn rejects_block_without_signature() {
+		let engine = new_test_clique().engine;
+		let mut header = Header::default();
+		header.set_number(1);
+		header.set_extra_data(vec![0; 32]);
+		header.set_seal(vec![::rlp::encode(&H256::zero()).into_vec(), ::rlp::encode(&H64::zero()).into_vec()]);
+
+		assert!(engine.verify_block_basic(&header).is_err());
+	}
+
+	#[test]
+	fn can_generate_and_verify_seal() {
+		let tap = AccountProvider::transient_provider();
+		let signer = tap.insert_account(keccak("").into(), "").unwrap();
+
+		let spec = new_test_clique();
+		let engine = &*spec.engine;
+		engine.set_signer(Arc::new(tap), signer, "".into());
+		let genesis_header = spec.genesis_header();
+		let db = spec.ensure_db_good(get_temp_state_db(), &Default::default()).unwrap();
+		let last_hashes = Arc::new(vec![genesis_header.hash()]);
+		let b = OpenBlock::new(engine, Default::default(), false, db, &genesis_header, last_hashes, signer, (3141562.into(), 31415620.into()), vec![], false, &mut Vec::new().into_iter()).unwrap();
+		let b = b.close_and_lock();
+		assert_eq!(*b.header().difficulty(), DIFF_INTURN);
+
+		let seal = match engine.generate_seal(b.block(), &genesis_header) {
+			Seal::Regular(seal) => seal,
+			_ => panic!("the only signer should seal in turn"),
+		};
+		let sealed = b.try_seal(engine, seal).unwrap_or_else(|_| panic!("seal should be valid"));
+		let header = sealed.header();
+
+		assert!(header.author().is_zero());
+		assert_eq!(recover_creator(header).unwrap(), signer);
+		assert_eq!(engine.executive_author(header).unwrap(), signer);
+		assert!(engine.verify_block_basic(header).is_ok());
+		assert!(engine.verify_block_unordered(header).is_ok());
+		assert!(engine.verify_block_external(header).is_ok());
+	}
+
+	#[test]
+	fn votes_on_pending_proposals() {
+		let tap = AccountProvider::transient_provider();
+		let signer = tap.insert_account(keccak("").into(), "").unwrap();
+
+		let spec = new_test_clique();
+		let engine = Clique::new(CliqueParams { period: 1, epoch: 30_000 }, Spec::new_test_machine()).unwrap();
+		engine.set_signer(Arc::new(tap), signer, "".into());
+
+		// dropping a non-signer wouldn't change anything, so it's never voted on.
+		let proposed = Address::from(1);
+		engine.propose(proposed, VoteType::Add);
+		engine.propose(Address::from(2), VoteType::Remove);
+
+		let genesis_header = spec.genesis_header();
+		let db = spec.ensure_db_good(get_temp_state_db(), &Default::default()).unwrap();
+		let last_hashes = Arc::new(vec![genesis_header.hash()]);
+		let b = OpenBlock::new(&*engine, Default::default(), false, db, &genesis_header, last_hashes, signer, (3141562.into(), 31415620.into()), vec![], false, &mut Vec::new().into_iter()).unwrap();
+		let b = b.close_and_lock();
+
+		let seal = match engine.generate_seal(b.block(), &genesis_header) {
+			Seal::Regular(seal) => seal,
+			_ => panic!("the only signer should seal in turn"),
+		};
+		let sealed = b.try_seal(&*engine, seal).unwrap_or_else(|_| panic!("seal should be valid"));
+		let header = sealed.header();
+
+		assert_eq!(*header.author(), proposed);
+		assert_eq!(Rlp::new(&header.seal()[1]).as_val::<H64>().unwrap(), H64::from(NONCE_AUTH_VOTE));
+		assert_eq!(recover_creator(header).unwrap(), signer);
+		assert!(engine.verify_block_basic(header).is_ok());
+		assert!(engine.verify_block_external(header).is_ok());
+
+		// the vote of the only signer passes right away.
+		assert!(engine.state(&header.hash()).unwrap().is_authorized(&proposed));
+
+		engine.discard(&proposed);
+		assert_eq!(engine.proposals().keys().collect::<Vec<_>>(), vec![&Address::from(2)]);
+	}
+
+	#[test]
+	fn rebuilds_state_from_last_checkpoint() {
+		let keys: Vec<_> = (1..3).map(|i| KeyPair::from_secret(keccak(format!("{}", i)).into()).unwrap()).collect();
+		let signers: Vec<_> = keys.iter().map(KeyPair::address).collect();
+
+		let engine = Clique::new(CliqueParams { period: 1, epoch: 4 }, Spec::new_test_machine()).unwrap();
+		let client = Arc::new(TestBlockChainClient::new());
+		engine.register_client(Arc::downgrade(&client) as _);
+
+		// blocks 1 to 6 signed in turn, with a checkpoint at block 4.
+		let mut expected = CliqueBlockState::new(signers.iter().cloned().collect::<BTreeSet<_>>());
+		let mut parent: Header = client.block_header(BlockId::Number(0)).unwrap().decode().unwrap();
+		let mut headers = Vec::new();
+		for number in 1..7 {
+			let is_checkpoint = number % 4 == 0;
+			let key = &keys[number % 2];
+			let header = signed_header(&parent, key, &signers, is_checkpoint);
+			expected.apply(&header, key.address(), is_checkpoint).unwrap();
+			client.import_block(encode_block(&header)).unwrap();
+			parent = header.clone();
+			headers.push(header);
+		}
+
+		// blocks before the checkpoint are only needed to know its recent signers.
+		assert_eq!(engine.state_for_header(&headers[5]).unwrap(), expected);
+		assert_eq!(engine.state(&headers[4].hash()).unwrap().signers(), expected.signers());
+	}
+
+	#[test]
+	fn fails_to_rebuild_state_without_ancestors_of_checkpoint() {
+		let keys: Vec<_> = (1..3).map(|i| KeyPair::from_secret(keccak(format!("{}", i)).into()).unwrap()).collect();
+		let signers: Vec<_> = keys.iter().map(KeyPair::address).collect();
+
+		let engine = Clique::new(CliqueParams { period: 1, epoch: 4 }, Spec::new_test_machine()).unwrap();
+		let client = Arc::new(TestBlockChainClient::new());
+		engine.register_client(Arc::downgrade(&client) as _);
+
+		// the parent of the checkpoint isn't known to the client.
+		let mut parent = Header::default();
+		parent.set_number(3);
+		let checkpoint = signed_header(&parent, &keys[0], &signers, true);
+
+		match engine.state_for_header(&checkpoint) {
+			Err(Error(ErrorKind::Engine(EngineError::CliqueMissingAncestor(hash)), _)) => assert_eq!(hash, parent.hash()),
+			other => panic!("expected missing ancestor, got {:?}", other),
+		}
+	}
+}
@@ -18,6 +18,7 @@
 
 mod authority_round;
 mod basic_authority;
+mod clique;
 mod instant_seal;
 mod null_engine;
 mod signer;
@@ -31,6 +32,7 @@ pub mod epoch;
 
 pub use self::authority_round::AuthorityRound;
 pub use self::basic_authority::BasicAuthority;
+pub use self::clique::Clique;
 pub use self::epoch::{EpochVerifier, Transition as EpochTransition};
 pub use self::instant_seal::InstantSeal;
 pub use self::null_engine::NullEngine;
@@ -53,10 +55,11 @@ use transaction::{self, UnverifiedTransaction, SignedTransaction};
 
 use ethkey::Signature;
 use parity_machine::{Machine, LocalizedMachine as Localized, TotalScoredHeader};
-use ethereum_types::{H256, U256, Address};
+use ethereum_types::{H64, H256, U256, Address};
 use unexpected::{Mismatch, OutOfBounds};
 use bytes::Bytes;
 use types::ancestry_action::AncestryAction;
+use types::validator_report::ValidatorReport;
 
 /// Default EIP-210 contract code.
 /// As defined in https://github.com/ethereum/EIPs/pull/210
@@ -92,6 +95,24 @@ pub enum EngineError {
 	MalformedMessage(String),
 	/// Requires client ref, but none registered.
 	RequiresClient,
+	/// Clique extra data is missing the signer vanity.
+	CliqueMissingVanity,
+	/// Clique extra data is missing the signature.
+	CliqueMissingSignature,
+	/// Clique checkpoint block has an invalid signer list.
+	CliqueCheckpointInvalidSigners(usize),
+	/// Clique checkpoint signer list doesn't match the current signers.
+	CliqueFaultyCheckpointSigners,
+	/// Clique non-checkpoint block carries a signer list.
+	CliqueExtraSigners,
+	/// Clique block has a nonce which is not a valid vote.
+	CliqueInvalidNonce(H64),
+	/// Clique checkpoint block casts a vote.
+	CliqueCheckpointVote,
+	/// Clique signer has signed one of the recent blocks.
+	CliqueTooRecentlySigned(Address),
+	/// Clique signer set can't be rebuilt, an ancestor down to the last checkpoint is missing.
+	CliqueMissingAncestor(H256),
 }
 
 impl fmt::Display for EngineError {
@@ -107,6 +128,15 @@ impl fmt::Display for EngineError {
 			FailedSystemCall(ref msg) => format!("Failed to make system call: {}", msg),
 			MalformedMessage(ref msg) => format!("Received malformed consensus message: {}", msg),
 			RequiresClient => format!("Call requires client but none registered"),
+			CliqueMissingVanity => format!("Extra data is missing vanity data"),
+			CliqueMissingSignature => format!("Extra data is missing signature"),
+			CliqueCheckpointInvalidSigners(len) => format!("Checkpoint signer list has length {}, expected a non-zero multiple of 20", len),
+			CliqueFaultyCheckpointSigners => format!("Checkpoint signer list doesn't match the current signers"),
+			CliqueExtraSigners => format!("Non-checkpoint block contains a signer list"),
+			CliqueInvalidNonce(ref nonce) => format!("Nonce {} is neither an authorize nor a drop vote", nonce),
+			CliqueCheckpointVote => format!("Checkpoint block casts a vote"),
+			CliqueTooRecentlySigned(ref address) => format!("The signer: {} has signed a block too recently", address),
+			CliqueMissingAncestor(ref hash) => format!("Missing ancestor block {} to rebuild the signer set", hash),
 		};
 
 		f.write_fmt(format_args!("Engine error ({})", msg))
@@ -228,6 +258,18 @@ pub trait Engine<M: Machine>: Sync + Send {
 		Ok(())
 	}
 
+	/// Block transformation functions, after a locally generated seal has been set.
+	/// Engines which sign parts of the header other than the seal fields finalize it here.
+	fn on_seal_block(&self, _block: &mut M::LiveBlock) -> Result<(), M::Error> {
+		Ok(())
+	}
+
+	/// The address credited with transaction fees and returned by `COINBASE` when executing
+	/// the block, which is not necessarily the header's author.
+	fn executive_author(&self, header: &M::Header) -> Result<Address, M::Error> {
+		Ok(*::parity_machine::Header::author(header))
+	}
+
 	/// None means that it requires external input (e.g. PoW) to seal a block.
 	/// Some(true) means the engine is currently prime for seal generation (i.e. node is the current validator).
 	/// Some(false) means that the node might seal internally but is not qualified now.
@@ -291,10 +333,27 @@ pub trait Engine<M: Machine>: Sync + Send {
 	///
 	/// This either means that an immediate transition occurs or a block signalling transition
 	/// has reached finality. The `Headers` given are not guaranteed to return any blocks
-	/// from any epoch other than the current.
+	/// from any epoch other than the current. The client keeps track of finality and provides
+	/// the blocks finalized by importing the chain head, as returned by `ancestry_actions`.
 	///
 	/// Return optional transition proof.
 	fn is_epoch_end(
+		&self,
+		_chain_head: &M::Header,
+		_finalized: &[H256],
+		_chain: &Headers<M::Header>,
+		_transition_store: &PendingTransitionStore,
+	) -> Option<Vec<u8>> {
+		None
+	}
+
+	/// Whether a block is the end of an epoch.
+	///
+	/// Same as `is_epoch_end`, for light clients which don't keep track of finality.
+	/// Finality of the blocks in the current epoch is built by the engine itself.
+	///
+	/// Return optional transition proof.
+	fn is_epoch_end_light(
 		&self,
 		_chain_head: &M::Header,
 		_chain: &Headers<M::Header>,
@@ -336,6 +395,9 @@ pub trait Engine<M: Machine>: Sync + Send {
 	/// Stops any services that the may hold the Engine and makes it safe to drop.
 	fn stop(&self) {}
 
+	/// Validator misbehaviour reports sent by this node, oldest first.
+	fn validator_reports(&self) -> Vec<ValidatorReport> { Vec::new() }
+
 	/// Create a factory for building snapshot chunks and restoring from them.
 	/// Returning `None` indicates that this engine doesn't support snapshot creation.
 	fn snapshot_components(&self) -> Option<Box<SnapshotComponents>> {
@@ -361,8 +423,8 @@ pub trait Engine<M: Machine>: Sync + Send {
 	}
 
 	/// Gather all ancestry actions. Called at the last stage when a block is committed. The Engine must guarantee that
-	/// the ancestry exists.
-	fn ancestry_actions(&self, _block: &M::LiveBlock, _ancestry: &mut Iterator<Item=M::ExtendedHeader>) -> Vec<AncestryAction> {
+	/// the ancestry exists. The header is the sealed header of the committed block, which may itself be finalized.
+	fn ancestry_actions(&self, _header: &M::Header, _ancestry: &mut Iterator<Item=M::ExtendedHeader>) -> Vec<AncestryAction> {
 		Vec::new()
 	}
 
@@ -41,7 +41,7 @@ use ethkey::{self, Message, Signature};
 use account_provider::AccountProvider;
 use block::*;
 use engines::{Engine, Seal, EngineError, ConstructedVerifier};
-use engines::block_reward::{self, RewardKind};
+use engines::block_reward::{self, BlockRewardContract, RewardKind};
 use io::IoService;
 use super::signer::EngineSigner;
 use super::validator_set::{ValidatorSet, SimpleList};
@@ -50,6 +50,8 @@ use super::vote_collector::VoteCollector;
 use self::message::*;
 use self::params::TendermintParams;
 use machine::{AuxiliaryData, EthereumMachine};
+use types::ancestry_action::AncestryAction;
+use types::validator_report::ValidatorReport;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub enum Step {
@@ -100,6 +102,10 @@ pub struct Tendermint {
 	validators: Box<ValidatorSet>,
 	/// Reward per block, in base units.
 	block_reward: U256,
+	/// Block reward contract transition block.
+	block_reward_contract_transition: u64,
+	/// Block reward contract.
+	block_reward_contract: Option<BlockRewardContract>,
 	/// ethereum machine descriptor
 	machine: EthereumMachine,
 }
@@ -184,6 +190,8 @@ impl Tendermint {
 				last_proposed: Default::default(),
 				validators: our_params.validators,
 				block_reward: our_params.block_reward,
+				block_reward_contract_transition: our_params.block_reward_contract_transition,
+				block_reward_contract: our_params.block_reward_contract,
 				machine: machine,
 			});
 
@@ -555,11 +563,14 @@ impl Engine<EthereumMachine> for Tendermint {
 	fn on_close_block(&self, block: &mut ExecutedBlock) -> Result<(), Error>{
 		let author = *block.header().author();
 
-		block_reward::apply_block_rewards(
-			&[(author, RewardKind::Author, self.block_reward)],
-			block,
-			&self.machine,
-		)
+		let rewards = match self.block_reward_contract {
+			Some(ref c) if block.header().number() >= self.block_reward_contract_transition => {
+				block_reward::contract_rewards(c, &[(author, RewardKind::Author)], block, &self.machine)?
+			},
+			_ => vec![(author, RewardKind::Author, self.block_reward)],
+		};
+
+		block_reward::apply_block_rewards(&rewards, block, &self.machine)
 	}
 
 	fn verify_local_seal(&self, _header: &Header) -> Result<(), Error> {
@@ -636,6 +647,7 @@ impl Engine<EthereumMachine> for Tendermint {
 	fn is_epoch_end(
 		&self,
 		chain_head: &Header,
+		_finalized: &[H256],
 		_chain: &super::Headers<Header>,
 		transition_store: &super::PendingTransitionStore,
 	) -> Option<Vec<u8>> {
@@ -653,6 +665,15 @@ impl Engine<EthereumMachine> for Tendermint {
 		None
 	}
 
+	fn is_epoch_end_light(
+		&self,
+		chain_head: &Header,
+		chain: &super::Headers<Header>,
+		transition_store: &super::PendingTransitionStore,
+	) -> Option<Vec<u8>> {
+		self.is_epoch_end(chain_head, &[], chain, transition_store)
+	}
+
 	fn epoch_verifier<'a>(&self, _header: &Header, proof: &'a [u8]) -> ConstructedVerifier<'a, EthereumMachine> {
 		let (signal_number, set_proof, finality_proof) = match destructure_proofs(proof) {
 			Ok(x) => x,
@@ -758,6 +779,10 @@ impl Engine<EthereumMachine> for Tendermint {
 		self.to_step(next_step);
 	}
 
+	fn validator_reports(&self) -> Vec<ValidatorReport> {
+		self.validators.reports()
+	}
+
 	fn register_client(&self, client: Weak<EngineClient>) {
 		if let Some(c) = client.upgrade() {
 			self.height.store(c.chain_info().best_block_number as usize + 1, AtomicOrdering::SeqCst);
@@ -766,6 +791,15 @@ impl Engine<EthereumMachine> for Tendermint {
 		self.validators.register_client(client);
 	}
 
+	fn ancestry_actions(&self, _header: &Header, ancestry: &mut Iterator<Item=ExtendedHeader>) -> Vec<AncestryAction> {
+		// only committed blocks are imported, so the parent is final.
+		ancestry.next()
+			.into_iter()
+			.filter(|parent| !parent.is_finalized && parent.header.number() != 0)
+			.map(|parent| AncestryAction::MarkFinalized(parent.header.hash()))
+			.collect()
+	}
+
 	fn fork_choice(&self, new: &ExtendedHeader, current: &ExtendedHeader) -> super::ForkChoice {
 		super::total_difficulty_fork_choice(new, current)
 	}
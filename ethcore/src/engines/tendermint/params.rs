@@ -19,6 +19,7 @@
 use ethjson;
 use std::time::Duration;
 use ethereum_types::U256;
+use super::super::block_reward::BlockRewardContract;
 use super::super::validator_set::{ValidatorSet, new_validator_set};
 use super::super::transition::Timeouts;
 use super::Step;
@@ -31,6 +32,10 @@ pub struct TendermintParams {
 	pub timeouts: TendermintTimeouts,
 	/// Reward per block in base units.
 	pub block_reward: U256,
+	/// Block reward contract transition block.
+	pub block_reward_contract_transition: u64,
+	/// Block reward contract.
+	pub block_reward_contract: Option<BlockRewardContract>,
 }
 
 /// Base timeout of each step in ms.
@@ -85,6 +90,8 @@ impl From<ethjson::spec::TendermintParams> for TendermintParams {
 				commit: p.timeout_commit.map_or(dt.commit, to_duration),
 			},
 			block_reward: p.block_reward.map_or(U256::default(), Into::into),
+			block_reward_contract_transition: p.block_reward_contract_transition.map_or(0, Into::into),
+			block_reward_contract: p.block_reward_contract_address.map(|address| BlockRewardContract::new(address.into())),
 		}
 	}
 }
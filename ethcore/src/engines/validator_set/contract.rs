@@ -17,6 +17,7 @@
 /// Validator set maintained in a contract, updated using `getValidators` method.
 /// It can also report validators for misbehaviour with two levels: `reportMalicious` and `reportBenign`.
 
+use std::collections::VecDeque;
 use std::sync::Weak;
 
 use bytes::Bytes;
@@ -26,18 +27,23 @@ use parking_lot::RwLock;
 use client::EngineClient;
 use header::{Header, BlockNumber};
 use machine::{AuxiliaryData, Call, EthereumMachine};
+use types::validator_report::{ValidatorReport, ReportKind};
 
 use super::{ValidatorSet, SimpleList, SystemCall};
 use super::safe_contract::ValidatorSafeContract;
 
 use_contract!(validator_report, "ValidatorReport", "res/contracts/validator_report.json");
 
+/// Maximal number of reports remembered.
+const MAX_REPORTS: usize = 1024;
+
 /// A validator contract with reporting.
 pub struct ValidatorContract {
 	contract_address: Address,
 	validators: ValidatorSafeContract,
 	provider: validator_report::ValidatorReport,
 	client: RwLock<Option<Weak<EngineClient>>>, // TODO [keorn]: remove
+	reports: RwLock<VecDeque<ValidatorReport>>,
 }
 
 impl ValidatorContract {
@@ -47,24 +53,33 @@ impl ValidatorContract {
 			validators: ValidatorSafeContract::new(contract_address),
 			provider: validator_report::ValidatorReport::default(),
 			client: RwLock::new(None),
+			reports: RwLock::new(VecDeque::new()),
 		}
 	}
 }
 
 impl ValidatorContract {
-	fn transact(&self, data: Bytes) -> Result<(), String> {
+	/// Send a report to the contract. Malicious reports are sent as service transactions,
+	/// benign ones pay the usual gas price.
+	fn transact(&self, data: Bytes, service: bool) -> Result<H256, String> {
 		let client = self.client.read().as_ref()
 			.and_then(Weak::upgrade)
 			.ok_or_else(|| "No client!")?;
 
-		match client.as_full_client() {
-			Some(c) => {
-				c.transact_contract(self.contract_address, data)
-					.map_err(|e| format!("Transaction import error: {}", e))?;
-				Ok(())
-			},
-			None => Err("No full client!".into()),
+		let client = client.as_full_client().ok_or_else(|| "No full client!")?;
+		let result = match service {
+			true => client.transact_service_contract(self.contract_address, data),
+			false => client.transact_contract(self.contract_address, data),
+		};
+		result.map_err(|e| format!("Transaction import error: {}", e))
+	}
+
+	fn record(&self, validator: Address, block: BlockNumber, kind: ReportKind, transaction: Option<H256>) {
+		let mut reports = self.reports.write();
+		if reports.len() >= MAX_REPORTS {
+			reports.pop_front();
 		}
+		reports.push_back(ValidatorReport { validator, block, kind, transaction });
 	}
 }
 
@@ -111,19 +126,37 @@ impl ValidatorSet for ValidatorContract {
 	}
 
 	fn report_malicious(&self, address: &Address, _set_block: BlockNumber, block: BlockNumber, proof: Bytes) {
-		let data = self.provider.functions().report_malicious().input(*address, block, proof);
-		match self.transact(data) {
-			Ok(_) => warn!(target: "engine", "Reported malicious validator {}", address),
-			Err(s) => warn!(target: "engine", "Validator {} could not be reported {}", address, s),
-		}
+		let data = self.provider.functions().report_malicious().input(*address, block, proof.clone());
+		let transaction = match self.transact(data, true) {
+			Ok(hash) => {
+				warn!(target: "engine", "Reported malicious validator {}", address);
+				Some(hash)
+			},
+			Err(s) => {
+				warn!(target: "engine", "Validator {} could not be reported {}", address, s);
+				None
+			},
+		};
+		self.record(*address, block, ReportKind::Malicious(proof), transaction);
 	}
 
 	fn report_benign(&self, address: &Address, _set_block: BlockNumber, block: BlockNumber) {
 		let data = self.provider.functions().report_benign().input(*address, block);
-		match self.transact(data) {
-			Ok(_) => warn!(target: "engine", "Reported benign validator misbehaviour {}", address),
-			Err(s) => warn!(target: "engine", "Validator {} could not be reported {}", address, s),
-		}
+		let transaction = match self.transact(data, false) {
+			Ok(hash) => {
+				warn!(target: "engine", "Reported benign validator misbehaviour {}", address);
+				Some(hash)
+			},
+			Err(s) => {
+				warn!(target: "engine", "Validator {} could not be reported {}", address, s);
+				None
+			},
+		};
+		self.record(*address, block, ReportKind::Benign, transaction);
+	}
+
+	fn reports(&self) -> Vec<ValidatorReport> {
+		self.reports.read().iter().cloned().collect()
 	}
 
 	fn register_client(&self, client: Weak<EngineClient>) {
@@ -31,6 +31,7 @@ use ethjson::spec::ValidatorSet as ValidatorSpec;
 use client::EngineClient;
 use header::{Header, BlockNumber};
 use machine::{AuxiliaryData, Call, EthereumMachine};
+use types::validator_report::ValidatorReport;
 
 #[cfg(test)]
 pub use self::test::TestSet;
@@ -137,6 +138,8 @@ pub trait ValidatorSet: Send + Sync {
 	fn report_malicious(&self, _validator: &Address, _set_block: BlockNumber, _block: BlockNumber, _proof: Bytes) {}
 	/// Notifies about benign misbehaviour.
 	fn report_benign(&self, _validator: &Address, _set_block: BlockNumber, _block: BlockNumber) {}
+	/// Reports sent by this node, oldest first.
+	fn reports(&self) -> Vec<ValidatorReport> { Vec::new() }
 	/// Allows blockchain state access.
 	fn register_client(&self, _client: Weak<EngineClient>) {}
 }
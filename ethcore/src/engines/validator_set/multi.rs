@@ -25,6 +25,7 @@ use ids::BlockId;
 use header::{BlockNumber, Header};
 use client::EngineClient;
 use machine::{AuxiliaryData, Call, EthereumMachine};
+use types::validator_report::ValidatorReport;
 use super::{SystemCall, ValidatorSet};
 
 type BlockNumberLookup = Box<Fn(BlockId) -> Result<BlockNumber, String> + Send + Sync + 'static>;
@@ -131,6 +132,10 @@ impl ValidatorSet for Multi {
 		self.correct_set_by_number(set_block).1.report_benign(validator, set_block, block);
 	}
 
+	fn reports(&self) -> Vec<ValidatorReport> {
+		self.sets.values().flat_map(|set| set.reports()).collect()
+	}
+
 	fn register_client(&self, client: Weak<EngineClient>) {
 		for set in self.sets.values() {
 			set.register_client(client.clone());
@@ -22,6 +22,7 @@ use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
 use heapsize::HeapSizeOf;
 use ethereum_types::{H256, Address};
 use bytes::Bytes;
+use parking_lot::RwLock;
 
 use machine::{AuxiliaryData, Call, EthereumMachine};
 use header::{Header, BlockNumber};
@@ -32,14 +33,21 @@ pub struct TestSet {
 	validator: SimpleList,
 	last_malicious: Arc<AtomicUsize>,
 	last_benign: Arc<AtomicUsize>,
+	last_malicious_proof: Arc<RwLock<Bytes>>,
 }
 
 impl TestSet {
 	pub fn new(last_malicious: Arc<AtomicUsize>, last_benign: Arc<AtomicUsize>) -> Self {
+		TestSet::new_with_proof(last_malicious, last_benign, Default::default())
+	}
+
+	/// Create a set which also keeps the proof of the last malicious report.
+	pub fn new_with_proof(last_malicious: Arc<AtomicUsize>, last_benign: Arc<AtomicUsize>, last_malicious_proof: Arc<RwLock<Bytes>>) -> Self {
 		TestSet {
 			validator: SimpleList::new(vec![Address::from_str("7d577a597b2742b498cb5cf0c26cdcd726d39e6e").unwrap()]),
 			last_malicious: last_malicious,
 			last_benign: last_benign,
+			last_malicious_proof: last_malicious_proof,
 		}
 	}
 }
@@ -79,7 +87,8 @@ impl ValidatorSet for TestSet {
 		1
 	}
 
-	fn report_malicious(&self, _validator: &Address, _set_block: BlockNumber, block: BlockNumber, _proof: Bytes) {
+	fn report_malicious(&self, _validator: &Address, _set_block: BlockNumber, block: BlockNumber, proof: Bytes) {
+		*self.last_malicious_proof.write() = proof;
 		self.last_malicious.store(block as usize, AtomicOrdering::SeqCst)
 	}
 
@@ -19,7 +19,7 @@ use std::cmp;
 use std::collections::BTreeMap;
 use std::sync::Arc;
 use hash::{KECCAK_EMPTY_LIST_RLP};
-use engines::block_reward::{self, RewardKind};
+use engines::block_reward::{self, BlockRewardContract, RewardKind};
 use ethash::{quick_get_difficulty, slow_hash_block_number, EthashManager, OptimizeFor};
 use ethereum_types::{H256, H64, U256, Address};
 use unexpected::{OutOfBounds, Mismatch};
@@ -114,6 +114,10 @@ pub struct EthashParams {
 	pub mcip3_dev_contract: Address,
 	/// Block reward in base units.
 	pub block_reward: U256,
+	/// Block reward contract transition block.
+	pub block_reward_contract_transition: u64,
+	/// Block reward contract.
+	pub block_reward_contract: Option<BlockRewardContract>,
 	/// EIP-649 transition block.
 	pub eip649_transition: u64,
 	/// EIP-649 bomb delay.
@@ -149,6 +153,8 @@ impl From<ethjson::spec::EthashParams> for EthashParams {
 			mcip3_dev_reward: p.mcip3_dev_reward.map_or(U256::from(0), Into::into),
 			mcip3_dev_contract: p.mcip3_dev_contract.map_or_else(Address::new, Into::into),
 			block_reward: p.block_reward.map_or_else(Default::default, Into::into),
+			block_reward_contract_transition: p.block_reward_contract_transition.map_or(0, Into::into),
+			block_reward_contract: p.block_reward_contract_address.map(|address| BlockRewardContract::new(address.into())),
 			eip649_transition: p.eip649_transition.map_or(u64::max_value(), Into::into),
 			eip649_delay: p.eip649_delay.map_or(DEFAULT_EIP649_DELAY, Into::into),
 			eip649_reward: p.eip649_reward.map(Into::into),
@@ -231,6 +237,18 @@ impl Engine<EthereumMachine> for Arc<Ethash> {
 		let author = *LiveBlock::header(&*block).author();
 		let number = LiveBlock::header(&*block).number();
 
+		if let Some(ref c) = self.ethash_params.block_reward_contract {
+			if number >= self.ethash_params.block_reward_contract_transition {
+				let mut benefactors = vec![(author, RewardKind::Author)];
+				for u in LiveBlock::uncles(&*block) {
+					benefactors.push((*u.author(), RewardKind::Uncle));
+				}
+
+				let rewards = block_reward::contract_rewards(c, &benefactors, block, &self.machine)?;
+				return block_reward::apply_block_rewards(&rewards, block, &self.machine);
+			}
+		}
+
 		let mut rewards = Vec::new();
 
 		// Applies EIP-649 reward.
@@ -487,7 +505,8 @@ mod tests {
 	use header::Header;
 	use spec::Spec;
 	use engines::Engine;
-	use super::super::{new_morden, new_mcip3_test, new_homestead_test_machine};
+	use super::super::{new_morden, new_mcip3_test, new_homestead_test_machine, new_byzantium_test_machine};
+	use engines::block_reward::BlockRewardContract;
 	use super::{Ethash, EthashParams, ecip1017_eras_block_reward};
 	use rlp;
 	use tempdir::TempDir;
@@ -506,6 +525,8 @@ mod tests {
 			homestead_transition: 1150000,
 			duration_limit: 13,
 			block_reward: 0.into(),
+			block_reward_contract_transition: 0,
+			block_reward_contract: None,
 			difficulty_hardfork_transition: u64::max_value(),
 			difficulty_hardfork_bound_divisor: U256::from(0),
 			bomb_defuse_transition: u64::max_value(),
@@ -594,6 +615,34 @@ mod tests {
 		assert_eq!(b.state().balance(&uncle_author).unwrap(), "3cb71f51fc558000".into());
 	}
 
+	#[test]
+	fn on_close_block_with_block_reward_contract() {
+		// the spec has a block reward contract deployed at 0x42 which rewards (1000 + kind)
+		let spec = Spec::new_test_round_block_reward_contract();
+		let ethparams = EthashParams {
+			block_reward: U256::from(1_000_000),
+			block_reward_contract_transition: 1,
+			block_reward_contract: Some(BlockRewardContract::new("0000000000000000000000000000000000000042".into())),
+			..get_default_ethash_params()
+		};
+		let tempdir = TempDir::new("").unwrap();
+		let ethash = Ethash::new(tempdir.path(), ethparams, new_byzantium_test_machine(), None);
+
+		let genesis_header = spec.genesis_header();
+		let db = spec.ensure_db_good(get_temp_state_db(), &Default::default()).unwrap();
+		let last_hashes = Arc::new(vec![genesis_header.hash()]);
+		let author: Address = "0000000000000000000000000000000000000033".into();
+		let mut b = OpenBlock::new(&ethash, Default::default(), false, db, &genesis_header, last_hashes, author, (3141562.into(), 31415620.into()), vec![], false, &mut Vec::new().into_iter()).unwrap();
+		let mut uncle = Header::new();
+		let uncle_author: Address = "0000000000000000000000000000000000000034".into();
+		uncle.set_author(uncle_author);
+		b.push_uncle(uncle).unwrap();
+
+		let b = b.close();
+		assert_eq!(b.state().balance(&author).unwrap(), U256::from(1000));
+		assert_eq!(b.state().balance(&uncle_author).unwrap(), U256::from(1000 + 1));
+	}
+
 	#[test]
 	fn has_valid_mcip3_era_block_rewards() {
 		let spec = new_mcip3_test();
@@ -153,6 +153,7 @@ pub mod header;
 pub mod machine;
 pub mod miner;
 pub mod pod_state;
+pub mod pruning;
 pub mod snapshot;
 pub mod spec;
 pub mod state;
@@ -0,0 +1,164 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Bundles of transactions which have to be included in an authored block
+//! together, in the given order, or not at all.
+
+use std::collections::HashSet;
+
+use ethereum_types::H256;
+use hash::keccak;
+use header::BlockNumber;
+use transaction::SignedTransaction;
+
+/// Maximal number of bundles waiting for inclusion.
+const MAX_BUNDLES: usize = 64;
+
+/// Ordered group of transactions included atomically.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bundle {
+	/// Hash identifying the bundle.
+	pub hash: H256,
+	/// Transactions to include, in order.
+	pub transactions: Vec<SignedTransaction>,
+	/// Last block number the bundle may be included in.
+	pub target_block: BlockNumber,
+}
+
+impl Bundle {
+	/// Create a new bundle. The hash is computed from the hashes of the transactions.
+	pub fn new(transactions: Vec<SignedTransaction>, target_block: BlockNumber) -> Self {
+		let hashes: Vec<u8> = transactions.iter()
+			.flat_map(|tx| tx.hash().to_vec())
+			.collect();
+
+		Bundle {
+			hash: keccak(&hashes),
+			transactions,
+			target_block,
+		}
+	}
+}
+
+/// Bundles waiting for inclusion, in order of submission.
+#[derive(Debug, Default)]
+pub struct BundleStore {
+	bundles: Vec<Bundle>,
+}
+
+impl BundleStore {
+	/// Add a bundle to the store, replacing the oldest one if the store is full.
+	/// Re-submitting a bundle only updates its target block.
+	pub fn insert(&mut self, bundle: Bundle) {
+		if let Some(existing) = self.bundles.iter_mut().find(|b| b.hash == bundle.hash) {
+			existing.target_block = bundle.target_block;
+			return;
+		}
+
+		if self.bundles.len() >= MAX_BUNDLES {
+			let dropped = self.bundles.remove(0);
+			debug!(target: "miner", "Bundle store full, dropping bundle {:?}", dropped.hash);
+		}
+
+		self.bundles.push(bundle);
+	}
+
+	/// Bundles which may be included in a block with given number.
+	pub fn ready(&self, block_number: BlockNumber) -> Vec<Bundle> {
+		self.bundles.iter()
+			.filter(|b| b.target_block >= block_number)
+			.cloned()
+			.collect()
+	}
+
+	/// Remove bundles which can't be included after the given best block anymore:
+	/// those past their target block and those with any transaction already included.
+	pub fn cull(&mut self, best_block: BlockNumber, included: &HashSet<H256>) {
+		self.bundles.retain(|b| {
+			b.target_block > best_block && !b.transactions.iter().any(|tx| included.contains(&tx.hash()))
+		});
+	}
+
+	/// Number of bundles in the store.
+	pub fn len(&self) -> usize {
+		self.bundles.len()
+	}
+
+	/// Whether the store is empty.
+	pub fn is_empty(&self) -> bool {
+		self.bundles.is_empty()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ethkey::{Generator, Random};
+	use transaction::Transaction;
+
+	fn transaction(nonce: u64) -> SignedTransaction {
+		let keypair = Random.generate().unwrap();
+		Transaction {
+			nonce: nonce.into(),
+			..Default::default()
+		}.sign(keypair.secret(), None)
+	}
+
+	#[test]
+	fn should_return_bundles_until_target_block() {
+		// given
+		let mut store = BundleStore::default();
+		store.insert(Bundle::new(vec![transaction(0), transaction(1)], 10));
+		store.insert(Bundle::new(vec![transaction(2)], 12));
+
+		// then
+		assert_eq!(store.ready(10).len(), 2);
+		assert_eq!(store.ready(11).len(), 1);
+		assert_eq!(store.ready(13).len(), 0);
+	}
+
+	#[test]
+	fn should_update_target_block_of_resubmitted_bundle() {
+		// given
+		let mut store = BundleStore::default();
+		let txs = vec![transaction(0), transaction(1)];
+		store.insert(Bundle::new(txs.clone(), 10));
+
+		// when
+		store.insert(Bundle::new(txs, 20));
+
+		// then
+		assert_eq!(store.len(), 1);
+		assert_eq!(store.ready(15).len(), 1);
+	}
+
+	#[test]
+	fn should_cull_expired_and_included_bundles() {
+		// given
+		let mut store = BundleStore::default();
+		let included = transaction(0);
+		store.insert(Bundle::new(vec![transaction(1)], 10));
+		store.insert(Bundle::new(vec![included.clone(), transaction(2)], 20));
+		store.insert(Bundle::new(vec![transaction(3)], 20));
+
+		// when
+		store.cull(10, &vec![included.hash()].into_iter().collect());
+
+		// then
+		assert_eq!(store.len(), 1);
+		assert_eq!(store.ready(11)[0].transactions.len(), 1);
+	}
+}
@@ -16,13 +16,13 @@
 
 use std::time::{Instant, Duration};
 use std::collections::{BTreeMap, HashSet, HashMap};
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
 
 use ansi_term::Colour;
 use bytes::Bytes;
 use engines::{EthEngine, Seal};
 use error::{Error, ErrorKind, ExecutionError};
-use ethcore_miner::gas_pricer::GasPricer;
+use ethcore_miner::gas_pricer::{BlockGasPrices, GasPricer, GasPricePercentileStatus};
 use ethcore_miner::pool::{self, TransactionQueue, VerifiedTransaction, QueueStatus, PrioritizationStrategy};
 use ethcore_miner::work_notify::NotifyWork;
 use ethereum_types::{H256, U256, Address};
@@ -40,13 +40,16 @@ use using_queue::{UsingQueue, GetAction};
 use account_provider::{AccountProvider, SignError as AccountError};
 use block::{ClosedBlock, IsBlock, Block, SealedBlock};
 use client::{
-	BlockChain, ChainInfo, CallContract, BlockProducer, SealedBlockImporter, Nonce
+	BlockChain, BlockInfo, ChainInfo, CallContract, BlockProducer, SealedBlockImporter, Nonce
 };
 use client::BlockId;
+use encoded;
 use executive::contract_address;
 use header::{Header, BlockNumber};
 use miner;
+use miner::bundles::{Bundle, BundleStore};
 use miner::pool_client::{PoolClient, CachedNonceClient};
+use miner::PrioritySenderChecker;
 use receipt::{Receipt, RichReceipt};
 use spec::Spec;
 use state::State;
@@ -128,6 +131,12 @@ pub struct MinerOptions {
 	pub tx_queue_penalization: Penalization,
 	/// Do we refuse to accept service transactions even if sender is certified.
 	pub refuse_service_transactions: bool,
+	/// Senders whose transactions get reserved block space and are protected from eviction.
+	pub priority_senders: HashSet<Address>,
+	/// Should the priority senders registry contract be consulted as well.
+	pub priority_senders_contract: bool,
+	/// Percentage of block gas reserved for transactions of priority senders.
+	pub priority_gas_percent: usize,
 	/// Transaction pool limits.
 	pub pool_limits: pool::Options,
 	/// Initial transaction verification options.
@@ -150,15 +159,20 @@ impl Default for MinerOptions {
 			tx_queue_strategy: PrioritizationStrategy::GasPriceOnly,
 			tx_queue_penalization: Penalization::Disabled,
 			refuse_service_transactions: false,
+			priority_senders: HashSet::new(),
+			priority_senders_contract: false,
+			priority_gas_percent: 0,
 			pool_limits: pool::Options {
 				max_count: 8_192,
 				max_per_sender: 81,
 				max_mem_usage: 4 * 1024 * 1024,
+				replace_bump_percent: None,
 			},
 			pool_verification_options: pool::verifier::Options {
 				minimal_gas_price: DEFAULT_MINIMAL_GAS_PRICE.into(),
 				block_gas_limit: U256::max_value(),
 				tx_gas_limit: U256::max_value(),
+				max_nonce_gap: None,
 			},
 		}
 	}
@@ -198,11 +212,14 @@ pub struct Miner {
 	sealing: Mutex<SealingWork>,
 	params: RwLock<AuthoringParams>,
 	listeners: RwLock<Vec<Box<NotifyWork>>>,
+	work_subscribers: RwLock<Vec<Weak<NotifyWork>>>,
 	nonce_cache: RwLock<HashMap<Address, U256>>,
 	gas_pricer: Mutex<GasPricer>,
 	options: MinerOptions,
 	// TODO [ToDr] Arc is only required because of price updater
 	transaction_queue: Arc<TransactionQueue>,
+	bundles: RwLock<BundleStore>,
+	priority_senders: PrioritySenderChecker,
 	engine: Arc<EthEngine>,
 	accounts: Option<Arc<AccountProvider>>,
 }
@@ -214,16 +231,28 @@ impl Miner {
 		self.sealing.lock().enabled = true;
 	}
 
+	/// Push listener that will be notified about new jobs, without enabling sealing.
+	/// Work is only prepared when requested, e.g. by `eth_getWork` or other work listeners.
+	pub fn add_work_subscriber(&self, notifier: Weak<NotifyWork>) {
+		self.work_subscribers.write().push(notifier);
+	}
+
 	/// Set a callback to be notified about imported transactions' hashes.
 	pub fn add_transactions_listener(&self, f: Box<Fn(&[H256]) + Send + Sync>) {
 		self.transaction_queue.add_listener(f);
 	}
 
+	/// Set a callback to be notified about events of every transaction in the pool.
+	pub fn add_transaction_events_listener(&self, f: Box<Fn(&[(H256, pool::TransactionEvent)]) + Send + Sync>) {
+		self.transaction_queue.add_event_listener(f);
+	}
+
 	/// Creates new instance of miner Arc.
 	pub fn new(options: MinerOptions, gas_pricer: GasPricer, spec: &Spec, accounts: Option<Arc<AccountProvider>>) -> Self {
 		let limits = options.pool_limits.clone();
 		let verifier_options = options.pool_verification_options.clone();
 		let tx_queue_strategy = options.tx_queue_strategy;
+		let priority_senders = PrioritySenderChecker::new(options.priority_senders.clone(), options.priority_senders_contract);
 
 		Miner {
 			sealing: Mutex::new(SealingWork {
@@ -236,10 +265,13 @@ impl Miner {
 			}),
 			params: RwLock::new(AuthoringParams::default()),
 			listeners: RwLock::new(vec![]),
+			work_subscribers: RwLock::new(vec![]),
 			gas_pricer: Mutex::new(gas_pricer),
 			nonce_cache: RwLock::new(HashMap::with_capacity(1024)),
 			options,
 			transaction_queue: Arc::new(TransactionQueue::new(limits, verifier_options, tx_queue_strategy)),
+			bundles: RwLock::new(BundleStore::default()),
+			priority_senders,
 			accounts,
 			engine: spec.engine.clone(),
 		}
@@ -255,6 +287,7 @@ impl Miner {
 				minimal_gas_price,
 				block_gas_limit: U256::max_value(),
 				tx_gas_limit: U256::max_value(),
+				max_nonce_gap: None,
 			},
 			reseal_min_period: Duration::from_secs(0),
 			..Default::default()
@@ -281,6 +314,65 @@ impl Miner {
 		});
 	}
 
+	/// Samples gas prices of transactions included in the most recent blocks
+	/// if the minimal gas price is learned from them.
+	///
+	/// Meant to be called once on startup; afterwards the samples are kept
+	/// up to date with the blocks enacted and retracted on import.
+	pub fn init_gas_price_samples<C: BlockInfo>(&self, chain: &C) {
+		let blocks = match self.gas_pricer.lock().sample_blocks() {
+			Some(blocks) => blocks,
+			None => return,
+		};
+
+		let mut hash = chain.best_block_header().hash();
+		let mut recent = Vec::new();
+		while recent.len() < blocks {
+			let block = match chain.block(BlockId::Hash(hash)) {
+				Some(ref block) if block.number() == 0 => break,
+				Some(block) => block,
+				None => break,
+			};
+			hash = block.parent_hash();
+			recent.push(block_gas_prices(&block));
+		}
+		recent.reverse();
+
+		self.gas_pricer.lock().update_samples(&[], recent);
+	}
+
+	// move the window of sampled gas prices along an import route.
+	fn update_gas_price_samples<C: BlockInfo>(&self, chain: &C, enacted: &[H256], retracted: &[H256]) {
+		let blocks = match self.gas_pricer.lock().sample_blocks() {
+			Some(blocks) => blocks,
+			None => return,
+		};
+
+		// older blocks would fall out of the window right away.
+		let enacted = enacted[enacted.len().saturating_sub(blocks)..].iter()
+			.filter_map(|hash| chain.block(BlockId::Hash(*hash)))
+			.map(|block| block_gas_prices(&block))
+			.collect();
+
+		self.gas_pricer.lock().update_samples(retracted, enacted);
+	}
+
+	/// Re-imports transactions restored from a dump of the pool made on shutdown.
+	///
+	/// Transactions are fully verified again and keep their restored marker while in the pool.
+	pub fn import_restored_transactions<C: miner::BlockChainClient>(
+		&self,
+		chain: &C,
+		transactions: Vec<UnverifiedTransaction>,
+	) -> Vec<Result<(), transaction::Error>> {
+		trace!(target: "external_tx", "Importing restored transactions");
+		let client = self.pool_client(chain);
+		self.transaction_queue.import(
+			client,
+			transactions.into_iter().map(pool::verifier::Transaction::Restored).collect(),
+		)
+	}
+
 	/// Retrieves an existing pending block iff it's not older than given block number.
 	///
 	/// NOTE: This will not prepare a new pending block if it's not existing.
@@ -306,6 +398,7 @@ impl Miner {
 			&*self.engine,
 			self.accounts.as_ref().map(|x| &**x),
 			self.options.refuse_service_transactions,
+			Some(&self.priority_senders),
 		)
 	}
 
@@ -382,9 +475,64 @@ impl Miner {
 		};
 
 		let block_start = Instant::now();
+
+		// Bundles go first, each one is either included as a whole or skipped.
+		let bundles = self.bundles.read().ready(block_number);
+		for bundle in bundles {
+			let verified = bundle.transactions.iter().map(|tx| client.verify_signed(tx)).collect::<Result<Vec<_>, _>>();
+			let result = verified
+				.map_err(|e| e.into())
+				.and_then(|_| open_block.push_bundle(bundle.transactions.clone()));
+
+			match result {
+				Ok(true) => {
+					debug!(target: "miner", "Pushed bundle {:?} with {} transactions.", bundle.hash, bundle.transactions.len());
+					tx_count += bundle.transactions.len();
+				},
+				Ok(false) => {
+					debug!(target: "miner", "Skipping bundle {:?}: one of the transactions failed.", bundle.hash);
+				},
+				Err(e) => {
+					debug!(target: "miner", "Skipping bundle {:?}: {:?}", bundle.hash, e);
+				},
+			}
+		}
+
 		debug!(target: "miner", "Attempting to push {} transactions.", pending.len());
 
-		for tx in pending {
+		// Transactions of priority senders go first and may use the whole block,
+		// other transactions can't use the gas reserved for priority senders.
+		let priority_senders: HashSet<Address> = pending.iter()
+			.filter(|tx| tx.is_from_priority_sender())
+			.map(|tx| tx.signed().sender())
+			.collect();
+		let (priority, regular): (Vec<_>, Vec<_>) = pending.into_iter()
+			.partition(|tx| priority_senders.contains(&tx.signed().sender()));
+		let gas_limit = *open_block.block().header().gas_limit();
+		let reserved_gas = gas_limit / U256::from(100) * U256::from(self.options.priority_gas_percent);
+		let gas_used_before = open_block.block().receipts().last().map_or_else(U256::zero, |r| r.gas_used);
+		let mut regular_gas_limit = None;
+
+		let pending = priority.into_iter().map(|tx| (tx, true)).chain(regular.into_iter().map(|tx| (tx, false)));
+		for (tx, is_priority) in pending {
+			if !is_priority && !reserved_gas.is_zero() {
+				let gas_used = open_block.block().receipts().last().map_or_else(U256::zero, |r| r.gas_used);
+				let limit = *regular_gas_limit.get_or_insert_with(|| {
+					let priority_gas_used = gas_used - gas_used_before;
+					gas_limit - reserved_gas.saturating_sub(priority_gas_used)
+				});
+
+				if gas_used + tx.signed().gas > limit {
+					debug!(target: "miner", "Skipping adding transaction to block because of gas reserved for priority senders: {:?}", tx.signed().hash());
+					skipped_transactions += 1;
+					if skipped_transactions > MAX_SKIPPED_TRANSACTIONS {
+						debug!(target: "miner", "Reached skipped transactions threshold. Assuming block is full.");
+						break;
+					}
+					continue;
+				}
+			}
+
 			let start = Instant::now();
 
 			let transaction = tx.signed().clone();
@@ -599,6 +747,13 @@ impl Miner {
 		}
 	}
 
+	/// Returns true if any work subscriber is waiting for new work.
+	fn has_active_work_subscribers(&self) -> bool {
+		self.work_subscribers.read().iter()
+			.filter_map(|subscriber| subscriber.upgrade())
+			.any(|subscriber| subscriber.is_subscribed())
+	}
+
 	/// Prepares work which has to be done to seal.
 	fn prepare_work(&self, block: ClosedBlock, original_work_hash: Option<H256>) {
 		let (work, is_new) = {
@@ -624,7 +779,7 @@ impl Miner {
 
 				sealing.queue.push(block);
 				// If push notifications are enabled we assume all work items are used.
-				if is_new && !self.listeners.read().is_empty() {
+				if is_new && (!self.listeners.read().is_empty() || self.has_active_work_subscribers()) {
 					sealing.queue.use_last_ref();
 				}
 
@@ -644,6 +799,13 @@ impl Miner {
 				for notifier in self.listeners.read().iter() {
 					notifier.notify(pow_hash, difficulty, number)
 				}
+				self.work_subscribers.write().retain(|subscriber| match subscriber.upgrade() {
+					Some(notifier) => {
+						notifier.notify(pow_hash, difficulty, number);
+						true
+					},
+					None => false,
+				});
 			});
 		}
 	}
@@ -692,6 +854,14 @@ impl Miner {
 
 const SEALING_TIMEOUT_IN_BLOCKS : u64 = 5;
 
+fn block_gas_prices(block: &encoded::Block) -> BlockGasPrices {
+	BlockGasPrices {
+		number: block.number(),
+		hash: block.hash(),
+		gas_prices: block.transaction_views().iter().map(|t| t.gas_price()).collect(),
+	}
+}
+
 impl miner::MinerService for Miner {
 	type State = State<::state_db::StateDB>;
 
@@ -797,6 +967,31 @@ impl miner::MinerService for Miner {
 		imported
 	}
 
+	fn submit_bundle<C: miner::BlockChainClient>(
+		&self,
+		chain: &C,
+		transactions: Vec<SignedTransaction>,
+		target_block: BlockNumber,
+	) -> H256 {
+		let bundle = Bundle::new(transactions, target_block);
+		let hash = bundle.hash;
+
+		trace!(target: "miner", "Submitting bundle {:?} with {} transactions", hash, bundle.transactions.len());
+		self.bundles.write().insert(bundle);
+
+		// --------------------------------------------------------------------------
+		// | NOTE Code below requires sealing locks.                                |
+		// | Make sure to release the locks before calling that method.             |
+		// --------------------------------------------------------------------------
+		if self.options.reseal_on_own_tx && self.sealing.lock().reseal_allowed() {
+			if self.engine.seals_internally().unwrap_or(false) || !self.prepare_pending_block(chain) {
+				self.update_sealing(chain);
+			}
+		}
+
+		hash
+	}
+
 	fn local_transactions(&self) -> BTreeMap<H256, pool::local_transactions::Status> {
 		self.transaction_queue.local_transactions()
 	}
@@ -866,6 +1061,10 @@ impl miner::MinerService for Miner {
 		self.transaction_queue.status()
 	}
 
+	fn gas_price_percentile_status(&self) -> Option<GasPricePercentileStatus> {
+		self.gas_pricer.lock().percentile_status()
+	}
+
 	fn pending_receipt(&self, best_block: BlockNumber, hash: &H256) -> Option<RichReceipt> {
 		self.map_existing_pending_block(|pending| {
 			let txs = pending.transactions();
@@ -1014,6 +1213,7 @@ impl miner::MinerService for Miner {
 		self.nonce_cache.write().clear();
 
 		// First update gas limit in transaction queue and minimal gas price.
+		self.update_gas_price_samples(chain, enacted, retracted);
 		let gas_limit = *chain.best_block_header().gas_limit();
 		self.update_transaction_queue_limits(gas_limit);
 
@@ -1039,6 +1239,15 @@ impl miner::MinerService for Miner {
 		// ...and at the end remove the old ones
 		self.transaction_queue.cull(client);
 
+		// Drop bundles which expired or got included.
+		if !self.bundles.read().is_empty() {
+			let included: HashSet<H256> = enacted.iter()
+				.filter_map(|hash| chain.block(BlockId::Hash(*hash)))
+				.flat_map(|block| block.transaction_hashes())
+				.collect();
+			self.bundles.write().cull(chain.best_block_header().number(), &included);
+		}
+
 		if enacted.len() > 0 || (imported.len() > 0 && self.options.reseal_on_uncle) {
 			// Reset `next_allowed_reseal` in case a block is imported.
 			// Even if min_period is high, we will always attempt to create
@@ -1075,14 +1284,16 @@ impl miner::MinerService for Miner {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
 	use ethkey::{Generator, Random};
 	use hash::keccak;
 	use header::BlockNumber;
 	use rustc_hex::FromHex;
 
 	use client::{TestBlockChainClient, EachBlockWith, ChainInfo, ImportSealedBlock};
+	use ethcore_miner::gas_pricer::GasPricePercentileOptions;
 	use miner::MinerService;
-	use test_helpers::{generate_dummy_client, generate_dummy_client_with_spec_and_accounts};
+	use test_helpers::{generate_dummy_client, generate_dummy_client_with_data, generate_dummy_client_with_spec_and_accounts};
 	use transaction::{Transaction};
 
 	#[test]
@@ -1096,6 +1307,92 @@ mod tests {
 		assert!(sealing_work.is_some(), "Expected closed block");
 	}
 
+	#[test]
+	fn should_notify_work_subscribers_without_enabling_sealing() {
+		struct Subscriber(Mutex<Vec<BlockNumber>>);
+
+		impl NotifyWork for Subscriber {
+			fn notify(&self, _pow_hash: H256, _difficulty: U256, number: u64) {
+				self.0.lock().push(number);
+			}
+		}
+
+		// given
+		let client = TestBlockChainClient::default();
+		let miner = Miner::new_for_tests(&Spec::new_test(), None);
+		let subscriber = Arc::new(Subscriber(Mutex::new(vec![])));
+		miner.add_work_subscriber(Arc::downgrade(&subscriber) as Weak<_>);
+		assert!(!miner.sealing.lock().enabled);
+
+		// when
+		miner.work_package(&client).unwrap();
+
+		// then
+		assert_eq!(*subscriber.0.lock(), vec![1]);
+
+		// when
+		drop(subscriber);
+		client.add_blocks(1, EachBlockWith::Nothing);
+		miner.work_package(&client).unwrap();
+
+		// then
+		assert!(miner.work_subscribers.read().is_empty());
+	}
+
+	#[test]
+	fn should_only_count_work_subscribers_with_subscriptions() {
+		struct Subscriber(AtomicBool);
+
+		impl NotifyWork for Subscriber {
+			fn notify(&self, _pow_hash: H256, _difficulty: U256, _number: u64) {}
+
+			fn is_subscribed(&self) -> bool {
+				self.0.load(AtomicOrdering::SeqCst)
+			}
+		}
+
+		// given
+		let miner = Miner::new_for_tests(&Spec::new_test(), None);
+		let subscriber = Arc::new(Subscriber(AtomicBool::new(false)));
+		miner.add_work_subscriber(Arc::downgrade(&subscriber) as Weak<_>);
+
+		// then
+		assert!(!miner.has_active_work_subscribers());
+
+		// when
+		subscriber.0.store(true, AtomicOrdering::SeqCst);
+
+		// then
+		assert!(miner.has_active_work_subscribers());
+
+		// when
+		drop(subscriber);
+
+		// then
+		assert!(!miner.has_active_work_subscribers());
+	}
+
+	#[test]
+	fn should_learn_minimal_gas_price_from_recent_blocks() {
+		// given
+		let client = generate_dummy_client_with_data(10, 1, slice_into![1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+		let gas_pricer = GasPricer::new_percentile(GasPricePercentileOptions {
+			percentile: 50,
+			blocks: 4,
+		});
+		let miner = Miner::new(MinerOptions::default(), gas_pricer, &Spec::new_test(), None);
+
+		// when
+		miner.init_gas_price_samples(&*client);
+		miner.update_transaction_queue_limits(*client.best_block_header().gas_limit());
+
+		// then
+		assert_eq!(miner.queue_status().options.minimal_gas_price, 8.into());
+		let status = miner.gas_price_percentile_status().unwrap();
+		assert_eq!(status.block_number, 10);
+		assert_eq!(status.transactions, 4);
+	}
+
 	#[test]
 	fn should_still_work_after_a_couple_of_blocks() {
 		// given
@@ -1134,11 +1431,15 @@ mod tests {
 				tx_queue_penalization: Penalization::Disabled,
 				tx_queue_strategy: PrioritizationStrategy::GasPriceOnly,
 				refuse_service_transactions: false,
+				priority_senders: HashSet::new(),
+				priority_senders_contract: false,
+				priority_gas_percent: 0,
 				pool_limits: Default::default(),
 				pool_verification_options: pool::verifier::Options {
 					minimal_gas_price: 0.into(),
 					block_gas_limit: U256::max_value(),
 					tx_gas_limit: U256::max_value(),
+					max_nonce_gap: None,
 				},
 			},
 			GasPricer::new_fixed(0u64.into()),
@@ -1222,6 +1523,143 @@ mod tests {
 		assert_eq!(miner.ready_transactions(&client).len(), 1);
 	}
 
+	fn bundle(nonces: &[u64]) -> Vec<SignedTransaction> {
+		let keypair = Random.generate().unwrap();
+		nonces.iter().map(|nonce| Transaction {
+			action: Action::Create,
+			value: U256::zero(),
+			data: "3331600055".from_hex().unwrap(),
+			gas: U256::from(100_000),
+			gas_price: U256::zero(),
+			nonce: (*nonce).into(),
+		}.sign(keypair.secret(), Some(2))).collect()
+	}
+
+	#[test]
+	fn should_include_bundle_in_pending_block() {
+		// given
+		let client = TestBlockChainClient::new_with_spec(Spec::new_null());
+		let miner = miner();
+		let transactions = bundle(&[0, 1]);
+		let hashes: Vec<_> = transactions.iter().map(|tx| tx.hash()).collect();
+
+		// when
+		miner.submit_bundle(&client, transactions, 10);
+
+		// then
+		let pending = miner.pending_transactions(0).unwrap();
+		assert_eq!(pending.iter().map(|tx| tx.hash()).collect::<Vec<_>>(), hashes);
+	}
+
+	#[test]
+	fn should_not_include_bundle_partially() {
+		// given
+		let client = TestBlockChainClient::new_with_spec(Spec::new_null());
+		let miner = miner();
+
+		// when
+		miner.submit_bundle(&client, bundle(&[0, 2]), 10);
+
+		// then
+		assert_eq!(miner.pending_transactions(0).unwrap().len(), 0);
+	}
+
+	#[test]
+	fn should_not_include_bundle_with_failing_transaction() {
+		// given
+		let client = TestBlockChainClient::new_with_spec(Spec::new_null());
+		let miner = miner();
+		let mut transactions = bundle(&[0, 1]);
+		let keypair = Random.generate().unwrap();
+		// init code consisting of the invalid instruction
+		transactions.push(Transaction {
+			action: Action::Create,
+			value: U256::zero(),
+			data: vec![0xfe],
+			gas: U256::from(100_000),
+			gas_price: U256::zero(),
+			nonce: U256::zero(),
+		}.sign(keypair.secret(), Some(2)));
+
+		// when
+		miner.submit_bundle(&client, transactions, 10);
+
+		// then
+		assert_eq!(miner.pending_transactions(0).unwrap().len(), 0);
+	}
+
+	#[test]
+	fn should_not_include_bundle_before_eip658() {
+		// given
+		let client = TestBlockChainClient::default();
+		let miner = miner();
+
+		// when
+		miner.submit_bundle(&client, bundle(&[0, 1]), 10);
+
+		// then
+		assert_eq!(miner.pending_transactions(0).unwrap().len(), 0);
+	}
+
+	#[test]
+	fn should_not_include_expired_bundle() {
+		// given
+		let client = TestBlockChainClient::new_with_spec(Spec::new_null());
+		let miner = miner();
+
+		// when
+		miner.submit_bundle(&client, bundle(&[0, 1]), 0);
+
+		// then
+		assert_eq!(miner.pending_transactions(0).unwrap().len(), 0);
+	}
+
+	#[test]
+	fn should_reserve_block_gas_for_priority_senders() {
+		// given
+		let client = TestBlockChainClient::default();
+		let regular = transaction();
+		let priority = transaction();
+		let miner = Miner::new(MinerOptions {
+			pending_set: PendingSet::AlwaysSealing,
+			priority_senders: vec![priority.sender()].into_iter().collect(),
+			priority_gas_percent: 100,
+			pool_verification_options: pool::verifier::Options {
+				minimal_gas_price: 0.into(),
+				block_gas_limit: U256::max_value(),
+				tx_gas_limit: U256::max_value(),
+				max_nonce_gap: None,
+			},
+			..Default::default()
+		}, GasPricer::new_fixed(0u64.into()), &Spec::new_test(), None);
+
+		// when
+		let res = miner.import_external_transactions(&client, vec![regular.into(), priority.clone().into()]);
+		assert!(miner.prepare_pending_block(&client));
+
+		// then
+		assert_eq!(res, vec![Ok(()), Ok(())]);
+		assert_eq!(miner.ready_transactions(&client).iter().map(|tx| tx.signed().hash()).collect::<Vec<_>>(), vec![priority.hash()]);
+	}
+
+	#[test]
+	fn should_import_restored_transaction() {
+		// given
+		let client = TestBlockChainClient::default();
+		let miner = miner();
+		let transaction = transaction().into();
+
+		// when
+		let res = miner.import_restored_transactions(&client, vec![transaction]).pop().unwrap();
+
+		// then
+		assert_eq!(res.unwrap(), ());
+		let queued = miner.queued_transactions();
+		assert_eq!(queued.len(), 1);
+		assert!(queued[0].is_restored());
+		assert!(miner.local_transactions().is_empty());
+	}
+
 	#[test]
 	fn should_not_seal_unless_enabled() {
 		let miner = miner();
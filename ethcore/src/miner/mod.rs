@@ -20,18 +20,22 @@
 //! Keeps track of transactions and currently sealed pending block.
 
 mod miner;
+mod priority_sender_checker;
 mod service_transaction_checker;
 
+pub mod bundles;
 pub mod pool_client;
 pub mod stratum;
 
 pub use self::miner::{Miner, MinerOptions, Penalization, PendingSet, AuthoringParams};
+pub use self::priority_sender_checker::PrioritySenderChecker;
 
 use std::sync::Arc;
 use std::collections::BTreeMap;
 
 use bytes::Bytes;
 use ethereum_types::{H256, U256, Address};
+use ethcore_miner::gas_pricer::GasPricePercentileStatus;
 use ethcore_miner::pool::{VerifiedTransaction, QueueStatus, local_transactions};
 
 use block::{Block, SealedBlock};
@@ -139,6 +143,13 @@ pub trait MinerService : Send + Sync {
 		-> Result<(), transaction::Error>
 		where C: BlockChainClient;
 
+	/// Submits a bundle of transactions to be included in authored blocks
+	/// atomically and in the given order, up to the `target_block`.
+	/// Bundles are never included in blocks before `eip658Transition`.
+	/// Returns the hash of the bundle.
+	fn submit_bundle<C>(&self, chain: &C, transactions: Vec<SignedTransaction>, target_block: BlockNumber) -> H256
+		where C: BlockChainClient;
+
 	/// Removes transaction from the pool.
 	///
 	/// Attempts to "cancel" a transaction. If it was not propagated yet (or not accepted by other peers)
@@ -175,6 +186,10 @@ pub trait MinerService : Send + Sync {
 	/// Status includes verification thresholds and current pool utilization and limits.
 	fn queue_status(&self) -> QueueStatus;
 
+	/// Get the inputs and result of the last minimal gas price calibration,
+	/// if the minimal gas price is learned from recently included transactions.
+	fn gas_price_percentile_status(&self) -> Option<GasPricePercentileStatus>;
+
 	// Misc
 
 	/// Suggested gas price.
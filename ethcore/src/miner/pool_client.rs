@@ -34,6 +34,7 @@ use client::{TransactionId, BlockInfo, CallContract, Nonce};
 use engines::EthEngine;
 use header::Header;
 use miner;
+use miner::priority_sender_checker::PrioritySenderChecker;
 use miner::service_transaction_checker::ServiceTransactionChecker;
 
 type NoncesCache = RwLock<HashMap<Address, U256>>;
@@ -49,6 +50,7 @@ pub struct PoolClient<'a, C: 'a> {
 	accounts: Option<&'a AccountProvider>,
 	best_block_header: Header,
 	service_transaction_checker: Option<ServiceTransactionChecker>,
+	priority_sender_checker: Option<&'a PrioritySenderChecker>,
 }
 
 impl<'a, C: 'a> Clone for PoolClient<'a, C> {
@@ -60,6 +62,7 @@ impl<'a, C: 'a> Clone for PoolClient<'a, C> {
 			accounts: self.accounts.clone(),
 			best_block_header: self.best_block_header.clone(),
 			service_transaction_checker: self.service_transaction_checker.clone(),
+			priority_sender_checker: self.priority_sender_checker,
 		}
 	}
 }
@@ -67,13 +70,15 @@ impl<'a, C: 'a> Clone for PoolClient<'a, C> {
 impl<'a, C: 'a> PoolClient<'a, C> where
 C: BlockInfo + CallContract,
 {
-	/// Creates new client given chain, nonce cache, accounts and service transaction verifier.
+	/// Creates new client given chain, nonce cache, accounts, service transaction verifier
+	/// and priority senders checker.
 	pub fn new(
 		chain: &'a C,
 		cache: &'a NoncesCache,
 		engine: &'a EthEngine,
 		accounts: Option<&'a AccountProvider>,
 		refuse_service_transactions: bool,
+		priority_sender_checker: Option<&'a PrioritySenderChecker>,
 	) -> Self {
 		let best_block_header = chain.best_block_header();
 		PoolClient {
@@ -87,6 +92,7 @@ C: BlockInfo + CallContract,
 			} else {
 				Some(Default::default())
 			},
+			priority_sender_checker,
 		}
 	}
 
@@ -125,6 +131,13 @@ impl<'a, C: 'a> pool::client::Client for PoolClient<'a, C> where
 			nonce: self.cached_nonces.account_nonce(address),
 			balance: self.chain.latest_balance(address),
 			is_local: self.accounts.map_or(false, |accounts| accounts.has_account(*address).unwrap_or(false)),
+			is_priority: self.priority_sender_checker.map_or(false, |checker| match checker.check(self.chain, address) {
+				Ok(is_priority) => is_priority,
+				Err(e) => {
+					debug!(target: "txqueue", "Unable to verify priority sender: {:?}", e);
+					false
+				},
+			}),
 		}
 	}
 
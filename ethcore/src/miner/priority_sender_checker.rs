@@ -0,0 +1,70 @@
+// Copyright 2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity. If not, see <http://www.gnu.org/licenses/>.
+
+//! A priority senders checker.
+//!
+//! Transactions of priority senders get a reserved share of block gas
+//! and are never evicted from the pool in favour of regular transactions.
+
+use std::collections::HashSet;
+
+use client::{RegistryInfo, CallContract, BlockId};
+use ethereum_types::Address;
+
+use_contract!(priority_sender, "PrioritySender", "res/contracts/priority_sender.json");
+
+const PRIORITY_SENDER_CONTRACT_REGISTRY_NAME: &'static str = "priority_sender_checker";
+
+/// Priority senders checker.
+#[derive(Default, Clone)]
+pub struct PrioritySenderChecker {
+	senders: HashSet<Address>,
+	use_contract: bool,
+	contract: priority_sender::PrioritySender,
+}
+
+impl PrioritySenderChecker {
+	/// Creates new checker given a list of priority senders
+	/// and whether the registry contract should be consulted as well.
+	pub fn new(senders: HashSet<Address>, use_contract: bool) -> Self {
+		PrioritySenderChecker {
+			senders,
+			use_contract,
+			contract: Default::default(),
+		}
+	}
+
+	/// Checks if given address is a priority sender.
+	pub fn check<C: CallContract + RegistryInfo>(&self, client: &C, sender: &Address) -> Result<bool, String> {
+		if self.senders.contains(sender) {
+			return Ok(true)
+		}
+
+		if !self.use_contract {
+			return Ok(false)
+		}
+
+		let address = client.registry_address(PRIORITY_SENDER_CONTRACT_REGISTRY_NAME.to_owned(), BlockId::Latest)
+			.ok_or_else(|| "contract is not configured")?;
+
+		trace!(target: "txqueue", "Checking priority sender contract for {}", sender);
+
+		self.contract.functions()
+			.certified()
+			.call(*sender, &|data| client.call_contract(BlockId::Latest, address, data))
+			.map_err(|e| e.to_string())
+	}
+}
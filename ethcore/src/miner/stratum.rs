@@ -23,12 +23,14 @@ use std::fmt;
 use client::{Client, ImportSealedBlock};
 use ethereum_types::{H64, H256, clean_0x, U256};
 use ethereum::ethash::Ethash;
-use ethash::SeedHashCompute;
+use ethash::{SeedHashCompute, quick_get_difficulty};
 use ethcore_miner::work_notify::NotifyWork;
 use ethcore_stratum::{
-	JobDispatcher, PushWorkHandler,
+	JobDispatcher, PushWorkHandler, Share, ShareOutcome,
 	Stratum as StratumService, Error as StratumServiceError,
 };
+
+pub use ethcore_stratum::{PoolOptions, VardiffOptions};
 use miner::{Miner, MinerService};
 use parking_lot::Mutex;
 use rlp::encode;
@@ -44,6 +46,8 @@ pub struct Options {
 	pub port: u16,
 	/// Secret for peers
 	pub secret: Option<H256>,
+	/// Extranonce allocation and share difficulty
+	pub pool: PoolOptions,
 }
 
 struct SubmitPayload {
@@ -151,6 +155,41 @@ impl JobDispatcher for StratumJobDispatcher {
 			}
 		})
 	}
+
+	fn submit_share(&self, payload: Vec<String>, share: &Share) -> Result<ShareOutcome, StratumServiceError> {
+		let decoded = SubmitPayload::from_args(payload.clone()).map_err(|e|
+			StratumServiceError::Dispatch(e.to_string())
+		)?;
+
+		// workers must stay within their own part of the nonce space, with or without share difficulty.
+		if !format!("{:x}", decoded.nonce).starts_with(&share.extranonce) {
+			return Err(StratumServiceError::Dispatch(format!("Nonce {} does not start with extranonce {}", decoded.nonce, share.extranonce)));
+		}
+
+		let share_difficulty = match share.difficulty {
+			Some(difficulty) => difficulty,
+			None => return self.submit(payload).map(|_| ShareOutcome::Block),
+		};
+
+		// only a quick check for shares, block solutions are fully verified on import
+		let difficulty = Ethash::boundary_to_difficulty(&H256(quick_get_difficulty(
+			&decoded.pow_hash.0,
+			decoded.nonce.low_u64(),
+			&decoded.mix_hash.0,
+		)));
+		if difficulty < U256::from(share_difficulty) {
+			return Err(StratumServiceError::Dispatch(format!("Share difficulty {} below worker difficulty {}", difficulty, share_difficulty)));
+		}
+
+		let work_difficulty = self.with_core(|client, miner| miner.work_package(&*client)
+			.and_then(|(pow_hash, _number, _timestamp, difficulty)| if pow_hash == decoded.pow_hash { Some(difficulty) } else { None })
+		);
+		match work_difficulty {
+			Some(work_difficulty) if difficulty >= work_difficulty => self.submit(payload).map(|_| ShareOutcome::Block),
+			Some(_) => Ok(ShareOutcome::Share),
+			None => Err(StratumServiceError::Dispatch(format!("Stale share for {}", decoded.pow_hash))),
+		}
+	}
 }
 
 impl StratumJobDispatcher {
@@ -230,10 +269,11 @@ impl Stratum {
 
 		let dispatcher = Arc::new(StratumJobDispatcher::new(miner, client));
 
-		let stratum_svc = StratumService::start(
+		let stratum_svc = StratumService::start_with_options(
 			&SocketAddr::new(options.listen_addr.parse::<IpAddr>()?, options.port),
 			dispatcher.clone(),
 			options.secret.clone(),
+			options.pool.clone(),
 		)?;
 
 		Ok(Stratum {
@@ -0,0 +1,436 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Offline conversion of the state database between pruning algorithms.
+//!
+//! The state trie of the chosen blocks is walked node by node, together with
+//! the storage tries and code of every account, and copied into a database
+//! using the target `journaldb::Algorithm`.
+
+use std::sync::Arc;
+
+use account_db::{AccountDB, AccountDBMut};
+use blockchain::{BlockChain, BlockProvider};
+use db::{self, NUM_COLUMNS};
+use error::Error;
+use hash::{KECCAK_EMPTY, KECCAK_NULL_RLP};
+use types::basic_account::BasicAccount;
+
+use bytes::Bytes;
+use ethereum_types::H256;
+use hashdb::HashDB;
+use journaldb::{self, Algorithm, JournalDB};
+use kvdb::KeyValueDB;
+use rlp::{self, Rlp};
+use trie::TrieError;
+use trie::node::Node;
+use util_error::UtilError;
+
+/// Number of state nodes buffered in memory before being written out.
+const NODES_PER_BATCH: usize = 16 * 1024;
+
+// a trie node reference along with the key nibbles leading to it.
+type PathedHash = (H256, Vec<u8>);
+
+// collect child node references and leaf values of a single trie node.
+// nodes shorter than 32 bytes are inlined into their parent, so recurse into them.
+fn node_refs(node: &[u8], path: &[u8], children: &mut Vec<PathedHash>, leaves: &mut Vec<(Vec<u8>, Bytes)>) -> Result<(), Error> {
+	fn child(raw: &[u8], path: Vec<u8>, children: &mut Vec<PathedHash>, leaves: &mut Vec<(Vec<u8>, Bytes)>) -> Result<(), Error> {
+		let rlp = Rlp::new(raw);
+		if rlp.is_list() {
+			node_refs(raw, &path, children, leaves)
+		} else {
+			if rlp.size() == 32 {
+				children.push((rlp.as_val()?, path));
+			}
+			Ok(())
+		}
+	}
+
+	let extend = |partial: &[u8]| path.iter().chain(partial).cloned().collect::<Vec<_>>();
+
+	match Node::decoded(node).map_err(TrieError::DecoderError)? {
+		Node::Empty => {},
+		Node::Leaf(partial, value) => {
+			let partial: Vec<_> = (0..partial.len()).map(|i| partial.at(i)).collect();
+			leaves.push((extend(&partial), value.to_vec()));
+		},
+		Node::Extension(partial, raw) => {
+			let partial: Vec<_> = (0..partial.len()).map(|i| partial.at(i)).collect();
+			child(raw, extend(&partial), children, leaves)?;
+		},
+		Node::Branch(nodes, value) => {
+			for (i, raw) in nodes.iter().enumerate() {
+				child(raw, extend(&[i as u8]), children, leaves)?;
+			}
+			if let Some(value) = value {
+				leaves.push((path.to_vec(), value.to_vec()));
+			}
+		},
+	}
+
+	Ok(())
+}
+
+// pack a full-length nibble path back into the hashed key.
+fn nibbles_to_hash(nibbles: &[u8]) -> Result<H256, Error> {
+	if nibbles.len() != 64 {
+		return Err(TrieError::DecoderError(rlp::DecoderError::Custom("Unexpected state trie key length.")).into());
+	}
+
+	let mut hash = H256::default();
+	for (i, pair) in nibbles.chunks(2).enumerate() {
+		hash[i] = (pair[0] << 4) | pair[1];
+	}
+	Ok(hash)
+}
+
+// copy every node of the trie at `root` which is not present in `to` yet.
+// returns the number of copied nodes.
+fn copy_trie(from: &HashDB, to: &mut HashDB, root: &H256) -> Result<usize, Error> {
+	let mut stack = vec![(*root, Vec::new())];
+	let mut leaves = Vec::new();
+	let mut copied = 0;
+
+	while let Some((hash, path)) = stack.pop() {
+		if to.contains(&hash) {
+			continue;
+		}
+
+		let node = from.get(&hash).ok_or_else(|| TrieError::IncompleteDatabase(hash))?;
+		node_refs(&node, &path, &mut stack, &mut leaves)?;
+		to.emplace(hash, node);
+		leaves.clear();
+		copied += 1;
+	}
+
+	Ok(copied)
+}
+
+// copy the storage trie and code of a single account.
+fn copy_account(from: &HashDB, to: &mut HashDB, address_hash: H256, account: &BasicAccount) -> Result<usize, Error> {
+	let from = AccountDB::from_hash(from, address_hash);
+	let mut to = AccountDBMut::from_hash(to, address_hash);
+
+	let mut copied = 0;
+	if account.storage_root != KECCAK_NULL_RLP {
+		copied += copy_trie(&from, &mut to, &account.storage_root)?;
+	}
+
+	if account.code_hash != KECCAK_EMPTY && !to.contains(&account.code_hash) {
+		let code = from.get(&account.code_hash).ok_or_else(|| TrieError::IncompleteDatabase(account.code_hash))?;
+		to.emplace(account.code_hash, code);
+		copied += 1;
+	}
+
+	Ok(copied)
+}
+
+fn inject(to: &mut JournalDB) -> Result<(), Error> {
+	let mut batch = to.backing().transaction();
+	to.inject(&mut batch)?;
+	to.backing().write(batch).map_err(UtilError::from)?;
+	Ok(())
+}
+
+/// Copy the state at `root` from `from` into `to`, including account storage and code.
+///
+/// Subtries already present in `to` are skipped, so copying consecutive states
+/// only transfers what changed between them. Nodes are injected into `to` as
+/// they are copied; `progress` is called with the running total of copied nodes.
+pub fn copy_state(from: &HashDB, to: &mut JournalDB, root: &H256, progress: &mut FnMut(usize)) -> Result<usize, Error> {
+	let mut stack = vec![(*root, Vec::new())];
+	let mut accounts = Vec::new();
+	let mut total = 0;
+	let mut pending = 0;
+
+	while let Some((hash, path)) = stack.pop() {
+		if hash == KECCAK_NULL_RLP || to.contains(&hash) {
+			continue;
+		}
+
+		let node = from.get(&hash).ok_or_else(|| TrieError::IncompleteDatabase(hash))?;
+		node_refs(&node, &path, &mut stack, &mut accounts)?;
+		to.emplace(hash, node);
+		pending += 1;
+
+		for (path, account) in accounts.drain(..) {
+			let account: BasicAccount = rlp::decode(&account)?;
+			pending += copy_account(from, to.as_hashdb_mut(), nibbles_to_hash(&path)?, &account)?;
+		}
+
+		if pending >= NODES_PER_BATCH {
+			total += pending;
+			pending = 0;
+			inject(to)?;
+			progress(total);
+		}
+	}
+
+	total += pending;
+	inject(to)?;
+	progress(total);
+	Ok(total)
+}
+
+/// Rewrite the client database `from`, pruned with `from_algorithm`, into the empty
+/// database `to` using `to_algorithm`.
+///
+/// Everything except the state is copied as is; the state is copied at the best block only.
+/// Returns the number of the block whose state was copied.
+pub fn convert(
+	from: Arc<KeyValueDB>,
+	from_algorithm: Algorithm,
+	to: Arc<KeyValueDB>,
+	to_algorithm: Algorithm,
+	progress: &mut FnMut(usize),
+) -> Result<u64, Error> {
+	for col in (0..NUM_COLUMNS.unwrap_or(0)).filter(|col| Some(*col) != db::COL_STATE) {
+		let mut batch = to.transaction();
+		for (i, (key, value)) in from.iter(Some(col)).enumerate() {
+			batch.put(Some(col), &key, &value);
+			if i % NODES_PER_BATCH == NODES_PER_BATCH - 1 {
+				to.write(batch).map_err(UtilError::from)?;
+				batch = to.transaction();
+			}
+		}
+		to.write(batch).map_err(UtilError::from)?;
+	}
+
+	let chain = BlockChain::new(Default::default(), &[], from.clone());
+	let best = chain.best_block_header();
+
+	let source = journaldb::new(from, from_algorithm, db::COL_STATE);
+	let mut target = journaldb::new(to, to_algorithm, db::COL_STATE);
+	copy_state(source.as_hashdb(), &mut *target, best.state_root(), progress)?;
+
+	let mut batch = target.backing().transaction();
+	target.journal_under(&mut batch, best.number(), &best.hash())?;
+	target.backing().write(batch).map_err(UtilError::from)?;
+
+	Ok(best.number())
+}
+
+/// Shrink the state of an archive client database `db` down to the last `keep` states.
+///
+/// The retained states are first copied into the empty database `scratch`, which
+/// can be removed afterwards; every other state node is then deleted from `db`.
+/// Returns the number of deleted state nodes.
+pub fn shrink_archive(db: Arc<KeyValueDB>, scratch: Arc<KeyValueDB>, keep: u64, progress: &mut FnMut(usize)) -> Result<usize, Error> {
+	let chain = BlockChain::new(Default::default(), &[], db.clone());
+	let best = chain.best_block_number();
+	let first = best.saturating_sub(keep.saturating_sub(1));
+
+	let source = journaldb::new(db.clone(), Algorithm::Archive, db::COL_STATE);
+	let mut retained = journaldb::new(scratch.clone(), Algorithm::Archive, db::COL_STATE);
+	let mut copied = 0;
+
+	for number in first..(best + 1) {
+		let header = chain.block_hash(number)
+			.and_then(|hash| chain.block_header_data(&hash))
+			.ok_or_else(|| UtilError::from(format!("Missing header of block #{}", number)))?;
+
+		let base = copied;
+		copied += copy_state(source.as_hashdb(), &mut *retained, &header.state_root(), &mut |n| progress(base + n))?;
+	}
+
+	// only 32-byte keys are state nodes; anything else is journaldb metadata.
+	let mut deleted = 0;
+	let mut batch = db.transaction();
+	for (key, _) in db.iter(db::COL_STATE).filter(|&(ref key, _)| key.len() == 32) {
+		if scratch.get(db::COL_STATE, &key).map_err(UtilError::from)?.is_none() {
+			batch.delete(db::COL_STATE, &key);
+			deleted += 1;
+			if deleted % NODES_PER_BATCH == 0 {
+				db.write(batch).map_err(UtilError::from)?;
+				batch = db.transaction();
+			}
+		}
+	}
+	db.write(batch).map_err(UtilError::from)?;
+
+	Ok(deleted)
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use account_db::AccountDBMut;
+	use hash::keccak;
+	use journaldb::{self, Algorithm};
+	use kvdb_memorydb;
+	use trie::{TrieDBMut, TrieMut};
+	use types::basic_account::BasicAccount;
+	use db::{COL_STATE, NUM_COLUMNS};
+	use ethereum_types::{H256, U256};
+	use hashdb::{HashDB, DBValue};
+	use rlp;
+
+	use blockchain::BlockChain;
+	use client::{BlockChainClient, BlockId, ChainInfo, Client, ClientConfig};
+	use ethkey::KeyPair;
+	use factory::Factories;
+	use io::IoChannel;
+	use kvdb::KeyValueDB;
+	use miner::Miner;
+	use spec::Spec;
+	use state::State;
+	use state_db::StateDB;
+	use test_helpers::push_block_with_transactions;
+	use transaction::{Action, Transaction};
+
+	use super::{convert, copy_state, shrink_archive};
+
+	// archive client database with `blocks` blocks, each of them bumping the nonce of `sender`.
+	fn archive_client_db(sender: &KeyPair, blocks: u64) -> (Arc<KeyValueDB>, Arc<Client>) {
+		let spec = Spec::new_test();
+		let db: Arc<KeyValueDB> = Arc::new(kvdb_memorydb::create(NUM_COLUMNS.unwrap()));
+		let config = ClientConfig { pruning: Algorithm::Archive, ..Default::default() };
+		let client = Client::new(
+			config,
+			&spec,
+			db.clone(),
+			Arc::new(Miner::new_for_tests(&spec, None)),
+			IoChannel::disconnected(),
+		).unwrap();
+
+		for nonce in 0..blocks {
+			let transaction = Transaction {
+				nonce: nonce.into(),
+				gas_price: 0.into(),
+				gas: 21000.into(),
+				action: Action::Call(Default::default()),
+				value: 0.into(),
+				data: vec![],
+			}.sign(sender.secret(), Some(spec.chain_id()));
+			push_block_with_transactions(&client, &[transaction]);
+		}
+
+		assert_eq!(client.chain_info().best_block_number, blocks);
+		(db, client)
+	}
+
+	fn state_root(client: &Client, number: u64) -> H256 {
+		client.block_header(BlockId::Number(number)).unwrap().state_root()
+	}
+
+	fn state_keys(db: &Arc<KeyValueDB>) -> usize {
+		db.iter(COL_STATE).filter(|&(ref key, _)| key.len() == 32).count()
+	}
+
+	#[test]
+	fn copies_state_between_algorithms() {
+		let source_db = Arc::new(kvdb_memorydb::create(NUM_COLUMNS.unwrap()));
+		let mut source = journaldb::new(source_db, Algorithm::Archive, COL_STATE);
+
+		let address_hash = keccak(b"account");
+		let code = b"some code".to_vec();
+		let code_hash = keccak(&code);
+
+		let mut storage_root = H256::default();
+		{
+			let mut db = AccountDBMut::from_hash(source.as_hashdb_mut(), address_hash);
+			{
+				let mut storage = TrieDBMut::new(&mut db, &mut storage_root);
+				for i in 0u8..100 {
+					storage.insert(&keccak(&[i]), &rlp::encode(&U256::from(i as u64))).unwrap();
+				}
+			}
+			db.emplace(code_hash, DBValue::from_slice(&code));
+		}
+
+		let account = BasicAccount {
+			nonce: 1.into(),
+			balance: 2.into(),
+			storage_root: storage_root,
+			code_hash: code_hash,
+		};
+
+		let mut root = H256::default();
+		{
+			let mut trie = TrieDBMut::new(source.as_hashdb_mut(), &mut root);
+			trie.insert(&address_hash, &rlp::encode(&account)).unwrap();
+			for i in 0u8..200 {
+				let other = BasicAccount { nonce: U256::from(i as u64), .. account.clone() };
+				trie.insert(&keccak(&[i]), &rlp::encode(&other)).unwrap();
+			}
+		}
+
+		let target_db = Arc::new(kvdb_memorydb::create(NUM_COLUMNS.unwrap()));
+		let mut target = journaldb::new(target_db, Algorithm::OverlayRecent, COL_STATE);
+		let mut reported = 0;
+		let copied = copy_state(source.as_hashdb(), &mut *target, &root, &mut |n| reported = n).unwrap();
+
+		assert_eq!(copied, reported);
+		assert!(copied > 0);
+		for key in source.keys().keys() {
+			assert!(target.contains(key));
+		}
+
+		// copying again is a no-op.
+		assert_eq!(copy_state(source.as_hashdb(), &mut *target, &root, &mut |_| ()).unwrap(), 0);
+	}
+
+	#[test]
+	fn converts_client_database() {
+		let sender = KeyPair::from_secret_slice(&keccak("sender")).unwrap();
+		let (source_db, client) = archive_client_db(&sender, 5);
+
+		let target_db: Arc<KeyValueDB> = Arc::new(kvdb_memorydb::create(NUM_COLUMNS.unwrap()));
+		let number = convert(source_db, Algorithm::Archive, target_db.clone(), Algorithm::OverlayRecent, &mut |_| ()).unwrap();
+		assert_eq!(number, 5);
+
+		// non-state columns are copied verbatim.
+		let chain = BlockChain::new(Default::default(), &[], target_db.clone());
+		assert_eq!(chain.best_block_hash(), client.chain_info().best_block_hash);
+		assert_eq!(chain.best_block_number(), 5);
+
+		// the best state is readable through the target algorithm.
+		let journal = journaldb::new(target_db.clone(), Algorithm::OverlayRecent, COL_STATE);
+		assert_eq!(journal.latest_era(), Some(5));
+		let state_db = StateDB::new(journal, 0);
+		let state = State::from_existing(state_db, state_root(&client, 5), U256::zero(), Factories::default()).unwrap();
+		assert_eq!(state.nonce(&sender.address()).unwrap(), 5.into());
+
+		// earlier states are left behind.
+		assert!(target_db.get(COL_STATE, &state_root(&client, 4)).unwrap().is_none());
+	}
+
+	#[test]
+	fn shrinks_archive_to_last_states() {
+		let sender = KeyPair::from_secret_slice(&keccak("sender")).unwrap();
+		let (db, client) = archive_client_db(&sender, 6);
+		let roots: Vec<_> = (0..7).map(|n| state_root(&client, n)).collect();
+
+		let scratch: Arc<KeyValueDB> = Arc::new(kvdb_memorydb::create(NUM_COLUMNS.unwrap()));
+		let before = state_keys(&db);
+		let deleted = shrink_archive(db.clone(), scratch.clone(), 2, &mut |_| ()).unwrap();
+
+		assert!(deleted > 0);
+		assert_eq!(state_keys(&db), before - deleted);
+		assert_eq!(state_keys(&db), state_keys(&scratch));
+
+		// only the states of blocks 5 and 6 are retained.
+		for (number, root) in roots.iter().enumerate() {
+			assert_eq!(db.get(COL_STATE, root).unwrap().is_some(), number >= 5, "state of block #{}", number);
+		}
+
+		let state_db = StateDB::new(journaldb::new(db, Algorithm::Archive, COL_STATE), 0);
+		let state = State::from_existing(state_db, roots[5], U256::zero(), Factories::default()).unwrap();
+		assert_eq!(state.nonce(&sender.address()).unwrap(), 5.into());
+	}
+}
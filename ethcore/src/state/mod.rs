@@ -199,6 +199,40 @@ pub fn check_proof(
 	machine: &Machine,
 	env_info: &EnvInfo,
 ) -> ProvedExecution {
+	check_proof_with_options(proof, root, transaction, machine, env_info, TransactOptions::with_no_tracing())
+}
+
+/// Check the given proof of execution, running the transaction with the given
+/// tracing options. Behaves like `check_proof` otherwise.
+pub fn check_proof_with_options<T, V>(
+	proof: &[DBValue],
+	root: H256,
+	transaction: &SignedTransaction,
+	machine: &Machine,
+	env_info: &EnvInfo,
+	options: TransactOptions<T, V>,
+) -> ProvedExecution where
+	T: trace::Tracer<Output=FlatTrace>,
+	V: trace::VMTracer<Output=VMTrace>,
+{
+	check_proof_of_sequence(proof, root, &[], transaction, machine, env_info, options)
+}
+
+/// Check the given proof of execution of a transaction preceded by other transactions
+/// of its block. The preceding transactions are executed first, without tracing, and
+/// the gas they use is accounted in the environment of the proved transaction.
+pub fn check_proof_of_sequence<T, V>(
+	proof: &[DBValue],
+	root: H256,
+	preceding: &[SignedTransaction],
+	transaction: &SignedTransaction,
+	machine: &Machine,
+	env_info: &EnvInfo,
+	options: TransactOptions<T, V>,
+) -> ProvedExecution where
+	T: trace::Tracer<Output=FlatTrace>,
+	V: trace::VMTracer<Output=VMTrace>,
+{
 	let backend = self::backend::ProofCheck::new(proof);
 	let mut factories = Factories::default();
 	factories.accountdb = ::account_db::Factory::Plain;
@@ -215,8 +249,17 @@ pub fn check_proof(
 		Err(_) => return ProvedExecution::BadProof,
 	};
 
-	let options = TransactOptions::with_no_tracing().save_output_from_contract();
-	match state.execute(env_info, machine, transaction, options, true) {
+	let mut env_info = env_info.clone();
+	for tx in preceding {
+		match state.execute(&env_info, machine, tx, TransactOptions::with_no_tracing(), true) {
+			Ok(executed) => env_info.gas_used = env_info.gas_used + executed.gas_used,
+			Err(ExecutionError::Internal(_)) => return ProvedExecution::BadProof,
+			Err(e) => return ProvedExecution::Failed(e),
+		}
+	}
+
+	let options = options.save_output_from_contract();
+	match state.execute(&env_info, machine, transaction, options, true) {
 		Ok(executed) => ProvedExecution::Complete(executed),
 		Err(ExecutionError::Internal(_)) => ProvedExecution::BadProof,
 		Err(e) => ProvedExecution::Failed(e),
@@ -602,6 +645,64 @@ impl<B: Backend> State<B> {
 		r
 	}
 
+	/// Get the value of storage at `key` of account `address` as it was when the checkpoint
+	/// with index `start_checkpoint_index` was created. Returns `None` if there is no such checkpoint.
+	pub fn checkpoint_storage_at(&self, start_checkpoint_index: usize, address: &Address, key: &H256) -> trie::Result<Option<H256>> {
+		{
+			let checkpoints = self.checkpoints.borrow();
+			if start_checkpoint_index >= checkpoints.len() {
+				return Ok(None);
+			}
+
+			// The first checkpoint (starting from the requested one) which noted the account
+			// holds the account as it was at the requested checkpoint.
+			for checkpoint in checkpoints.iter().skip(start_checkpoint_index) {
+				match checkpoint.get(address) {
+					Some(&Some(AccountEntry { account: Some(ref account), .. })) => {
+						let account_db = self.factories.accountdb.readonly(self.db.as_hashdb(), account.address_hash(address));
+						return account.storage_at(account_db.as_hashdb(), key).map(Some);
+					},
+					// The account did not exist at that point.
+					Some(&Some(AccountEntry { account: None, .. })) => return Ok(Some(H256::new())),
+					// The account was not cached at that point, so it held the value committed to the DB.
+					Some(&None) => return self.original_storage_at(address, key).map(Some),
+					None => {},
+				}
+			}
+		}
+
+		// The account has not been modified since the checkpoint.
+		self.storage_at(address, key).map(Some)
+	}
+
+	/// Get the value of storage at `key` of account `address` as committed to the backing
+	/// database, ignoring any changes in the local cache.
+	fn original_storage_at(&self, address: &Address, key: &H256) -> trie::Result<H256> {
+		let cached = self.db.get_cached(address, |acc| match acc {
+			None => Ok(H256::new()),
+			Some(a) => {
+				let account_db = self.factories.accountdb.readonly(self.db.as_hashdb(), a.address_hash(address));
+				a.storage_at(account_db.as_hashdb(), key)
+			}
+		});
+
+		if let Some(res) = cached {
+			return res;
+		}
+
+		if self.db.is_known_null(address) { return Ok(H256::zero()) }
+
+		let db = self.factories.trie.readonly(self.db.as_hashdb(), &self.root)?;
+		let from_rlp = |b: &[u8]| Account::from_rlp(b).expect("decoding db value failed");
+		match db.get_with(address, from_rlp)? {
+			Some(account) => {
+				let account_db = self.factories.accountdb.readonly(self.db.as_hashdb(), account.address_hash(address));
+				account.storage_at(account_db.as_hashdb(), key)
+			},
+			None => Ok(H256::new()),
+		}
+	}
+
 	/// Get accounts' code.
 	pub fn code(&self, a: &Address) -> trie::Result<Option<Arc<Bytes>>> {
 		self.ensure_cached(a, RequireCache::Code, true,
@@ -2182,6 +2283,56 @@ mod tests {
 		assert_eq!(state.balance(&a).unwrap(), U256::from(0));
 	}
 
+	#[test]
+	fn checkpoint_storage_at() {
+		let mut state = get_temp_state();
+		let a = Address::zero();
+		let k = H256::from(&U256::from(0));
+		let c0 = H256::from(&U256::from(0));
+		let c1 = H256::from(&U256::from(0xffff));
+		let c2 = H256::from(&U256::from(0xcafe));
+
+		assert_eq!(state.checkpoint_storage_at(0, &a, &k).unwrap(), None);
+
+		state.set_storage(&a, k, c1).unwrap();
+		state.commit().unwrap();
+		state.clear();
+
+		state.checkpoint();
+		assert_eq!(state.checkpoint_storage_at(0, &a, &k).unwrap(), Some(c1));
+
+		state.set_storage(&a, k, c2).unwrap();
+		state.checkpoint();
+		state.set_storage(&a, k, c0).unwrap();
+		assert_eq!(state.storage_at(&a, &k).unwrap(), c0);
+		assert_eq!(state.checkpoint_storage_at(0, &a, &k).unwrap(), Some(c1));
+		assert_eq!(state.checkpoint_storage_at(1, &a, &k).unwrap(), Some(c2));
+		assert_eq!(state.checkpoint_storage_at(2, &a, &k).unwrap(), None);
+
+		state.discard_checkpoint();
+		assert_eq!(state.checkpoint_storage_at(0, &a, &k).unwrap(), Some(c1));
+		state.revert_to_checkpoint();
+		assert_eq!(state.storage_at(&a, &k).unwrap(), c1);
+	}
+
+	#[test]
+	fn checkpoint_storage_at_uncached_account() {
+		let mut state = get_temp_state();
+		let a = Address::zero();
+		let k = H256::from(&U256::from(0));
+		let c1 = H256::from(&U256::from(0xffff));
+
+		state.set_storage(&a, k, c1).unwrap();
+		state.commit().unwrap();
+		state.clear();
+
+		// the account is replaced without being read first, so the checkpoint notes it as uncached.
+		state.checkpoint();
+		state.new_contract(&a, U256::zero(), U256::zero());
+		assert_eq!(state.storage_at(&a, &k).unwrap(), H256::new());
+		assert_eq!(state.checkpoint_storage_at(0, &a, &k).unwrap(), Some(c1));
+	}
+
 	#[test]
 	fn create_empty() {
 		let mut state = get_temp_state();
@@ -391,3 +391,150 @@ fn transaction_proof() {
 	assert_eq!(state.balance(&Address::default()).unwrap(), 5.into());
 	assert_eq!(state.balance(&address).unwrap(), 95.into());
 }
+
+#[test]
+fn transaction_proof_with_tracing() {
+	use ::client::ProvingBlockChainClient;
+
+	let client = generate_dummy_client(0);
+	let address = Address::random();
+	let test_spec = Spec::new_test();
+	let mut b = client.prepare_open_block(Address::default(), (3141562.into(), 31415620.into()), vec![]);
+	b.block_mut().state_mut().add_balance(&address, &5.into(), CleanupMode::NoEmpty).unwrap();
+	b.block_mut().state_mut().commit().unwrap();
+	let b = b.close_and_lock().seal(&*test_spec.engine, vec![]).unwrap();
+	client.import_sealed_block(b).unwrap();
+
+	let transaction = Transaction {
+		nonce: 0.into(),
+		gas_price: 0.into(),
+		gas: 21000.into(),
+		action: Action::Call(Address::default()),
+		value: 5.into(),
+		data: Vec::new(),
+	}.fake_sign(address);
+
+	let proof = client.prove_transaction(transaction.clone(), BlockId::Latest).unwrap().1;
+	let root = *client.best_block_header().state_root();
+
+	let proved = state::check_proof_with_options(
+		&proof,
+		root,
+		&transaction,
+		test_spec.engine.machine(),
+		&client.latest_env_info(),
+		TransactOptions::with_tracing_and_vm_tracing(),
+	);
+
+	match proved {
+		state::ProvedExecution::Complete(executed) => {
+			assert_eq!(executed.trace.len(), 1);
+			assert!(executed.vm_trace.is_some());
+		}
+		_ => panic!("execution should be proved"),
+	}
+}
+
+#[test]
+fn simulates_transactions_on_top_of_block() {
+	use client::{Call, Nonce, StateClient};
+	use error::{CallError, ExecutionError};
+
+	let client = generate_dummy_client(0);
+	let key = KeyPair::from_secret(keccak("test").into()).unwrap();
+	let transaction = |nonce: u64| Transaction {
+		nonce: nonce.into(),
+		gas_price: 0.into(),
+		gas: 21000.into(),
+		action: Action::Call(Address::default()),
+		value: 0.into(),
+		data: Vec::new(),
+	}.sign(key.secret(), None);
+	let header = client.best_block_header();
+
+	let mut state = client.state_at(BlockId::Latest).unwrap();
+	let results = client.simulate(&[
+		(transaction(0), Default::default()),
+		(transaction(1), Default::default()),
+	], &mut state, &header).unwrap();
+
+	assert_eq!(results.len(), 2);
+	assert_eq!(results[1].cumulative_gas_used, 42000.into());
+	assert_eq!(client.latest_nonce(&key.address()), 0.into());
+
+	let mut state = client.state_at(BlockId::Latest).unwrap();
+	match client.simulate(&[(transaction(1), Default::default())], &mut state, &header) {
+		Err(CallError::Execution(ExecutionError::InvalidNonce { .. })) => {},
+		other => panic!("expected invalid nonce, got {:?}", other),
+	}
+}
+
+#[test]
+fn simulates_transactions_in_child_of_non_empty_block() {
+	use client::{Call, StateClient};
+	use rustc_hex::FromHex;
+
+	let client = generate_dummy_client_with_data(2, 2, slice_into![10]);
+	let header = client.best_block_header();
+	assert_eq!(header.number(), 2);
+	assert!(!header.gas_used().is_zero());
+
+	// init code returning NUMBER and BLOCKHASH(2).
+	let key = KeyPair::from_secret(keccak("test").into()).unwrap();
+	let transaction = Transaction {
+		nonce: 0.into(),
+		gas_price: 0.into(),
+		gas: 100_000.into(),
+		action: Action::Create,
+		value: 0.into(),
+		data: "4360005260024060205260406000f3".from_hex().unwrap(),
+	}.sign(key.secret(), None);
+
+	let mut state = client.state_at(BlockId::Latest).unwrap();
+	let results = client.simulate(&[(transaction, Default::default())], &mut state, &header).unwrap();
+
+	assert_eq!(results[0].cumulative_gas_used, results[0].gas_used);
+	assert_eq!(U256::from(&results[0].output[..32]), 3.into());
+	assert_eq!(&results[0].output[32..], &*header.hash());
+}
+
+#[test]
+fn transaction_proof_of_sequence() {
+	use ::client::ProvingBlockChainClient;
+
+	let client = generate_dummy_client(0);
+	let address = Address::random();
+	let test_spec = Spec::new_test();
+	let mut b = client.prepare_open_block(Address::default(), (3141562.into(), 31415620.into()), vec![]);
+	b.block_mut().state_mut().add_balance(&address, &10.into(), CleanupMode::NoEmpty).unwrap();
+	b.block_mut().state_mut().commit().unwrap();
+	let b = b.close_and_lock().seal(&*test_spec.engine, vec![]).unwrap();
+	client.import_sealed_block(b).unwrap();
+
+	let transfer = |nonce: u64| Transaction {
+		nonce: nonce.into(),
+		gas_price: 0.into(),
+		gas: 21000.into(),
+		action: Action::Call(Address::default()),
+		value: 5.into(),
+		data: Vec::new(),
+	}.fake_sign(address);
+	let (first, second) = (transfer(0), transfer(1));
+
+	let mut proof = client.prove_transaction(first.clone(), BlockId::Latest).unwrap().1;
+	proof.extend(client.prove_transaction(second.clone(), BlockId::Latest).unwrap().1);
+	let root = *client.best_block_header().state_root();
+	let env_info = client.latest_env_info();
+	let machine = test_spec.engine.machine();
+
+	// the second transaction is only valid after the first.
+	match state::check_proof(&proof, root, &second, machine, &env_info) {
+		state::ProvedExecution::Failed(_) => {},
+		_ => panic!("execution should fail on the nonce"),
+	}
+
+	match state::check_proof_of_sequence(&proof, root, &[first], &second, machine, &env_info, TransactOptions::with_no_tracing()) {
+		state::ProvedExecution::Complete(executed) => assert_eq!(executed.cumulative_gas_used, 42000.into()),
+		_ => panic!("execution should be proved"),
+	}
+}
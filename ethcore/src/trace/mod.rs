@@ -21,12 +21,14 @@ mod db;
 mod executive_tracer;
 mod import;
 mod noop_tracer;
+mod struct_logger;
 mod types;
 
 pub use self::config::Config;
 pub use self::db::TraceDB;
 pub use self::noop_tracer::{NoopTracer, NoopVMTracer};
 pub use self::executive_tracer::{ExecutiveTracer, ExecutiveVMTracer};
+pub use self::struct_logger::{StructLogger, StructLoggerConfig, StructLog};
 pub use self::import::ImportRequest;
 pub use self::localized::LocalizedTrace;
 
@@ -0,0 +1,224 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Struct logger: a VM tracer producing geth-compatible struct logs.
+
+use std::collections::BTreeMap;
+use ethereum_types::U256;
+use evm::INSTRUCTIONS;
+use trace::VMTracer;
+
+const SSTORE: u8 = 0x55;
+
+/// Struct logger options.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct StructLoggerConfig {
+	/// Don't capture the stack.
+	pub disable_stack: bool,
+	/// Don't capture the memory.
+	pub disable_memory: bool,
+	/// Don't capture the storage.
+	pub disable_storage: bool,
+}
+
+/// Snapshot of the machine state taken before executing a single instruction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructLog {
+	/// Program counter.
+	pub pc: usize,
+	/// Instruction mnemonic.
+	pub op: &'static str,
+	/// Gas remaining before the instruction is executed.
+	pub gas: U256,
+	/// Gas cost of the instruction.
+	pub gas_cost: U256,
+	/// Call depth, starting at 1 for the transaction itself.
+	pub depth: usize,
+	/// Stack, bottom first.
+	pub stack: Option<Vec<U256>>,
+	/// Memory.
+	pub memory: Option<Vec<u8>>,
+	/// Storage slots written by the current call frame.
+	pub storage: Option<BTreeMap<U256, U256>>,
+}
+
+/// VM tracer producing a `StructLog` for every executed instruction.
+///
+/// Stack, memory and storage are not exposed by the interpreter, so they are
+/// rebuilt from the diffs reported after each instruction.
+pub struct StructLogger {
+	config: StructLoggerConfig,
+	depth: usize,
+	logs: Vec<StructLog>,
+	stack: Vec<U256>,
+	memory: Vec<u8>,
+	storage: BTreeMap<U256, U256>,
+	current_gas: U256,
+	current_instruction: u8,
+}
+
+impl StructLogger {
+	/// Create a new top-level instance.
+	pub fn new(config: StructLoggerConfig) -> Self {
+		StructLogger::with_depth(config, 0)
+	}
+
+	fn with_depth(config: StructLoggerConfig, depth: usize) -> Self {
+		StructLogger {
+			config: config,
+			depth: depth,
+			logs: Vec::new(),
+			stack: Vec::new(),
+			memory: Vec::new(),
+			storage: BTreeMap::new(),
+			current_gas: U256::zero(),
+			current_instruction: 0,
+		}
+	}
+}
+
+impl VMTracer for StructLogger {
+	type Output = Vec<StructLog>;
+
+	fn trace_next_instruction(&mut self, _pc: usize, _instruction: u8, current_gas: U256) -> bool {
+		self.current_gas = current_gas;
+		true
+	}
+
+	fn trace_prepare_execute(&mut self, pc: usize, instruction: u8, gas_cost: U256) {
+		self.current_instruction = instruction;
+
+		// like geth, show the written value at the `SSTORE` itself.
+		if instruction == SSTORE && self.stack.len() >= 2 {
+			let len = self.stack.len();
+			self.storage.insert(self.stack[len - 1], self.stack[len - 2]);
+		}
+
+		self.logs.push(StructLog {
+			pc: pc,
+			op: INSTRUCTIONS[instruction as usize].name,
+			gas: self.current_gas,
+			gas_cost: gas_cost,
+			depth: self.depth,
+			stack: if self.config.disable_stack { None } else { Some(self.stack.clone()) },
+			memory: if self.config.disable_memory { None } else { Some(self.memory.clone()) },
+			storage: if self.config.disable_storage { None } else { Some(self.storage.clone()) },
+		});
+	}
+
+	fn trace_executed(&mut self, _gas_used: U256, stack_push: &[U256], mem_diff: Option<(usize, &[u8])>, _store_diff: Option<(U256, U256)>) {
+		let args = INSTRUCTIONS[self.current_instruction as usize].args;
+		let len = self.stack.len();
+		self.stack.truncate(len.saturating_sub(args));
+		self.stack.extend_from_slice(stack_push);
+
+		if let Some((offset, data)) = mem_diff {
+			let end = offset + data.len();
+			if end > self.memory.len() {
+				// memory is always expanded in whole words.
+				self.memory.resize((end + 31) / 32 * 32, 0);
+			}
+			self.memory[offset..end].copy_from_slice(data);
+		}
+	}
+
+	fn prepare_subtrace(&self, _code: &[u8]) -> Self {
+		StructLogger::with_depth(self.config, self.depth + 1)
+	}
+
+	fn done_subtrace(&mut self, sub: Self) {
+		self.logs.extend(sub.logs);
+	}
+
+	fn drain(self) -> Option<Vec<StructLog>> {
+		Some(self.logs)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn should_rebuild_stack_memory_and_storage() {
+		let mut top = StructLogger::new(Default::default());
+		let mut tracer = top.prepare_subtrace(&[]);
+
+		// PUSH1 0x2a
+		tracer.trace_next_instruction(0, 0x60, 100.into());
+		tracer.trace_prepare_execute(0, 0x60, 3.into());
+		tracer.trace_executed(97.into(), &[42.into()], None, None);
+		// PUSH1 0x00
+		tracer.trace_next_instruction(2, 0x60, 97.into());
+		tracer.trace_prepare_execute(2, 0x60, 3.into());
+		tracer.trace_executed(94.into(), &[0.into()], None, None);
+		// MSTORE
+		let mut word = [0u8; 32];
+		word[31] = 42;
+		tracer.trace_next_instruction(4, 0x52, 94.into());
+		tracer.trace_prepare_execute(4, 0x52, 6.into());
+		tracer.trace_executed(88.into(), &[], Some((0, &word)), None);
+		// PUSH1 0x2a, PUSH1 0x01, SSTORE
+		tracer.trace_next_instruction(5, 0x60, 88.into());
+		tracer.trace_prepare_execute(5, 0x60, 3.into());
+		tracer.trace_executed(85.into(), &[42.into()], None, None);
+		tracer.trace_next_instruction(7, 0x60, 85.into());
+		tracer.trace_prepare_execute(7, 0x60, 3.into());
+		tracer.trace_executed(82.into(), &[1.into()], None, None);
+		tracer.trace_next_instruction(9, 0x55, 82.into());
+		tracer.trace_prepare_execute(9, 0x55, 20000.into());
+
+		top.done_subtrace(tracer);
+		let logs = top.drain().unwrap();
+
+		assert_eq!(logs.len(), 6);
+		assert_eq!(logs[0].op, "PUSH1");
+		assert_eq!(logs[0].depth, 1);
+		assert_eq!(logs[0].gas, 100.into());
+		assert_eq!(logs[0].stack, Some(vec![]));
+		assert_eq!(logs[2].op, "MSTORE");
+		assert_eq!(logs[2].stack, Some(vec![42.into(), 0.into()]));
+		assert_eq!(logs[2].memory, Some(vec![]));
+		assert_eq!(logs[3].memory, Some(word.to_vec()));
+		assert_eq!(logs[3].stack, Some(vec![]));
+
+		let mut storage = BTreeMap::new();
+		storage.insert(1.into(), 42.into());
+		assert_eq!(logs[5].op, "SSTORE");
+		assert_eq!(logs[5].gas_cost, 20000.into());
+		assert_eq!(logs[5].storage, Some(storage));
+		assert_eq!(logs[4].storage, Some(BTreeMap::new()));
+	}
+
+	#[test]
+	fn should_respect_disabled_captures() {
+		let config = StructLoggerConfig {
+			disable_stack: true,
+			disable_memory: true,
+			disable_storage: true,
+		};
+		let mut tracer = StructLogger::new(config).prepare_subtrace(&[]);
+
+		tracer.trace_next_instruction(0, 0x60, 100.into());
+		tracer.trace_prepare_execute(0, 0x60, 3.into());
+		tracer.trace_executed(97.into(), &[42.into()], None, None);
+
+		let logs = tracer.drain().unwrap();
+		assert_eq!(logs[0].stack, None);
+		assert_eq!(logs[0].memory, None);
+		assert_eq!(logs[0].storage, None);
+	}
+}
@@ -30,10 +30,13 @@ extern crate parking_lot;
 #[cfg(test)] extern crate ethcore_logger;
 
 mod traits;
+mod vardiff;
 
 pub use traits::{
 	JobDispatcher, PushWorkHandler, Error, ServiceConfiguration,
+	Share, ShareOutcome, WorkerStats, PoolOptions,
 };
+pub use vardiff::{Vardiff, VardiffOptions};
 
 use jsonrpc_tcp_server::{
 	Server as JsonRpcServer, ServerBuilder as JsonRpcServerBuilder,
@@ -43,8 +46,10 @@ use jsonrpc_core::{MetaIoHandler, Params, to_value, Value, Metadata, Compatibili
 use jsonrpc_macros::IoDelegate;
 use std::sync::Arc;
 
+use std::cmp;
 use std::net::SocketAddr;
 use std::collections::{HashSet, HashMap};
+use std::time::Instant;
 use hash::keccak;
 use ethereum_types::H256;
 use parking_lot::RwLock;
@@ -75,6 +80,16 @@ impl Stratum {
 		dispatcher: Arc<JobDispatcher>,
 		secret: Option<H256>,
 	) -> Result<Arc<Stratum>, Error> {
+		Self::start_with_options(addr, dispatcher, secret, PoolOptions::default())
+	}
+
+	/// Start the service with extranonce allocation and share difficulty configured by `options`
+	pub fn start_with_options(
+		addr: &SocketAddr,
+		dispatcher: Arc<JobDispatcher>,
+		secret: Option<H256>,
+		options: PoolOptions,
+	) -> Result<Arc<Stratum>, Error> {
 
 		let implementation = Arc::new(StratumImpl {
 			subscribers: RwLock::default(),
@@ -83,6 +98,10 @@ impl Stratum {
 			workers: Arc::new(RwLock::default()),
 			secret,
 			notify_counter: RwLock::new(NOTIFY_COUNTER_INITIAL),
+			options,
+			sessions: RwLock::default(),
+			next_extranonce: RwLock::new(0),
+			stats: RwLock::default(),
 		});
 
 		let mut delegate = IoDelegate::<StratumImpl, SocketMetadata>::new(implementation.clone());
@@ -105,6 +124,11 @@ impl Stratum {
 
 		Ok(stratum)
 	}
+
+	/// Share accounting statistics of all workers which submitted shares, by worker id
+	pub fn worker_stats(&self) -> HashMap<String, WorkerStats> {
+		self.implementation.stats.read().clone()
+	}
 }
 
 impl PushWorkHandler for Stratum {
@@ -137,6 +161,21 @@ struct StratumImpl {
 	secret: Option<H256>,
 	/// Dispatch notify couinter
 	notify_counter: RwLock<u32>,
+	/// Pool options
+	options: PoolOptions,
+	/// Connection-specific extranonce and share difficulty
+	sessions: RwLock<HashMap<SocketAddr, Session>>,
+	/// Next extranonce to allocate
+	next_extranonce: RwLock<u64>,
+	/// Share accounting (worker_id - stats)
+	stats: RwLock<HashMap<String, WorkerStats>>,
+}
+
+/// Extranonce and share difficulty of a single connection
+#[derive(Debug, Clone)]
+struct Session {
+	extranonce: String,
+	vardiff: Option<Vardiff>,
 }
 
 impl StratumImpl {
@@ -146,9 +185,10 @@ impl StratumImpl {
 
 		self.subscribers.write().push(meta.addr().clone());
 		self.job_que.write().insert(meta.addr().clone());
+		let session = self.open_session(meta.addr());
 		trace!(target: "stratum", "Subscription request from {:?}", meta.addr());
 
-		Ok(match self.dispatcher.initial() {
+		let initial = match self.dispatcher.initial() {
 			Some(initial) => match jsonrpc_core::Value::from_str(&initial) {
 				Ok(val) => Ok(val),
 				Err(e) => {
@@ -157,7 +197,12 @@ impl StratumImpl {
 				},
 			},
 			None => to_value(&[0u8; 0]),
-		}.expect("Empty slices are serializable; qed"))
+		}.expect("Empty slices are serializable; qed");
+
+		Ok(match self.options.extranonce_size {
+			0 => initial,
+			_ => Value::Array(vec![initial, Value::String(session.extranonce)]),
+		})
 	}
 
 	/// rpc method `mining.authorize`
@@ -170,7 +215,15 @@ impl StratumImpl {
 				}
 			}
 			trace!(target: "stratum", "New worker #{} registered", worker_id);
-			self.workers.write().insert(meta.addr().clone(), worker_id);
+			self.workers.write().insert(meta.addr().clone(), worker_id.clone());
+
+			let session = self.open_session(meta.addr());
+			if let Some(difficulty) = session.vardiff.map(|vardiff| vardiff.difficulty()) {
+				self.stats.write().entry(worker_id).or_insert_with(WorkerStats::default).difficulty = Some(difficulty);
+				if let Some(ref tcp_dispatcher) = meta.tcp_dispatcher {
+					self.push_difficulty(meta.addr(), difficulty, tcp_dispatcher);
+				}
+			}
 			to_value(true)
 		}).map(|v| v.expect("Only true/false is returned and it's always serializable; qed"))
 	}
@@ -180,21 +233,28 @@ impl StratumImpl {
 		Ok(match params {
 			Params::Array(vals) => {
 				// first two elements are service messages (worker_id & job_id)
-				match self.dispatcher.submit(vals.iter().skip(2)
+				let payload = vals.iter().skip(2)
 					.filter_map(|val| match *val {
 						Value::String(ref s) => Some(s.to_owned()),
 						_ => None
 					})
-					.collect::<Vec<String>>()) {
-						Ok(()) => {
-							self.update_peers(&meta.tcp_dispatcher.expect("tcp_dispatcher is always initialized; qed"));
-							to_value(true)
-						},
-						Err(submit_err) => {
-							warn!("Error while submitting share: {:?}", submit_err);
-							to_value(false)
-						}
+					.collect::<Vec<String>>();
+				let tcp_dispatcher = meta.tcp_dispatcher.expect("tcp_dispatcher is always initialized; qed");
+				let share = self.share(&meta.addr);
+				let result = self.dispatcher.submit_share(payload, &share);
+				self.account_share(&meta.addr, &share, &result, &tcp_dispatcher);
+
+				match result {
+					Ok(ShareOutcome::Block) => {
+						self.update_peers(&tcp_dispatcher);
+						to_value(true)
+					},
+					Ok(ShareOutcome::Share) => to_value(true),
+					Err(submit_err) => {
+						warn!("Error while submitting share: {:?}", submit_err);
+						to_value(false)
 					}
+				}
 			},
 			_ => {
 				trace!(target: "stratum", "Invalid submit work format {:?}", params);
@@ -203,6 +263,105 @@ impl StratumImpl {
 		}.expect("Only true/false is returned and it's always serializable; qed"))
 	}
 
+	/// Session of the connection, opened with a new extranonce and the initial share difficulty if needed
+	fn open_session(&self, addr: &SocketAddr) -> Session {
+		let mut sessions = self.sessions.write();
+		if let Some(session) = sessions.get(addr) {
+			return session.clone();
+		}
+
+		let extranonce = match self.options.extranonce_size {
+			0 => String::new(),
+			_ => self.allocate_extranonce(&sessions),
+		};
+		let session = Session {
+			extranonce,
+			vardiff: self.options.vardiff.clone().map(|options| Vardiff::new(options, Instant::now())),
+		};
+		sessions.insert(*addr, session.clone());
+		session
+	}
+
+	/// Next extranonce not used by any of the open sessions
+	fn allocate_extranonce(&self, sessions: &HashMap<SocketAddr, Session>) -> String {
+		let size = cmp::min(self.options.extranonce_size, 8);
+		let mask = if size == 8 { ::std::u64::MAX } else { (1u64 << (8 * size)) - 1 };
+		let in_use = sessions.values().map(|session| session.extranonce.as_str()).collect::<HashSet<_>>();
+
+		let mut next = self.next_extranonce.write();
+		let mut candidate = String::new();
+		// there is a free extranonce among the next `sessions.len() + 1` unless the space is exhausted
+		for _ in 0..sessions.len() + 1 {
+			candidate = format!("{:01$x}", *next, size * 2);
+			*next = next.wrapping_add(1) & mask;
+			if !in_use.contains(candidate.as_str()) {
+				return candidate;
+			}
+		}
+
+		warn!(target: "stratum", "Extranonce space exhausted, reusing {}", candidate);
+		candidate
+	}
+
+	/// Context of a share submitted by the connection
+	fn share(&self, addr: &SocketAddr) -> Share {
+		let session = self.sessions.read().get(addr).cloned();
+		Share {
+			worker_id: self.workers.read().get(addr).cloned().unwrap_or_default(),
+			extranonce: session.as_ref().map(|session| session.extranonce.clone()).unwrap_or_default(),
+			difficulty: session.and_then(|session| session.vardiff).map(|vardiff| vardiff.difficulty()),
+		}
+	}
+
+	/// Update the share accounting and retarget the share difficulty of the connection
+	fn account_share(&self, addr: &SocketAddr, share: &Share, result: &Result<ShareOutcome, Error>, tcp_dispatcher: &Dispatcher) {
+		let now = Instant::now();
+		let retargeted = match *result {
+			Ok(_) => self.sessions.write().get_mut(addr)
+				.and_then(|session| session.vardiff.as_mut())
+				.and_then(|vardiff| vardiff.record_share(now)),
+			Err(_) => None,
+		};
+
+		let stats = {
+			let mut stats = self.stats.write();
+			let stats = stats.entry(share.worker_id.clone()).or_insert_with(WorkerStats::default);
+			match *result {
+				Ok(outcome) => {
+					stats.accepted += 1;
+					stats.accepted_difficulty = stats.accepted_difficulty.saturating_add(share.difficulty.unwrap_or(0));
+					if outcome == ShareOutcome::Block {
+						stats.blocks += 1;
+					}
+				},
+				Err(_) => stats.rejected += 1,
+			}
+			stats.difficulty = retargeted.or(share.difficulty);
+			stats.last_share = Some(now);
+			stats.clone()
+		};
+
+		if let Some(difficulty) = retargeted {
+			self.push_difficulty(addr, difficulty, tcp_dispatcher);
+		}
+		self.dispatcher.share_stats(&share.worker_id, &stats);
+	}
+
+	fn next_request_id(&self) -> u32 {
+		let mut counter = self.notify_counter.write();
+		if *counter == ::std::u32::MAX { *counter = NOTIFY_COUNTER_INITIAL; }
+		else { *counter = *counter + 1 }
+		*counter
+	}
+
+	fn push_difficulty(&self, addr: &SocketAddr, difficulty: u64, tcp_dispatcher: &Dispatcher) {
+		let msg = format!("{{ \"id\": {}, \"method\": \"mining.set_difficulty\", \"params\": [{}] }}", self.next_request_id(), difficulty);
+		trace!(target: "stratum", "pushing difficulty {} to {}", difficulty, addr);
+		if let Err(e) = tcp_dispatcher.push_message(addr, msg) {
+			warn!(target: "stratum", "Failed to push difficulty to {}: {:?}", addr, e);
+		}
+	}
+
 	/// Helper method
 	fn update_peers(&self, tcp_dispatcher: &Dispatcher) {
 		if let Some(job) = self.dispatcher.job() {
@@ -215,12 +374,7 @@ impl StratumImpl {
 	fn push_work_all(&self, payload: String, tcp_dispatcher: &Dispatcher) -> Result<(), Error> {
 		let hup_peers = {
 			let workers = self.workers.read();
-			let next_request_id = {
-				let mut counter = self.notify_counter.write();
-				if *counter == ::std::u32::MAX { *counter = NOTIFY_COUNTER_INITIAL; }
-				else { *counter = *counter + 1 }
-				*counter
-			};
+			let next_request_id = self.next_request_id();
 
 			let mut hup_peers = HashSet::with_capacity(0); // most of the cases won't be needed, hence avoid allocation
 			let workers_msg = format!("{{ \"id\": {}, \"method\": \"mining.notify\", \"params\": {} }}", next_request_id, payload);
@@ -243,7 +397,11 @@ impl StratumImpl {
 
 		if !hup_peers.is_empty() {
 			let mut workers = self.workers.write();
-			for hup_peer in hup_peers { workers.remove(&hup_peer); }
+			let mut sessions = self.sessions.write();
+			for hup_peer in hup_peers {
+				workers.remove(&hup_peer);
+				sessions.remove(&hup_peer);
+			}
 		}
 
 		Ok(())
@@ -500,4 +658,58 @@ mod tests {
 			"{ \"id\": 17, \"method\": \"mining.notify\", \"params\": { \"00040008\", \"100500\" } }\n",
 			response);
 	}
+
+	struct PoolManager;
+
+	impl JobDispatcher for PoolManager {
+		fn initial(&self) -> Option<String> {
+			Some(r#"[ "dummy payload" ]"#.to_owned())
+		}
+
+		fn submit(&self, _payload: Vec<String>) -> Result<(), Error> {
+			Ok(())
+		}
+
+		fn submit_share(&self, payload: Vec<String>, _share: &Share) -> Result<ShareOutcome, Error> {
+			match payload.get(0).map(|s| s.as_str()) {
+				Some("0x01") => Ok(ShareOutcome::Share),
+				_ => Err(Error::Dispatch("Share below worker difficulty".into())),
+			}
+		}
+	}
+
+	#[test]
+	fn allocates_unique_extranonce() {
+		let addr = "127.0.0.1:19960".parse().unwrap();
+		let options = PoolOptions { extranonce_size: 2, vardiff: None };
+		let _stratum = Stratum::start_with_options(&addr, Arc::new(PoolManager), None, options)
+			.expect("There should be no error starting stratum");
+		let request = r#"{"jsonrpc": "2.0", "method": "mining.subscribe", "params": [], "id": 1}"#;
+
+		let response1 = String::from_utf8(dummy_request(&addr, request)).unwrap();
+		let response2 = String::from_utf8(dummy_request(&addr, request)).unwrap();
+
+		assert_eq!(terminated_str(r#"{"jsonrpc":"2.0","result":[["dummy payload"],"0000"],"id":1}"#), response1);
+		assert_eq!(terminated_str(r#"{"jsonrpc":"2.0","result":[["dummy payload"],"0001"],"id":1}"#), response2);
+	}
+
+	#[test]
+	fn accounts_worker_shares() {
+		let addr = "127.0.0.1:19955".parse().unwrap();
+		let stratum = Stratum::start_with_options(&addr, Arc::new(PoolManager), None, PoolOptions::default())
+			.expect("There should be no error starting stratum");
+		let request = r#"[
+			{"jsonrpc": "2.0", "method": "mining.authorize", "params": ["miner1", ""], "id": 1},
+			{"jsonrpc": "2.0", "method": "mining.submit", "params": ["miner1", "job", "0x01"], "id": 2},
+			{"jsonrpc": "2.0", "method": "mining.submit", "params": ["miner1", "job", "0x02"], "id": 3}
+		]"#.replace("\n", "").replace("\t", "");
+
+		let response = String::from_utf8(dummy_request(&addr, &request)).unwrap();
+
+		assert_eq!(terminated_str(r#"[{"jsonrpc":"2.0","result":true,"id":1},{"jsonrpc":"2.0","result":true,"id":2},{"jsonrpc":"2.0","result":false,"id":3}]"#), response);
+		let stats = stratum.worker_stats();
+		assert_eq!(stats["miner1"].accepted, 1);
+		assert_eq!(stats["miner1"].rejected, 1);
+		assert_eq!(stats["miner1"].blocks, 0);
+	}
 }
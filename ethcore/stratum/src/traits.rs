@@ -16,8 +16,10 @@
 
 use std;
 use std::error::Error as StdError;
+use std::time::Instant;
 use ethereum_types::H256;
 use jsonrpc_tcp_server::PushMessageError;
+use vardiff::VardiffOptions;
 
 #[derive(Debug, Clone)]
 pub enum Error {
@@ -50,6 +52,50 @@ pub trait JobDispatcher: Send + Sync {
 	fn job(&self) -> Option<String> { None }
 	// miner job result
 	fn submit(&self, payload: Vec<String>) -> Result<(), Error>;
+	// miner share result, checked against the share difficulty of the worker (if any)
+	// by default every share is treated as a block solution
+	fn submit_share(&self, payload: Vec<String>, _share: &Share) -> Result<ShareOutcome, Error> {
+		self.submit(payload).map(|_| ShareOutcome::Block)
+	}
+	// share accounting of a worker, updated after each of its submissions
+	fn share_stats(&self, _worker_id: &str, _stats: &WorkerStats) { }
+}
+
+/// Worker-specific context of a submitted share
+#[derive(Debug, Clone, PartialEq)]
+pub struct Share {
+	/// Id of the authorized worker
+	pub worker_id: String,
+	/// Hex-encoded extranonce allocated to the connection, empty if allocation is disabled
+	pub extranonce: String,
+	/// Share difficulty assigned to the worker, `None` if vardiff is disabled
+	pub difficulty: Option<u64>,
+}
+
+/// Outcome of a valid share
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShareOutcome {
+	/// Share meets the worker difficulty only
+	Share,
+	/// Share is a valid block solution
+	Block,
+}
+
+/// Share accounting statistics of a single worker
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WorkerStats {
+	/// Number of accepted shares
+	pub accepted: u64,
+	/// Number of rejected shares
+	pub rejected: u64,
+	/// Number of shares which were valid block solutions
+	pub blocks: u64,
+	/// Sum of share difficulties of accepted shares (with vardiff enabled)
+	pub accepted_difficulty: u64,
+	/// Current share difficulty
+	pub difficulty: Option<u64>,
+	/// Time of the last submission
+	pub last_share: Option<Instant>,
 }
 
 /// Interface that can handle requests to push job for workers
@@ -67,3 +113,13 @@ pub struct ServiceConfiguration {
 	pub port: u16,
 	pub secret: Option<H256>,
 }
+
+/// Options for running the stratum service as a mining pool frontend
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PoolOptions {
+	/// Size in bytes of the extranonce allocated to each connection (at most 8, 0 disables allocation).
+	/// When enabled, `mining.subscribe` returns the initial payload and the hex-encoded extranonce.
+	pub extranonce_size: usize,
+	/// Variable share difficulty, announced to workers with `mining.set_difficulty`
+	pub vardiff: Option<VardiffOptions>,
+}
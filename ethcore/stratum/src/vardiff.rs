@@ -0,0 +1,167 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Variable share difficulty, retargeted to keep a constant share rate per worker.
+
+use std::cmp;
+use std::time::{Duration, Instant};
+use ethereum_types::U256;
+
+/// Maximal factor by which the difficulty may change in a single retarget.
+const MAX_RETARGET_FACTOR: u64 = 4;
+
+/// Variable share difficulty options.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VardiffOptions {
+	/// Difficulty assigned to new workers.
+	pub initial_difficulty: u64,
+	/// Lowest difficulty a worker may be assigned.
+	pub min_difficulty: u64,
+	/// Highest difficulty a worker may be assigned.
+	pub max_difficulty: u64,
+	/// Desired time between two shares of a single worker.
+	pub target_share_time: Duration,
+	/// Number of shares after which the difficulty is retargeted.
+	pub retarget_shares: u32,
+}
+
+impl Default for VardiffOptions {
+	fn default() -> Self {
+		VardiffOptions {
+			initial_difficulty: 1_000_000_000,
+			min_difficulty: 1_000_000,
+			max_difficulty: ::std::u64::MAX,
+			target_share_time: Duration::from_secs(10),
+			retarget_shares: 8,
+		}
+	}
+}
+
+/// Share difficulty of a single worker.
+#[derive(Debug, Clone)]
+pub struct Vardiff {
+	options: VardiffOptions,
+	difficulty: u64,
+	shares: u32,
+	since: Instant,
+}
+
+impl Vardiff {
+	/// New worker difficulty, starting at the initial difficulty.
+	pub fn new(options: VardiffOptions, now: Instant) -> Self {
+		let difficulty = cmp::min(cmp::max(options.initial_difficulty, options.min_difficulty), options.max_difficulty);
+		Vardiff {
+			options,
+			difficulty,
+			shares: 0,
+			since: now,
+		}
+	}
+
+	/// Current share difficulty.
+	pub fn difficulty(&self) -> u64 {
+		self.difficulty
+	}
+
+	/// Record an accepted share. Returns the new difficulty if it was retargeted.
+	///
+	/// The difficulty is retargeted after `retarget_shares` shares, or on the first share
+	/// after the time those shares should have taken, so that slow workers are not stuck.
+	pub fn record_share(&mut self, now: Instant) -> Option<u64> {
+		self.shares += 1;
+		let elapsed = now.duration_since(self.since);
+		let window = self.options.target_share_time * self.options.retarget_shares;
+		if self.shares < self.options.retarget_shares && elapsed < window {
+			return None;
+		}
+
+		let expected = millis(self.options.target_share_time).saturating_mul(self.shares as u64);
+		let elapsed = cmp::max(millis(elapsed), 1);
+		let retargeted = U256::from(self.difficulty) * U256::from(expected) / U256::from(elapsed);
+		let retargeted = if retargeted > U256::from(::std::u64::MAX) { ::std::u64::MAX } else { retargeted.low_u64() };
+
+		let lowest = cmp::max(self.difficulty / MAX_RETARGET_FACTOR, self.options.min_difficulty);
+		let highest = cmp::min(self.difficulty.saturating_mul(MAX_RETARGET_FACTOR), self.options.max_difficulty);
+		let retargeted = cmp::min(cmp::max(retargeted, lowest), highest);
+
+		self.shares = 0;
+		self.since = now;
+
+		if retargeted == self.difficulty {
+			return None;
+		}
+
+		trace!(target: "stratum", "Retargeting share difficulty from {} to {}", self.difficulty, retargeted);
+		self.difficulty = retargeted;
+		Some(retargeted)
+	}
+}
+
+fn millis(duration: Duration) -> u64 {
+	duration.as_secs().saturating_mul(1000) + (duration.subsec_nanos() / 1_000_000) as u64
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn options() -> VardiffOptions {
+		VardiffOptions {
+			initial_difficulty: 1000,
+			min_difficulty: 100,
+			max_difficulty: 3000,
+			target_share_time: Duration::from_secs(10),
+			retarget_shares: 4,
+		}
+	}
+
+	#[test]
+	fn should_increase_difficulty_of_fast_worker() {
+		let now = Instant::now();
+		let mut vardiff = Vardiff::new(options(), now);
+
+		assert_eq!(vardiff.record_share(now + Duration::from_secs(5)), None);
+		assert_eq!(vardiff.record_share(now + Duration::from_secs(10)), None);
+		assert_eq!(vardiff.record_share(now + Duration::from_secs(15)), None);
+		assert_eq!(vardiff.record_share(now + Duration::from_secs(20)), Some(2000));
+		assert_eq!(vardiff.difficulty(), 2000);
+	}
+
+	#[test]
+	fn should_decrease_difficulty_of_slow_worker() {
+		let now = Instant::now();
+		let mut vardiff = Vardiff::new(options(), now);
+
+		// a single share after the whole retarget window
+		assert_eq!(vardiff.record_share(now + Duration::from_secs(50)), Some(250));
+	}
+
+	#[test]
+	fn should_respect_difficulty_bounds() {
+		let now = Instant::now();
+		let mut vardiff = Vardiff::new(options(), now);
+
+		for i in 1..4 {
+			assert_eq!(vardiff.record_share(now + Duration::from_millis(i)), None);
+		}
+		assert_eq!(vardiff.record_share(now + Duration::from_millis(4)), Some(3000));
+
+		let mut vardiff = Vardiff::new(options(), now);
+		assert_eq!(vardiff.record_share(now + Duration::from_secs(1000)), Some(250));
+		assert_eq!(vardiff.record_share(now + Duration::from_secs(2000)), Some(100));
+		assert_eq!(vardiff.record_share(now + Duration::from_secs(3000)), None);
+	}
+}
@@ -33,6 +33,13 @@ pub enum Error {
 	TooCheapToReplace,
 	/// Transaction was not imported to the queue because limit has been reached.
 	LimitReached,
+	/// Transaction nonce is too far ahead of the sender's state nonce.
+	NonceGapTooLarge {
+		/// Maximal allowed nonce gap
+		limit: U256,
+		/// Transaction nonce gap
+		got: U256,
+	},
 	/// Transaction's gas price is below threshold.
 	InsufficientGasPrice {
 		/// Minimal expected gas price
@@ -101,6 +108,8 @@ impl fmt::Display for Error {
 			Old => "No longer valid".into(),
 			TooCheapToReplace => "Gas price too low to replace".into(),
 			LimitReached => "Transaction limit reached".into(),
+			NonceGapTooLarge { limit, got } =>
+				format!("Nonce gap too large. Max={}, Given={}", limit, got),
 			InsufficientGasPrice { minimal, got } =>
 				format!("Insufficient gas price. Min={}, Given={}", minimal, got),
 			InsufficientGas { minimal, got } =>
@@ -0,0 +1,43 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Validator misbehaviour reports sent by this node.
+
+use bytes::Bytes;
+use ethereum_types::{H256, Address};
+use BlockNumber;
+
+/// Kind of a validator misbehaviour report.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReportKind {
+	/// Benign misbehaviour, e.g. a skipped step or an invalid block.
+	Benign,
+	/// Malicious misbehaviour, with the proof submitted along with it.
+	Malicious(Bytes),
+}
+
+/// A validator misbehaviour report sent by this node.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidatorReport {
+	/// Reported validator.
+	pub validator: Address,
+	/// Block at which the misbehaviour was detected.
+	pub block: BlockNumber,
+	/// Kind of misbehaviour.
+	pub kind: ReportKind,
+	/// Hash of the reporting transaction, `None` if it couldn't be submitted.
+	pub transaction: Option<H256>,
+}
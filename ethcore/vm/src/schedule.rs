@@ -48,6 +48,8 @@ pub struct Schedule {
 	pub sstore_reset_gas: usize,
 	/// Gas refund for `SSTORE` clearing (when `storage!=0`, `new==0`)
 	pub sstore_refund_gas: usize,
+	/// Gas price for `SSTORE` on a slot already modified in the current transaction (EIP-1283)
+	pub sstore_dirty_gas: usize,
 	/// Gas price for `JUMPDEST` opcode
 	pub jumpdest_gas: usize,
 	/// Gas price for `LOG*`
@@ -90,6 +92,8 @@ pub struct Schedule {
 	pub extcodesize_gas: usize,
 	/// Base price of EXTCODECOPY
 	pub extcodecopy_base_gas: usize,
+	/// Price of EXTCODEHASH
+	pub extcodehash_gas: usize,
 	/// Price of BALANCE
 	pub balance_gas: usize,
 	/// Price of SUICIDE
@@ -111,10 +115,14 @@ pub struct Schedule {
 	pub have_return_data: bool,
 	/// SHL, SHR, SAR opcodes enabled.
 	pub have_bitwise_shifting: bool,
+	/// EXTCODEHASH opcode enabled.
+	pub have_extcodehash: bool,
 	/// Kill basic accounts below this balance if touched.
 	pub kill_dust: CleanDustMode,
 	/// Enable EIP-86 rules
 	pub eip86: bool,
+	/// Enable EIP-1283 net gas metering for `SSTORE`
+	pub eip1283: bool,
 	/// Wasm extra schedule settings, if wasm activated
 	pub wasm: Option<WasmCosts>,
 }
@@ -197,6 +205,7 @@ impl Schedule {
 			have_revert: false,
 			have_return_data: false,
 			have_bitwise_shifting: false,
+			have_extcodehash: false,
 			stack_limit: 1024,
 			max_depth: 1024,
 			tier_step_gas: [0, 2, 3, 5, 8, 10, 20, 0],
@@ -208,6 +217,7 @@ impl Schedule {
 			sstore_set_gas: 20000,
 			sstore_reset_gas: 5000,
 			sstore_refund_gas: 15000,
+			sstore_dirty_gas: 200,
 			jumpdest_gas: 1,
 			log_gas: 375,
 			log_data_gas: 8,
@@ -229,6 +239,7 @@ impl Schedule {
 			copy_gas: 3,
 			extcodesize_gas: 700,
 			extcodecopy_base_gas: 700,
+			extcodehash_gas: 400,
 			balance_gas: 400,
 			suicide_gas: 5000,
 			suicide_to_new_account_cost: 25000,
@@ -239,6 +250,7 @@ impl Schedule {
 			have_static_call: false,
 			kill_dust: CleanDustMode::Off,
 			eip86: false,
+			eip1283: false,
 			wasm: None,
 		}
 	}
@@ -257,6 +269,8 @@ impl Schedule {
 	pub fn new_constantinople() -> Schedule {
 		let mut schedule = Self::new_byzantium();
 		schedule.have_bitwise_shifting = true;
+		schedule.have_extcodehash = true;
+		schedule.eip1283 = true;
 		schedule
 	}
 
@@ -268,6 +282,7 @@ impl Schedule {
 			have_revert: false,
 			have_return_data: false,
 			have_bitwise_shifting: false,
+			have_extcodehash: false,
 			stack_limit: 1024,
 			max_depth: 1024,
 			tier_step_gas: [0, 2, 3, 5, 8, 10, 20, 0],
@@ -279,6 +294,7 @@ impl Schedule {
 			sstore_set_gas: 20000,
 			sstore_reset_gas: 5000,
 			sstore_refund_gas: 15000,
+			sstore_dirty_gas: 200,
 			jumpdest_gas: 1,
 			log_gas: 375,
 			log_data_gas: 8,
@@ -300,6 +316,7 @@ impl Schedule {
 			copy_gas: 3,
 			extcodesize_gas: 20,
 			extcodecopy_base_gas: 20,
+			extcodehash_gas: 400,
 			balance_gas: 20,
 			suicide_gas: 0,
 			suicide_to_new_account_cost: 0,
@@ -310,6 +327,7 @@ impl Schedule {
 			have_static_call: false,
 			kill_dust: CleanDustMode::Off,
 			eip86: false,
+			eip1283: false,
 			wasm: None,
 		}
 	}
@@ -19,6 +19,7 @@ use std::collections::{HashMap, HashSet};
 
 use ethereum_types::{U256, H256, Address};
 use bytes::Bytes;
+use hash::keccak;
 use {
 	CallType, Schedule, EnvInfo,
 	ReturnData, Ext, ContractCreateResult, MessageCallResult,
@@ -54,7 +55,7 @@ pub struct FakeExt {
 	pub store: HashMap<H256, H256>,
 	pub suicides: HashSet<Address>,
 	pub calls: HashSet<FakeCall>,
-	pub sstore_clears: usize,
+	pub sstore_clears: i128,
 	pub depth: usize,
 	pub blockhashes: HashMap<U256, H256>,
 	pub codes: HashMap<Address, Arc<Bytes>>,
@@ -103,6 +104,10 @@ impl FakeExt {
 }
 
 impl Ext for FakeExt {
+	fn initial_storage_at(&self, _key: &H256) -> Result<H256> {
+		Ok(H256::new())
+	}
+
 	fn storage_at(&self, key: &H256) -> Result<H256> {
 		Ok(self.store.get(key).unwrap_or(&H256::new()).clone())
 	}
@@ -176,6 +181,10 @@ impl Ext for FakeExt {
 		Ok(self.codes.get(address).map_or(0, |c| c.len()))
 	}
 
+	fn extcodehash(&self, address: &Address) -> Result<H256> {
+		Ok(self.codes.get(address).map_or(H256::zero(), |c| keccak(c.as_ref())))
+	}
+
 	fn log(&mut self, topics: Vec<H256>, data: &[u8]) -> Result<()> {
 		self.logs.push(FakeLogEntry {
 			topics: topics,
@@ -209,8 +218,12 @@ impl Ext for FakeExt {
 		self.is_static
 	}
 
-	fn inc_sstore_clears(&mut self) {
-		self.sstore_clears += 1;
+	fn add_sstore_refund(&mut self, value: usize) {
+		self.sstore_clears += value as i128;
+	}
+
+	fn sub_sstore_refund(&mut self, value: usize) {
+		self.sstore_clears -= value as i128;
 	}
 
 	fn trace_next_instruction(&mut self, _pc: usize, _instruction: u8, _gas: U256) -> bool {
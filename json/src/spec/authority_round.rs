@@ -16,16 +16,27 @@
 
 //! Authority params deserialization.
 
+use std::collections::BTreeMap;
 use ethereum_types::Address;
 use uint::Uint;
 use super::ValidatorSet;
 
+/// Step duration deserialization.
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum StepDuration {
+	/// Duration of every step, in seconds.
+	Single(Uint),
+	/// Map of step numbers to the duration of the steps starting at them, in seconds.
+	Transitions(BTreeMap<Uint, Uint>),
+}
+
 /// Authority params deserialization.
 #[derive(Debug, PartialEq, Deserialize)]
 pub struct AuthorityRoundParams {
-	/// Block duration, in seconds.
+	/// Block duration, in seconds, or its transitions by step.
 	#[serde(rename="stepDuration")]
-	pub step_duration: Uint,
+	pub step_duration: StepDuration,
 	/// Valid authorities
 	pub validators: ValidatorSet,
 	/// Starting step. Determined automatically if not specified.
@@ -79,7 +90,7 @@ mod tests {
 	use serde_json;
 	use hash::Address;
 	use spec::validator_set::ValidatorSet;
-	use spec::authority_round::AuthorityRound;
+	use spec::authority_round::{AuthorityRound, StepDuration};
 
 	#[test]
 	fn authority_round_deserialization() {
@@ -98,7 +109,7 @@ mod tests {
 		}"#;
 
 		let deserialized: AuthorityRound = serde_json::from_str(s).unwrap();
-		assert_eq!(deserialized.params.step_duration, Uint(U256::from(0x02)));
+		assert_eq!(deserialized.params.step_duration, StepDuration::Single(Uint(U256::from(0x02))));
 		assert_eq!(deserialized.params.validators, ValidatorSet::List(vec![Address(H160::from("0xc6d9d2cd449a754c494264e1809c50e34d64562b"))]));
 		assert_eq!(deserialized.params.start_step, Some(Uint(U256::from(24))));
 		assert_eq!(deserialized.params.immediate_transitions, None);
@@ -106,4 +117,23 @@ mod tests {
 		assert_eq!(deserialized.params.maximum_uncle_count, Some(Uint(5.into())));
 
 	}
+
+	#[test]
+	fn authority_round_step_duration_transitions_deserialization() {
+		let s = r#"{
+			"params": {
+				"stepDuration": {
+					"0": 5,
+					"0x64": "0x02"
+				},
+				"validators": {
+					"list" : ["0xc6d9d2cd449a754c494264e1809c50e34d64562b"]
+				}
+			}
+		}"#;
+
+		let deserialized: AuthorityRound = serde_json::from_str(s).unwrap();
+		let durations = vec![(Uint(0.into()), Uint(5.into())), (Uint(100.into()), Uint(2.into()))].into_iter().collect();
+		assert_eq!(deserialized.params.step_duration, StepDuration::Transitions(durations));
+	}
 }
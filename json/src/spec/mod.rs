@@ -29,6 +29,7 @@ pub mod validator_set;
 pub mod basic_authority;
 pub mod authority_round;
 pub mod tendermint;
+pub mod clique;
 pub mod null_engine;
 pub mod hardcoded_sync;
 
@@ -43,7 +44,8 @@ pub use self::state::State;
 pub use self::ethash::{Ethash, EthashParams};
 pub use self::validator_set::ValidatorSet;
 pub use self::basic_authority::{BasicAuthority, BasicAuthorityParams};
-pub use self::authority_round::{AuthorityRound, AuthorityRoundParams};
+pub use self::authority_round::{AuthorityRound, AuthorityRoundParams, StepDuration};
 pub use self::tendermint::{Tendermint, TendermintParams};
+pub use self::clique::{Clique, CliqueParams};
 pub use self::null_engine::{NullEngine, NullEngineParams};
 pub use self::hardcoded_sync::HardcodedSync;
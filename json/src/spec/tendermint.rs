@@ -17,6 +17,7 @@
 //! Tendermint params deserialization.
 
 use uint::Uint;
+use hash::Address;
 use super::ValidatorSet;
 
 /// Tendermint params deserialization.
@@ -39,6 +40,13 @@ pub struct TendermintParams {
 	/// Reward per block.
 	#[serde(rename="blockReward")]
 	pub block_reward: Option<Uint>,
+	/// Block at which the block reward contract should start being used.
+	#[serde(rename="blockRewardContractTransition")]
+	pub block_reward_contract_transition: Option<Uint>,
+	/// Block reward contract address (setting the block reward contract
+	/// overrides the static block reward definition).
+	#[serde(rename="blockRewardContractAddress")]
+	pub block_reward_contract_address: Option<Address>,
 }
 
 /// Tendermint engine deserialization.
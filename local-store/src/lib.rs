@@ -14,11 +14,11 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity.  If not, see <http://www.gnu.org/licenses/>.
 
-//! Manages local node data: pending local transactions, sync security level
+//! Manages local node data: pending local transactions, dumped transaction pool, sync security level
 
 use std::sync::Arc;
 use std::fmt;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use transaction::{
 	SignedTransaction, PendingTransaction, UnverifiedTransaction,
@@ -49,6 +49,7 @@ extern crate ethkey;
 extern crate kvdb_memorydb;
 
 const LOCAL_TRANSACTIONS_KEY: &'static [u8] = &*b"LOCAL_TXS";
+const POOL_TRANSACTIONS_KEY: &'static [u8] = &*b"POOL_TXS";
 
 const UPDATE_TIMER: ::io::TimerToken = 0;
 const UPDATE_TIMEOUT: Duration = Duration::from_secs(15 * 60); // once every 15 minutes.
@@ -102,14 +103,18 @@ struct TransactionEntry {
 }
 
 impl TransactionEntry {
-	fn into_pending(self) -> Option<PendingTransaction> {
-		let tx: UnverifiedTransaction = match Rlp::new(&self.rlp_bytes).as_val() {
+	fn to_unverified(&self) -> Option<UnverifiedTransaction> {
+		match Rlp::new(&self.rlp_bytes).as_val() {
 			Err(e) => {
 				warn!(target: "local_store", "Invalid persistent transaction stored: {}", e);
-				return None
+				None
 			}
-			Ok(tx) => tx,
-		};
+			Ok(tx) => Some(tx),
+		}
+	}
+
+	fn into_pending(self) -> Option<PendingTransaction> {
+		let tx = self.to_unverified()?;
 
 		let hash = tx.hash();
 		match SignedTransaction::new(tx) {
@@ -131,10 +136,39 @@ impl From<PendingTransaction> for TransactionEntry {
 	}
 }
 
+// dump of the transaction pool, made on shutdown.
+#[derive(Serialize, Deserialize)]
+struct PoolDump {
+	timestamp: u64,
+	transactions: Vec<TransactionEntry>,
+}
+
 /// Something which can provide information about the local node.
 pub trait NodeInfo: Send + Sync {
 	/// Get all pending transactions of local origin.
 	fn pending_transactions(&self) -> Vec<PendingTransaction>;
+
+	/// Get all transactions in the pool which are not of local origin,
+	/// including future ones, ordered from the most to the least valuable.
+	fn queued_transactions(&self) -> Vec<PendingTransaction>;
+}
+
+/// Limits of the transaction pool dump.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PoolLimits {
+	/// Maximal number of transactions to dump. Zero disables the dump.
+	pub max_count: usize,
+	/// Maximal age of a dump to be restored.
+	pub max_age: Duration,
+}
+
+impl Default for PoolLimits {
+	fn default() -> Self {
+		PoolLimits {
+			max_count: 0,
+			max_age: Duration::from_secs(0),
+		}
+	}
 }
 
 /// Create a new local data store, given a database, a column to write to, and a node.
@@ -144,9 +178,14 @@ pub fn create<T: NodeInfo>(db: Arc<KeyValueDB>, col: Option<u32>, node: T) -> Lo
 		db: db,
 		col: col,
 		node: node,
+		pool_limits: PoolLimits::default(),
 	}
 }
 
+fn unix_now() -> u64 {
+	SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
 /// Manages local node data.
 ///
 /// In specific, this will be used to store things like unpropagated local transactions
@@ -155,9 +194,15 @@ pub struct LocalDataStore<T: NodeInfo> {
 	db: Arc<KeyValueDB>,
 	col: Option<u32>,
 	node: T,
+	pool_limits: PoolLimits,
 }
 
 impl<T: NodeInfo> LocalDataStore<T> {
+	/// Set limits of the transaction pool dump made on shutdown.
+	pub fn set_pool_limits(&mut self, limits: PoolLimits) {
+		self.pool_limits = limits;
+	}
+
 	/// Attempt to read pending transactions out of the local store.
 	pub fn pending_transactions(&self) -> Result<Vec<PendingTransaction>, Error> {
 		if let Some(val) = self.db.get(self.col, LOCAL_TRANSACTIONS_KEY).map_err(Error::Database)? {
@@ -173,6 +218,57 @@ impl<T: NodeInfo> LocalDataStore<T> {
 		}
 	}
 
+	/// Attempt to read the transaction pool dumped on shutdown out of the store.
+	///
+	/// The dump is removed, so it's restored at most once. Dumps older than the
+	/// configured maximal age are discarded.
+	pub fn pool_transactions(&self) -> Result<Vec<UnverifiedTransaction>, Error> {
+		let val = match self.db.get(self.col, POOL_TRANSACTIONS_KEY).map_err(Error::Database)? {
+			Some(val) => val,
+			None => return Ok(Vec::new()),
+		};
+
+		let mut batch = self.db.transaction();
+		batch.delete(self.col, POOL_TRANSACTIONS_KEY);
+		self.db.write(batch).map_err(Error::Database)?;
+
+		let dump: PoolDump = ::serde_json::from_slice(&val).map_err(Error::Json)?;
+		let age = unix_now().saturating_sub(dump.timestamp);
+		if age > self.pool_limits.max_age.as_secs() {
+			debug!(target: "local_store", "Discarding transaction pool dump made {} seconds ago.", age);
+			return Ok(Vec::new());
+		}
+
+		Ok(dump.transactions
+			.iter()
+			.take(self.pool_limits.max_count)
+			.filter_map(TransactionEntry::to_unverified)
+			.collect())
+	}
+
+	/// Dump the transactions in the pool which are not of local origin into the database.
+	pub fn dump_pool(&self) -> Result<(), Error> {
+		if self.pool_limits.max_count == 0 {
+			return Ok(());
+		}
+
+		trace!(target: "local_store", "Dumping transaction pool.");
+
+		let dump = PoolDump {
+			timestamp: unix_now(),
+			transactions: self.node.queued_transactions()
+				.into_iter()
+				.take(self.pool_limits.max_count)
+				.map(Into::into)
+				.collect(),
+		};
+
+		let mut batch = self.db.transaction();
+		let json = ::serde_json::to_vec(&dump).map_err(Error::Json)?;
+		batch.put_vec(self.col, POOL_TRANSACTIONS_KEY, json);
+		self.db.write(batch).map_err(Error::Database)
+	}
+
 	/// Update the entries in the database.
 	pub fn update(&self) -> Result<(), Error> {
 		trace!(target: "local_store", "Updating local store entries.");
@@ -189,6 +285,10 @@ impl<T: NodeInfo> LocalDataStore<T> {
 	pub fn clear(&self) -> Result<(), Error> {
 		trace!(target: "local_store", "Clearing local store entries.");
 
+		let mut batch = self.db.transaction();
+		batch.delete(self.col, POOL_TRANSACTIONS_KEY);
+		self.db.write(batch).map_err(Error::Database)?;
+
 		self.write_txs(&[])
 	}
 
@@ -225,15 +325,21 @@ impl<T: NodeInfo> Drop for LocalDataStore<T> {
 		debug!(target: "local_store", "Updating node data store on shutdown.");
 
 		let _ = self.update();
+
+		if let Err(e) = self.dump_pool() {
+			warn!(target: "local_store", "Error dumping transaction pool: {}", e);
+		}
 	}
 }
 
 #[cfg(test)]
 mod tests {
-	use super::NodeInfo;
+	use super::{NodeInfo, PoolLimits};
 
 	use std::sync::Arc;
-	use transaction::{Transaction, Condition, PendingTransaction};
+	use std::time::Duration;
+	use kvdb::KeyValueDB;
+	use transaction::{Transaction, Condition, PendingTransaction, UnverifiedTransaction};
 	use ethkey::{Brain, Generator};
 
 	// we want to test: round-trip of good transactions.
@@ -242,6 +348,20 @@ mod tests {
 	struct Dummy(Vec<PendingTransaction>);
 	impl NodeInfo for Dummy {
 		fn pending_transactions(&self) -> Vec<PendingTransaction> { self.0.clone() }
+		fn queued_transactions(&self) -> Vec<PendingTransaction> { vec![] }
+	}
+
+	struct Pool(Vec<PendingTransaction>);
+	impl NodeInfo for Pool {
+		fn pending_transactions(&self) -> Vec<PendingTransaction> { vec![] }
+		fn queued_transactions(&self) -> Vec<PendingTransaction> { self.0.clone() }
+	}
+
+	fn limits(max_count: usize, max_age: u64) -> PoolLimits {
+		PoolLimits {
+			max_count,
+			max_age: Duration::from_secs(max_age),
+		}
 	}
 
 	#[test]
@@ -328,4 +448,77 @@ mod tests {
 			assert_eq!(loaded, transactions);
 		}
 	}
+
+	#[test]
+	fn restores_pool_once() {
+		let keypair = Brain::new("abcd".into()).generate().unwrap();
+		let transactions: Vec<_> = (0..10u64).map(|nonce| {
+			let mut tx = Transaction::default();
+			tx.nonce = nonce.into();
+
+			PendingTransaction::new(tx.sign(keypair.secret(), None), None)
+		}).collect();
+		let expected: Vec<UnverifiedTransaction> = transactions.iter()
+			.take(5)
+			.map(|tx| tx.transaction.clone().into())
+			.collect();
+
+		let db = Arc::new(::kvdb_memorydb::create(0));
+		{
+			// nothing written yet, will dump at most 5 transactions of the pool.
+			let mut store = super::create(db.clone(), None, Pool(transactions.clone()));
+			store.set_pool_limits(limits(5, 60));
+			assert_eq!(store.pool_transactions().unwrap(), vec![]);
+		}
+		{
+			// dump written, will write nothing.
+			let mut store = super::create(db.clone(), None, Pool(vec![]));
+			store.set_pool_limits(limits(5, 60));
+			assert_eq!(store.pool_transactions().unwrap(), expected);
+			// the dump is only restored once.
+			assert_eq!(store.pool_transactions().unwrap(), vec![]);
+		}
+	}
+
+	#[test]
+	fn discards_stale_pool() {
+		let keypair = Brain::new("abcd".into()).generate().unwrap();
+		let transactions = vec![PendingTransaction::new(Transaction::default().sign(keypair.secret(), None), None)];
+
+		let db = Arc::new(::kvdb_memorydb::create(0));
+		let write_dump = |timestamp: u64| {
+			let dump = super::PoolDump {
+				timestamp,
+				transactions: transactions.iter().cloned().map(Into::into).collect(),
+			};
+			let mut batch = db.transaction();
+			batch.put_vec(None, super::POOL_TRANSACTIONS_KEY, ::serde_json::to_vec(&dump).unwrap());
+			db.write(batch).unwrap();
+		};
+
+		let mut store = super::create(db.clone(), None, Dummy(vec![]));
+		store.set_pool_limits(limits(5, 60));
+
+		write_dump(super::unix_now() - 120);
+		assert_eq!(store.pool_transactions().unwrap(), vec![]);
+
+		write_dump(super::unix_now() - 30);
+		assert_eq!(store.pool_transactions().unwrap().len(), 1);
+	}
+
+	#[test]
+	fn does_not_dump_pool_by_default() {
+		let keypair = Brain::new("abcd".into()).generate().unwrap();
+		let transactions = vec![PendingTransaction::new(Transaction::default().sign(keypair.secret(), None), None)];
+
+		let db = Arc::new(::kvdb_memorydb::create(0));
+		{
+			let _store = super::create(db.clone(), None, Pool(transactions));
+		}
+		{
+			let mut store = super::create(db.clone(), None, Dummy(vec![]));
+			store.set_pool_limits(limits(5, 60));
+			assert_eq!(store.pool_transactions().unwrap(), vec![]);
+		}
+	}
 }
@@ -16,13 +16,15 @@
 
 //! Auto-updates minimal gas price requirement.
 
+use std::collections::VecDeque;
 use std::time::{Instant, Duration};
 
 use ansi_term::Colour;
-use ethereum_types::U256;
+use ethereum_types::{H256, U256};
 use futures_cpupool::CpuPool;
 use price_info::{Client as PriceInfoClient, PriceInfo};
 use price_info::fetch::Client as FetchClient;
+use stats::Corpus;
 
 /// Options for the dynamic gas price recalibrator.
 #[derive(Debug, PartialEq)]
@@ -63,6 +65,87 @@ impl GasPriceCalibrator {
 	}
 }
 
+/// Options for the gas price oracle learning from recently included transactions.
+#[derive(Debug, PartialEq, Clone)]
+pub struct GasPricePercentileOptions {
+	/// Percentile of gas prices of included transactions to accept.
+	pub percentile: usize,
+	/// Number of recent blocks to sample.
+	pub blocks: usize,
+}
+
+/// Result of the last calibration of a `GasPricePercentileCalibrator` along with its inputs.
+#[derive(Debug, PartialEq, Clone)]
+pub struct GasPricePercentileStatus {
+	/// Percentile of gas prices of included transactions used.
+	pub percentile: usize,
+	/// Number of recent blocks sampled.
+	pub blocks: usize,
+	/// Number of the latest block sampled.
+	pub block_number: u64,
+	/// Number of transactions sampled.
+	pub transactions: usize,
+	/// Resulting minimal gas price.
+	pub gas_price: U256,
+}
+
+/// Gas prices of transactions included in a single block.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BlockGasPrices {
+	/// Block number.
+	pub number: u64,
+	/// Block hash.
+	pub hash: H256,
+	/// Gas prices of all transactions in the block.
+	pub gas_prices: Vec<U256>,
+}
+
+/// The gas price validator variant for a `GasPricer` which learns from recent blocks.
+#[derive(Debug, PartialEq)]
+pub struct GasPricePercentileCalibrator {
+	options: GasPricePercentileOptions,
+	// rolling window of the most recent blocks, oldest first.
+	samples: VecDeque<BlockGasPrices>,
+	status: Option<GasPricePercentileStatus>,
+}
+
+impl GasPricePercentileCalibrator {
+	fn update_samples(&mut self, retracted: &[H256], enacted: Vec<BlockGasPrices>) {
+		self.samples.retain(|block| !retracted.contains(&block.hash));
+		self.samples.extend(enacted);
+		while self.samples.len() > self.options.blocks {
+			self.samples.pop_front();
+		}
+
+		let block_number = match self.samples.back() {
+			Some(block) => block.number,
+			None => self.status.as_ref().map_or(0, |status| status.block_number),
+		};
+		let corpus: Corpus<U256> = self.samples.iter()
+			.flat_map(|block| block.gas_prices.iter().cloned())
+			.collect();
+
+		let gas_price = match corpus.percentile(self.options.percentile) {
+			Some(gas_price) => *gas_price,
+			// keep the previous price if recent blocks were empty.
+			None => self.status.as_ref().map_or_else(U256::zero, |status| status.gas_price),
+		};
+
+		trace!(target: "miner", "Gas price at {} percentile of {} transactions: {}", self.options.percentile, corpus.len(), gas_price);
+		self.status = Some(GasPricePercentileStatus {
+			percentile: self.options.percentile,
+			blocks: self.options.blocks,
+			block_number: block_number,
+			transactions: corpus.len(),
+			gas_price: gas_price,
+		});
+	}
+
+	fn recalibrate<F: FnOnce(U256) + Sync + Send + 'static>(&self, set_price: F) {
+		set_price(self.status.as_ref().map_or_else(U256::zero, |status| status.gas_price))
+	}
+}
+
 /// Struct to look after updating the acceptable gas price of a miner.
 #[derive(Debug, PartialEq)]
 pub enum GasPricer {
@@ -70,6 +153,8 @@ pub enum GasPricer {
 	Fixed(U256),
 	/// Gas price is calibrated according to a fixed amount of USD.
 	Calibrated(GasPriceCalibrator),
+	/// Gas price is a percentile of gas prices of transactions included in recent blocks.
+	Percentile(GasPricePercentileCalibrator),
 }
 
 impl GasPricer {
@@ -82,6 +167,15 @@ impl GasPricer {
 		})
 	}
 
+	/// Create a new `GasPricer` learning from recent blocks.
+	pub fn new_percentile(options: GasPricePercentileOptions) -> GasPricer {
+		GasPricer::Percentile(GasPricePercentileCalibrator {
+			options: options,
+			samples: VecDeque::new(),
+			status: None,
+		})
+	}
+
 	/// Create a new Fixed `GasPricer`.
 	pub fn new_fixed(gas_price: U256) -> GasPricer {
 		GasPricer::Fixed(gas_price)
@@ -92,6 +186,137 @@ impl GasPricer {
 		match *self {
 			GasPricer::Fixed(ref max) => set_price(max.clone()),
 			GasPricer::Calibrated(ref mut cal) => cal.recalibrate(set_price),
+			GasPricer::Percentile(ref cal) => cal.recalibrate(set_price),
 		}
 	}
+
+	/// Number of recent blocks whose transactions should be sampled, if this `GasPricer` learns from them.
+	pub fn sample_blocks(&self) -> Option<usize> {
+		match *self {
+			GasPricer::Percentile(ref cal) => Some(cal.options.blocks),
+			_ => None,
+		}
+	}
+
+	/// Update the window of recently included gas prices: blocks in `retracted` are dropped
+	/// and `enacted` blocks, oldest first, are appended. Only the most recent blocks are kept.
+	pub fn update_samples(&mut self, retracted: &[H256], enacted: Vec<BlockGasPrices>) {
+		if let GasPricer::Percentile(ref mut cal) = *self {
+			cal.update_samples(retracted, enacted);
+		}
+	}
+
+	/// Status of the last calibration, if this `GasPricer` learns from recent blocks.
+	pub fn percentile_status(&self) -> Option<GasPricePercentileStatus> {
+		match *self {
+			GasPricer::Percentile(ref cal) => cal.status.clone(),
+			_ => None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn percentile(percentile: usize, blocks: usize) -> GasPricer {
+		GasPricer::new_percentile(GasPricePercentileOptions {
+			percentile: percentile,
+			blocks: blocks,
+		})
+	}
+
+	fn block(number: u64, gas_prices: &[u64]) -> BlockGasPrices {
+		BlockGasPrices {
+			number: number,
+			hash: H256::from(number),
+			gas_prices: gas_prices.iter().cloned().map(U256::from).collect(),
+		}
+	}
+
+	fn recalibrated(pricer: &mut GasPricer) -> U256 {
+		let price = ::std::sync::Arc::new(::parking_lot::Mutex::new(None));
+		let p = price.clone();
+		pricer.recalibrate(move |gas_price| *p.lock() = Some(gas_price));
+		let gas_price = *price.lock();
+		gas_price.expect("fixed and percentile gas pricers set the price synchronously; qed")
+	}
+
+	#[test]
+	fn percentile_pricer_should_use_percentile_of_samples() {
+		// given
+		let mut pricer = percentile(50, 100);
+		assert_eq!(pricer.sample_blocks(), Some(100));
+		assert_eq!(recalibrated(&mut pricer), 0.into());
+
+		// when
+		pricer.update_samples(&[], vec![block(9, &[1, 2, 3, 4, 5]), block(10, &[6, 7, 8, 9, 10])]);
+
+		// then
+		assert_eq!(recalibrated(&mut pricer), 5.into());
+		assert_eq!(pricer.percentile_status(), Some(GasPricePercentileStatus {
+			percentile: 50,
+			blocks: 100,
+			block_number: 10,
+			transactions: 10,
+			gas_price: 5.into(),
+		}));
+	}
+
+	#[test]
+	fn percentile_pricer_should_only_keep_recent_blocks() {
+		// given
+		let mut pricer = percentile(50, 2);
+		pricer.update_samples(&[], vec![block(1, &[1]), block(2, &[2])]);
+		assert_eq!(recalibrated(&mut pricer), 1.into());
+
+		// when
+		pricer.update_samples(&[], vec![block(3, &[3])]);
+
+		// then
+		assert_eq!(recalibrated(&mut pricer), 2.into());
+		assert_eq!(pricer.percentile_status().unwrap().transactions, 2);
+		assert_eq!(pricer.percentile_status().unwrap().block_number, 3);
+	}
+
+	#[test]
+	fn percentile_pricer_should_drop_retracted_blocks() {
+		// given
+		let mut pricer = percentile(100, 10);
+		pricer.update_samples(&[], vec![block(1, &[1]), block(2, &[20])]);
+		assert_eq!(recalibrated(&mut pricer), 20.into());
+
+		// when
+		let mut replacement = block(2, &[3]);
+		replacement.hash = H256::from(22);
+		pricer.update_samples(&[H256::from(2)], vec![replacement]);
+
+		// then
+		assert_eq!(recalibrated(&mut pricer), 3.into());
+		assert_eq!(pricer.percentile_status().unwrap().transactions, 2);
+	}
+
+	#[test]
+	fn percentile_pricer_should_keep_price_when_blocks_are_empty() {
+		// given
+		let mut pricer = percentile(20, 1);
+		pricer.update_samples(&[], vec![block(10, &[10, 20, 30, 40, 50])]);
+		assert_eq!(recalibrated(&mut pricer), 10.into());
+
+		// when
+		pricer.update_samples(&[], vec![block(11, &[])]);
+
+		// then
+		assert_eq!(recalibrated(&mut pricer), 10.into());
+		assert_eq!(pricer.percentile_status().unwrap().transactions, 0);
+	}
+
+	#[test]
+	fn fixed_pricer_should_not_sample_blocks() {
+		let mut pricer = GasPricer::new_fixed(7.into());
+		assert_eq!(pricer.sample_blocks(), None);
+		pricer.update_samples(&[], vec![block(10, &[1])]);
+		assert_eq!(recalibrated(&mut pricer), 7.into());
+		assert_eq!(pricer.percentile_status(), None);
+	}
 }
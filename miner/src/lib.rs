@@ -31,6 +31,7 @@ extern crate parking_lot;
 extern crate price_info;
 extern crate rayon;
 extern crate rlp;
+extern crate stats;
 extern crate trace_time;
 extern crate transaction_pool as txpool;
 
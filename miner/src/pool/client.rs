@@ -34,6 +34,8 @@ pub struct AccountDetails {
 	pub balance: U256,
 	/// Is this account a local account?
 	pub is_local: bool,
+	/// Is this account a priority sender (transactions get reserved block space)?
+	pub is_priority: bool,
 }
 
 /// Transaction type
@@ -14,12 +14,14 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity.  If not, see <http://www.gnu.org/licenses/>.
 
-//! Notifier for new transaction hashes.
+//! Notifiers for new transaction hashes and transaction pool events.
 
 use std::fmt;
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use ethereum_types::H256;
+use transaction;
 use txpool::{self, VerifiedTransaction};
 
 use pool::VerifiedTransaction as Transaction;
@@ -64,6 +66,129 @@ impl txpool::Listener<Transaction> for Notifier {
 	}
 }
 
+/// Event in the life of a transaction in the pool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionEvent {
+	/// Transaction has been added to the pool.
+	Added,
+	/// Transaction has become ready for inclusion in a block.
+	Ready,
+	/// Transaction has been replaced by the transaction with given hash.
+	Replaced(H256),
+	/// Transaction has been pushed out of the pool because of the limits,
+	/// possibly to make room for the transaction with given hash.
+	Dropped(Option<H256>),
+	/// Transaction has been rejected with given reason.
+	Rejected(String),
+	/// Transaction has been marked as invalid by executor.
+	Invalid,
+	/// Transaction has been canceled.
+	Canceled,
+	/// Transaction has been culled from the pool (mined or stalled).
+	Culled,
+}
+
+type EventListener = Box<Fn(&[(H256, TransactionEvent)]) + Send + Sync>;
+
+/// Manages notifications to transaction pool event listeners.
+///
+/// Events are only recorded when there is at least one listener.
+#[derive(Default)]
+pub struct EventNotifier {
+	listeners: Vec<EventListener>,
+	ready: HashSet<H256>,
+	pending: Vec<(H256, TransactionEvent)>,
+}
+
+impl fmt::Debug for EventNotifier {
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_struct("EventNotifier")
+			.field("listeners", &self.listeners.len())
+			.field("ready", &self.ready.len())
+			.field("pending", &self.pending)
+			.finish()
+	}
+}
+
+impl EventNotifier {
+	/// Add new listener to receive notifications.
+	pub fn add(&mut self, f: EventListener) {
+		self.listeners.push(f)
+	}
+
+	/// Notify listeners about all events recorded since last notification.
+	pub fn notify(&mut self) {
+		if self.pending.is_empty() {
+			return;
+		}
+
+		for l in &self.listeners {
+			(l)(&self.pending);
+		}
+
+		self.pending.clear();
+	}
+
+	/// Record transactions which are ready for inclusion.
+	/// Only transactions which were not ready before are reported.
+	pub fn ready<'a, T: IntoIterator<Item = &'a H256>>(&mut self, hashes: T) {
+		if self.listeners.is_empty() {
+			return;
+		}
+
+		for hash in hashes {
+			if self.ready.insert(*hash) {
+				self.pending.push((*hash, TransactionEvent::Ready));
+			}
+		}
+	}
+
+	/// Record a transaction rejected before entering the pool.
+	pub fn verification_failed(&mut self, hash: H256, error: &transaction::Error) {
+		self.record(hash, TransactionEvent::Rejected(error.to_string()));
+	}
+
+	fn record(&mut self, hash: H256, event: TransactionEvent) {
+		if !self.listeners.is_empty() {
+			self.pending.push((hash, event));
+		}
+	}
+
+	fn removed(&mut self, hash: H256, event: TransactionEvent) {
+		self.ready.remove(&hash);
+		self.record(hash, event);
+	}
+}
+
+impl txpool::Listener<Transaction> for EventNotifier {
+	fn added(&mut self, tx: &Arc<Transaction>, old: Option<&Arc<Transaction>>) {
+		self.record(*tx.hash(), TransactionEvent::Added);
+		if let Some(old) = old {
+			self.removed(*old.hash(), TransactionEvent::Replaced(*tx.hash()));
+		}
+	}
+
+	fn rejected(&mut self, tx: &Arc<Transaction>, reason: &txpool::ErrorKind) {
+		self.record(*tx.hash(), TransactionEvent::Rejected(reason.to_string()));
+	}
+
+	fn dropped(&mut self, tx: &Arc<Transaction>, new: Option<&Transaction>) {
+		self.removed(*tx.hash(), TransactionEvent::Dropped(new.map(|new| *new.hash())));
+	}
+
+	fn invalid(&mut self, tx: &Arc<Transaction>) {
+		self.removed(*tx.hash(), TransactionEvent::Invalid);
+	}
+
+	fn canceled(&mut self, tx: &Arc<Transaction>) {
+		self.removed(*tx.hash(), TransactionEvent::Canceled);
+	}
+
+	fn mined(&mut self, tx: &Arc<Transaction>) {
+		self.removed(*tx.hash(), TransactionEvent::Culled);
+	}
+}
+
 
 /// Transaction pool logger.
 #[derive(Default, Debug)]
@@ -146,6 +271,36 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn should_notify_event_listeners() {
+		// given
+		let received = Arc::new(Mutex::new(vec![]));
+		let r = received.clone();
+		let listener = Box::new(move |events: &[(H256, TransactionEvent)]| {
+			r.lock().extend(events.iter().cloned());
+		});
+
+		let mut tx_listener = EventNotifier::default();
+		tx_listener.add(listener);
+
+		// when
+		let tx = new_tx();
+		let hash = *tx.hash();
+		tx_listener.added(&tx, None);
+		tx_listener.ready(&[hash]);
+		tx_listener.ready(&[hash]);
+		tx_listener.mined(&tx);
+		assert_eq!(*received.lock(), vec![]);
+
+		// then
+		tx_listener.notify();
+		assert_eq!(*received.lock(), vec![
+			(hash, TransactionEvent::Added),
+			(hash, TransactionEvent::Ready),
+			(hash, TransactionEvent::Culled),
+		]);
+	}
+
 	fn new_tx() -> Arc<Transaction> {
 		let signed = transaction::Transaction {
 			action: transaction::Action::Create,
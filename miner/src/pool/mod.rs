@@ -33,16 +33,19 @@ pub mod verifier;
 #[cfg(test)]
 mod tests;
 
+pub use self::listener::TransactionEvent;
 pub use self::queue::{TransactionQueue, Status as QueueStatus};
 pub use self::txpool::{VerifiedTransaction as PoolVerifiedTransaction, Options};
 
 /// How to prioritize transactions in the pool
-///
-/// TODO [ToDr] Implement more strategies.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum PrioritizationStrategy {
 	/// Simple gas-price based prioritization.
 	GasPriceOnly,
+	/// Gas-price based prioritization penalising very large gas limits.
+	GasFactorAndGasPrice,
+	/// Prioritization by the gas price paid per byte of transaction data.
+	FeePerByte,
 }
 
 /// Transaction priority.
@@ -53,6 +56,11 @@ pub(crate) enum Priority {
 	/// Transactions either from a local account or
 	/// submitted over local RPC connection via `eth_sendRawTransaction`
 	Local,
+	/// Transactions from priority senders (high priority)
+	///
+	/// Senders configured by the node operator (or a registry contract)
+	/// get a reserved share of block gas and are protected from eviction.
+	PrioritySender,
 	/// Transactions from retracted blocks (medium priority)
 	///
 	/// When block becomes non-canonical we re-import the transactions it contains
@@ -69,6 +77,13 @@ impl Priority {
 			_ => false,
 		}
 	}
+
+	fn is_priority_sender(&self) -> bool {
+		match *self {
+			Priority::PrioritySender => true,
+			_ => false,
+		}
+	}
 }
 
 /// Verified transaction stored in the pool.
@@ -79,6 +94,7 @@ pub struct VerifiedTransaction {
 	hash: H256,
 	sender: Address,
 	priority: Priority,
+	restored: bool,
 	insertion_id: usize,
 }
 
@@ -96,6 +112,7 @@ impl VerifiedTransaction {
 			hash,
 			sender,
 			priority: Priority::Retracted,
+			restored: false,
 			insertion_id: 0,
 		}
 	}
@@ -105,11 +122,21 @@ impl VerifiedTransaction {
 		self.priority
 	}
 
+	/// Is this transaction sent by one of the priority senders?
+	pub fn is_from_priority_sender(&self) -> bool {
+		self.priority.is_priority_sender()
+	}
+
 	/// Gets transaction insertion id.
 	pub(crate) fn insertion_id(&self) -> usize {
 		self.insertion_id
 	}
 
+	/// Returns true if the transaction was restored from a dump of the pool made on shutdown.
+	pub fn is_restored(&self) -> bool {
+		self.restored
+	}
+
 	/// Gets wrapped `SignedTransaction`
 	pub fn signed(&self) -> &transaction::SignedTransaction {
 		&self.transaction
@@ -30,8 +30,8 @@ use txpool::{self, Verifier};
 use pool::{self, scoring, verifier, client, ready, listener, PrioritizationStrategy};
 use pool::local_transactions::LocalTransactionsList;
 
-type Listener = (LocalTransactionsList, (listener::Notifier, listener::Logger));
-type Pool = txpool::Pool<pool::VerifiedTransaction, scoring::NonceAndGasPrice, Listener>;
+type Listener = (LocalTransactionsList, (listener::Notifier, (listener::Logger, listener::EventNotifier)));
+type Pool = txpool::Pool<pool::VerifiedTransaction, scoring::QueueScoring, Listener>;
 
 /// Max cache time in milliseconds for pending transactions.
 ///
@@ -149,9 +149,10 @@ impl TransactionQueue {
 		verification_options: verifier::Options,
 		strategy: PrioritizationStrategy,
 	) -> Self {
+		let scoring = scoring::QueueScoring::new(strategy, limits.replace_bump_percent);
 		TransactionQueue {
 			insertion_id: Default::default(),
-			pool: RwLock::new(txpool::Pool::new(Default::default(), scoring::NonceAndGasPrice(strategy), limits)),
+			pool: RwLock::new(txpool::Pool::new(Default::default(), scoring, limits)),
 			options: RwLock::new(verification_options),
 			cached_pending: RwLock::new(CachedPending::none()),
 		}
@@ -178,10 +179,25 @@ impl TransactionQueue {
 		let options = self.options.read().clone();
 
 		let verifier = verifier::Verifier::new(client, options, self.insertion_id.clone());
-		let results = transactions
+		let verified = transactions
 			.into_par_iter()
-			.map(|transaction| verifier.verify_transaction(transaction))
-			.map(|result| result.and_then(|verified| {
+			.map(|transaction| (transaction.hash(), verifier.verify_transaction(transaction)))
+			.collect::<Vec<_>>();
+
+		// Report rejected transactions under a single lock.
+		{
+			let mut pool = self.pool.write();
+			let events = &mut ((pool.listener_mut().1).1).1;
+			for &(hash, ref result) in &verified {
+				if let Err(ref err) = *result {
+					events.verification_failed(hash, err);
+				}
+			}
+		}
+
+		let results = verified
+			.into_iter()
+			.map(|(_hash, result)| result.and_then(|verified| {
 				self.pool.write().import(verified)
 					.map(|_imported| ())
 					.map_err(convert_error)
@@ -190,6 +206,7 @@ impl TransactionQueue {
 
 		// Notify about imported transactions.
 		(self.pool.write().listener_mut().1).0.notify();
+		self.notify_events();
 
 		if results.iter().any(|r| r.is_ok()) {
 			self.cached_pending.write().clear();
@@ -231,6 +248,13 @@ impl TransactionQueue {
 
 		let pending: Vec<_> = self.collect_pending(client, block_number, current_timestamp, nonce_cap, |i| i.collect());
 
+		{
+			let mut pool = self.pool.write();
+			let events = &mut ((pool.listener_mut().1).1).1;
+			events.ready(pending.iter().map(|tx| &tx.hash));
+			events.notify();
+		}
+
 		*cached_pending = CachedPending {
 			block_number,
 			current_timestamp,
@@ -258,7 +282,7 @@ impl TransactionQueue {
 		F: FnOnce(txpool::PendingIterator<
 			pool::VerifiedTransaction,
 			(ready::Condition, ready::State<C>),
-			scoring::NonceAndGasPrice,
+			scoring::QueueScoring,
 			Listener,
 		>) -> T,
 	{
@@ -294,6 +318,7 @@ impl TransactionQueue {
 		let state_readiness = ready::State::new(client, stale_id, nonce_cap);
 
 		let removed = self.pool.write().cull(None, state_readiness);
+		self.notify_events();
 		debug!(target: "txqueue", "Removed {} stalled transactions. {}", removed, self.status());
 	}
 
@@ -346,6 +371,7 @@ impl TransactionQueue {
 				.map(|hash| pool.remove(hash, is_invalid))
 				.collect::<Vec<_>>()
 		};
+		self.notify_events();
 
 		if results.iter().any(Option::is_some) {
 			self.cached_pending.write().clear();
@@ -357,6 +383,7 @@ impl TransactionQueue {
 	/// Clear the entire pool.
 	pub fn clear(&self) {
 		self.pool.write().clear();
+		self.notify_events();
 	}
 
 	/// Penalize given senders.
@@ -410,6 +437,16 @@ impl TransactionQueue {
 		let mut pool = self.pool.write();
 		(pool.listener_mut().1).0.add(f);
 	}
+
+	/// Add a callback to be notified about every event of every transaction in the pool.
+	pub fn add_event_listener(&self, f: Box<Fn(&[(H256, pool::TransactionEvent)]) + Send + Sync>) {
+		let mut pool = self.pool.write();
+		((pool.listener_mut().1).1).1.add(f);
+	}
+
+	fn notify_events(&self) {
+		((self.pool.write().listener_mut().1).1).1.notify();
+	}
 }
 
 
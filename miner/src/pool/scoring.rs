@@ -20,94 +20,253 @@
 //! Low nonces need to be included first. If there are two transactions from the same sender
 //! and with the same `nonce` only one of them can be included.
 //! We choose the one with higher gas price, but also require that gas price increment
-//! is high enough to prevent attacking miners by requiring them to reshuffle/reexecute
-//! the queue too often.
+//! (12.5% unless configured by `txpool::Options::replace_bump_percent`) is high enough to prevent attacking
+//! miners by requiring them to reshuffle/reexecute the queue too often.
 //!
-//! Transactions between senders are prioritized using `gas price`. Higher `gas price`
-//! yields more profits for miners. Additionally we prioritize transactions that originate
-//! from our local node (own transactions).
+//! Transactions between senders are prioritized using a score depending on the
+//! `PrioritizationStrategy`: `gas price` (higher `gas price` yields more profits for miners),
+//! `gas price` penalised for very large gas limits, or `gas price` per byte of transaction data.
+//! The same score is used to decide on replacements. Additionally we prioritize transactions
+//! that originate from our local node (own transactions).
 
 use std::cmp;
 
 use ethereum_types::U256;
+use rlp::Encodable;
 use txpool;
 use super::{PrioritizationStrategy, VerifiedTransaction};
 
 /// Transaction with the same (sender, nonce) can be replaced only if
-/// `new_gas_price > old_gas_price + old_gas_price >> SHIFT`
+/// `new_score > old_score + old_score >> SHIFT`, unless a different bump is configured.
 const GAS_PRICE_BUMP_SHIFT: usize = 3; // 2 = 25%, 3 = 12.5%, 4 = 6.25%
 
+/// Gas limit above which `GasFactorAndGasPrice` lowers the score proportionally to the excess.
+const GAS_FACTOR_THRESHOLD: u64 = 1_000_000;
+
+// score of a transaction before applying priority boosts and penalties.
+trait TransactionScore {
+	fn score(&self, tx: &VerifiedTransaction) -> U256;
+
+	// minimal score increase in percent required for a replacement, `None` for the default 12.5%.
+	fn replace_bump_percent(&self) -> Option<usize>;
+}
+
 /// Simple, gas-price based scoring for transactions.
 ///
 /// NOTE: Currently penalization does not apply to new transactions that enter the pool.
 /// We might want to store penalization status in some persistent state.
-#[derive(Debug)]
-pub struct NonceAndGasPrice(pub PrioritizationStrategy);
+#[derive(Debug, Default)]
+pub struct NonceAndGasPrice {
+	/// Minimal score increase, in percent, required to replace a transaction. Defaults to 12.5%.
+	pub replace_bump_percent: Option<usize>,
+}
 
-impl txpool::Scoring<VerifiedTransaction> for NonceAndGasPrice {
-	type Score = U256;
-	type Event = ();
+impl TransactionScore for NonceAndGasPrice {
+	fn score(&self, tx: &VerifiedTransaction) -> U256 {
+		tx.transaction.gas_price
+	}
 
-	fn compare(&self, old: &VerifiedTransaction, other: &VerifiedTransaction) -> cmp::Ordering {
-		old.transaction.nonce.cmp(&other.transaction.nonce)
+	fn replace_bump_percent(&self) -> Option<usize> {
+		self.replace_bump_percent
 	}
+}
 
-	fn choose(&self, old: &VerifiedTransaction, new: &VerifiedTransaction) -> txpool::scoring::Choice {
-		if old.transaction.nonce != new.transaction.nonce {
-			return txpool::scoring::Choice::InsertNew
-		}
+/// Gas-price based scoring penalising transactions with very large gas limits.
+///
+/// Transactions up to `GAS_FACTOR_THRESHOLD` gas are scored by gas price,
+/// above that the gas price is scaled down by `GAS_FACTOR_THRESHOLD / gas`.
+#[derive(Debug, Default)]
+pub struct GasFactorAndGasPrice {
+	/// Minimal score increase, in percent, required to replace a transaction. Defaults to 12.5%.
+	pub replace_bump_percent: Option<usize>,
+}
+
+impl TransactionScore for GasFactorAndGasPrice {
+	fn score(&self, tx: &VerifiedTransaction) -> U256 {
+		let threshold = U256::from(GAS_FACTOR_THRESHOLD);
+		let gas = cmp::max(tx.transaction.gas, threshold);
+		tx.transaction.gas_price.saturating_mul(threshold) / gas
+	}
 
-		let old_gp = old.transaction.gas_price;
-		let new_gp = new.transaction.gas_price;
+	fn replace_bump_percent(&self) -> Option<usize> {
+		self.replace_bump_percent
+	}
+}
+
+/// Scoring by the gas price a transaction pays per byte of its RLP encoding.
+///
+/// The gas limit is deliberately left out: a sender could declare far more gas
+/// than the transaction uses to inflate the score without paying for it.
+#[derive(Debug, Default)]
+pub struct NonceAndFeePerByte {
+	/// Minimal score increase, in percent, required to replace a transaction. Defaults to 12.5%.
+	pub replace_bump_percent: Option<usize>,
+}
+
+impl TransactionScore for NonceAndFeePerByte {
+	fn score(&self, tx: &VerifiedTransaction) -> U256 {
+		let size = tx.signed().rlp_bytes().len();
+		tx.transaction.gas_price / U256::from(size as u64)
+	}
+
+	fn replace_bump_percent(&self) -> Option<usize> {
+		self.replace_bump_percent
+	}
+}
 
-		let min_required_gp = old_gp + (old_gp >> GAS_PRICE_BUMP_SHIFT);
+macro_rules! impl_scoring {
+	($name: ident) => {
+		impl txpool::Scoring<VerifiedTransaction> for $name {
+			type Score = U256;
+			type Event = ();
 
-		match min_required_gp.cmp(&new_gp) {
-			cmp::Ordering::Greater => txpool::scoring::Choice::RejectNew,
-			_ => txpool::scoring::Choice::ReplaceOld,
+			fn compare(&self, old: &VerifiedTransaction, other: &VerifiedTransaction) -> cmp::Ordering {
+				old.transaction.nonce.cmp(&other.transaction.nonce)
+			}
+
+			fn choose(&self, old: &VerifiedTransaction, new: &VerifiedTransaction) -> txpool::scoring::Choice {
+				choose(self, old, new)
+			}
+
+			fn update_scores(&self, txs: &[txpool::Transaction<VerifiedTransaction>], scores: &mut [U256], change: txpool::scoring::Change) {
+				update_scores(self, txs, scores, change)
+			}
+
+			fn should_replace(&self, old: &VerifiedTransaction, new: &VerifiedTransaction) -> bool {
+				should_replace(self, old, new)
+			}
 		}
 	}
+}
 
-	fn update_scores(&self, txs: &[txpool::Transaction<VerifiedTransaction>], scores: &mut [U256], change: txpool::scoring::Change) {
-		use self::txpool::scoring::Change;
-
-		match change {
-			Change::Culled(_) => {},
-			Change::RemovedAt(_) => {}
-			Change::InsertedAt(i) | Change::ReplacedAt(i) => {
-				assert!(i < txs.len());
-				assert!(i < scores.len());
-
-				scores[i] = txs[i].transaction.transaction.gas_price;
-				let boost = match txs[i].priority() {
-					super::Priority::Local => 15,
-					super::Priority::Retracted => 10,
-					super::Priority::Regular => 0,
-				};
-				scores[i] = scores[i] << boost;
-			},
-			// We are only sending an event in case of penalization.
-			// So just lower the priority of all non-local transactions.
-			Change::Event(_) => {
-				for (score, tx) in scores.iter_mut().zip(txs) {
-					// Never penalize local transactions.
-					if !tx.priority().is_local() {
-						*score = *score >> 3;
-					}
+impl_scoring!(NonceAndGasPrice);
+impl_scoring!(GasFactorAndGasPrice);
+impl_scoring!(NonceAndFeePerByte);
+
+fn choose<S: TransactionScore>(scoring: &S, old: &VerifiedTransaction, new: &VerifiedTransaction) -> txpool::scoring::Choice {
+	if old.transaction.nonce != new.transaction.nonce {
+		return txpool::scoring::Choice::InsertNew
+	}
+
+	let old_score = scoring.score(old);
+	let new_score = scoring.score(new);
+
+	let bump = match scoring.replace_bump_percent() {
+		Some(percent) => old_score.saturating_mul(U256::from(percent as u64)) / U256::from(100),
+		None => old_score >> GAS_PRICE_BUMP_SHIFT,
+	};
+	let min_required_score = old_score.saturating_add(bump);
+
+	match min_required_score.cmp(&new_score) {
+		cmp::Ordering::Greater => txpool::scoring::Choice::RejectNew,
+		_ => txpool::scoring::Choice::ReplaceOld,
+	}
+}
+
+fn update_scores<S: TransactionScore>(scoring: &S, txs: &[txpool::Transaction<VerifiedTransaction>], scores: &mut [U256], change: txpool::scoring::Change) {
+	use self::txpool::scoring::Change;
+
+	match change {
+		Change::Culled(_) => {},
+		Change::RemovedAt(_) => {}
+		Change::InsertedAt(i) | Change::ReplacedAt(i) => {
+			assert!(i < txs.len());
+			assert!(i < scores.len());
+
+			scores[i] = scoring.score(&txs[i].transaction);
+			let boost = match txs[i].priority() {
+				super::Priority::Local => 15,
+				super::Priority::PrioritySender => 12,
+				super::Priority::Retracted => 10,
+				super::Priority::Regular => 0,
+			};
+			scores[i] = scores[i] << boost;
+		},
+		// We are only sending an event in case of penalization.
+		// So just lower the priority of all non-local transactions.
+		Change::Event(_) => {
+			for (score, tx) in scores.iter_mut().zip(txs) {
+				// Never penalize local transactions.
+				if !tx.priority().is_local() {
+					*score = *score >> 3;
 				}
-			},
+			}
+		},
+	}
+}
+
+fn should_replace<S: TransactionScore>(scoring: &S, old: &VerifiedTransaction, new: &VerifiedTransaction) -> bool {
+	if old.sender == new.sender {
+		// prefer earliest transaction
+		if new.transaction.nonce < old.transaction.nonce {
+			return true
 		}
 	}
 
-	fn should_replace(&self, old: &VerifiedTransaction, new: &VerifiedTransaction) -> bool {
-		if old.sender == new.sender {
-			// prefer earliest transaction
-			if new.transaction.nonce < old.transaction.nonce {
-				return true
-			}
+	// transactions of priority senders are only evicted in favour of local or other priority transactions
+	if old.priority().is_priority_sender() && !(new.priority().is_local() || new.priority().is_priority_sender()) {
+		return false
+	}
+
+	choose(scoring, old, new) == txpool::scoring::Choice::ReplaceOld
+}
+
+/// Scoring used by the transaction queue, as chosen by the `PrioritizationStrategy`.
+#[derive(Debug)]
+pub enum QueueScoring {
+	/// See `NonceAndGasPrice`.
+	GasPrice(NonceAndGasPrice),
+	/// See `GasFactorAndGasPrice`.
+	GasFactor(GasFactorAndGasPrice),
+	/// See `NonceAndFeePerByte`.
+	FeePerByte(NonceAndFeePerByte),
+}
+
+impl QueueScoring {
+	/// Create new scoring for given strategy and minimal replacement bump (in percent, 12.5% if `None`).
+	pub fn new(strategy: PrioritizationStrategy, replace_bump_percent: Option<usize>) -> Self {
+		match strategy {
+			PrioritizationStrategy::GasPriceOnly => QueueScoring::GasPrice(NonceAndGasPrice { replace_bump_percent }),
+			PrioritizationStrategy::GasFactorAndGasPrice => QueueScoring::GasFactor(GasFactorAndGasPrice { replace_bump_percent }),
+			PrioritizationStrategy::FeePerByte => QueueScoring::FeePerByte(NonceAndFeePerByte { replace_bump_percent }),
 		}
+	}
+}
 
-		self.choose(old, new) == txpool::scoring::Choice::ReplaceOld
+impl From<PrioritizationStrategy> for QueueScoring {
+	fn from(strategy: PrioritizationStrategy) -> Self {
+		QueueScoring::new(strategy, None)
+	}
+}
+
+macro_rules! dispatch {
+	($self: ident, $scoring: ident => $e: expr) => {
+		match *$self {
+			QueueScoring::GasPrice(ref $scoring) => $e,
+			QueueScoring::GasFactor(ref $scoring) => $e,
+			QueueScoring::FeePerByte(ref $scoring) => $e,
+		}
+	}
+}
+
+impl txpool::Scoring<VerifiedTransaction> for QueueScoring {
+	type Score = U256;
+	type Event = ();
+
+	fn compare(&self, old: &VerifiedTransaction, other: &VerifiedTransaction) -> cmp::Ordering {
+		dispatch!(self, s => txpool::Scoring::compare(s, old, other))
+	}
+
+	fn choose(&self, old: &VerifiedTransaction, new: &VerifiedTransaction) -> txpool::scoring::Choice {
+		dispatch!(self, s => txpool::Scoring::choose(s, old, new))
+	}
+
+	fn update_scores(&self, txs: &[txpool::Transaction<VerifiedTransaction>], scores: &mut [U256], change: txpool::scoring::Change) {
+		dispatch!(self, s => txpool::Scoring::update_scores(s, txs, scores, change))
+	}
+
+	fn should_replace(&self, old: &VerifiedTransaction, new: &VerifiedTransaction) -> bool {
+		dispatch!(self, s => txpool::Scoring::should_replace(s, old, new))
 	}
 }
 
@@ -122,7 +281,7 @@ mod tests {
 	#[test]
 	fn should_calculate_score_correctly() {
 		// given
-		let scoring = NonceAndGasPrice(PrioritizationStrategy::GasPriceOnly);
+		let scoring = NonceAndGasPrice::default();
 		let (tx1, tx2, tx3) = Tx::default().signed_triple();
 		let transactions = vec![tx1, tx2, tx3].into_iter().enumerate().map(|(i, tx)| {
 			let mut verified = tx.verified();
@@ -171,4 +330,86 @@ mod tests {
 		scoring.update_scores(&transactions, &mut *scores, txpool::scoring::Change::Event(()));
 		assert_eq!(scores, vec![32768.into(), 128.into(), 0.into()]);
 	}
+
+	#[test]
+	fn gas_factor_should_penalize_large_gas_limits() {
+		let scoring = GasFactorAndGasPrice::default();
+		let small = Tx::gas_and_gas_price(21_000, 10).signed().verified();
+		let threshold = Tx::gas_and_gas_price(1_000_000, 10).signed().verified();
+		let large = Tx::gas_and_gas_price(4_000_000, 10).signed().verified();
+
+		assert_eq!(scoring.score(&small), 10.into());
+		assert_eq!(scoring.score(&threshold), 10.into());
+		assert_eq!(scoring.score(&large), 2.into());
+	}
+
+	#[test]
+	fn gas_factor_should_replace_consistently_with_score() {
+		// given
+		let old = Tx::gas_and_gas_price(4_000_000, 10).signed().verified();
+		let lower_gas = Tx::gas_and_gas_price(2_000_000, 10).signed().verified();
+		let higher_gas = Tx::gas_and_gas_price(8_000_000, 15).signed().verified();
+
+		// then
+		assert_eq!(GasFactorAndGasPrice::default().choose(&old, &lower_gas), txpool::scoring::Choice::ReplaceOld);
+		assert_eq!(GasFactorAndGasPrice::default().choose(&old, &higher_gas), txpool::scoring::Choice::RejectNew);
+		assert_eq!(NonceAndGasPrice::default().choose(&old, &lower_gas), txpool::scoring::Choice::RejectNew);
+		assert_eq!(NonceAndGasPrice::default().choose(&old, &higher_gas), txpool::scoring::Choice::ReplaceOld);
+	}
+
+	#[test]
+	fn fee_per_byte_should_prefer_small_transactions() {
+		let scoring = NonceAndFeePerByte::default();
+		let small = Tx::gas_price(10_000).signed().verified();
+		let big = Tx::gas_price(10_000).big_one().verified();
+		let inflated = Tx::gas_and_gas_price(4_000_000, 10_000).signed().verified();
+
+		let size = small.signed().rlp_bytes().len() as u64;
+		assert_eq!(scoring.score(&small), U256::from(10_000 / size));
+		assert!(scoring.score(&small) > scoring.score(&big));
+		assert!(scoring.score(&inflated) <= scoring.score(&small));
+		assert_eq!(NonceAndGasPrice::default().score(&small), NonceAndGasPrice::default().score(&big));
+	}
+
+	#[test]
+	fn queue_scoring_should_follow_strategy() {
+		let transactions = vec![txpool::Transaction {
+			insertion_id: 0,
+			transaction: Arc::new(Tx::gas_and_gas_price(4_000_000, 10).signed().verified()),
+		}];
+		let fee_per_byte = NonceAndFeePerByte::default().score(&transactions[0].transaction) << 10;
+
+		for (strategy, expected) in vec![
+			(PrioritizationStrategy::GasPriceOnly, U256::from(10u64 << 10)),
+			(PrioritizationStrategy::GasFactorAndGasPrice, U256::from(2u64 << 10)),
+			(PrioritizationStrategy::FeePerByte, fee_per_byte),
+		] {
+			let scoring = QueueScoring::from(strategy);
+			let mut scores = vec![U256::zero()];
+			scoring.update_scores(&transactions, &mut *scores, txpool::scoring::Change::InsertedAt(0));
+			assert_eq!(scores, vec![expected]);
+		}
+	}
+
+	#[test]
+	fn should_require_configured_replacement_bump() {
+		// given
+		let old = Tx::gas_price(800).signed().verified();
+		let bump_5 = Tx::gas_price(840).signed().verified();
+		let bump_12 = Tx::gas_price(896).signed().verified();
+		let bump_12_5 = Tx::gas_price(900).signed().verified();
+		let bump_20 = Tx::gas_price(960).signed().verified();
+		let choose = |bump: Option<usize>, new: &VerifiedTransaction| QueueScoring::new(PrioritizationStrategy::GasPriceOnly, bump).choose(&old, new);
+
+		// then
+		assert_eq!(choose(Some(0), &old), txpool::scoring::Choice::ReplaceOld);
+		assert_eq!(choose(Some(5), &bump_5), txpool::scoring::Choice::ReplaceOld);
+		assert_eq!(choose(Some(12), &bump_5), txpool::scoring::Choice::RejectNew);
+		assert_eq!(choose(Some(12), &bump_12), txpool::scoring::Choice::ReplaceOld);
+		assert_eq!(choose(Some(25), &bump_20), txpool::scoring::Choice::RejectNew);
+
+		// 12.5% by default
+		assert_eq!(choose(None, &bump_12), txpool::scoring::Choice::RejectNew);
+		assert_eq!(choose(None, &bump_12_5), txpool::scoring::Choice::ReplaceOld);
+	}
 }
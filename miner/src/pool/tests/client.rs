@@ -29,6 +29,7 @@ pub struct TestClient {
 	gas_required: U256,
 	is_service_transaction: bool,
 	local_address: Address,
+	priority_address: Option<Address>,
 	max_transaction_size: usize,
 }
 
@@ -39,10 +40,12 @@ impl Default for TestClient {
 				nonce: 123.into(),
 				balance: 63_100.into(),
 				is_local: false,
+				is_priority: false,
 			},
 			gas_required: 21_000.into(),
 			is_service_transaction: false,
 			local_address: Default::default(),
+			priority_address: None,
 			max_transaction_size: MAX_TRANSACTION_SIZE,
 		}
 	}
@@ -73,6 +76,11 @@ impl TestClient {
 		self
 	}
 
+	pub fn with_priority_sender(mut self, address: &Address) -> Self {
+		self.priority_address = Some(*address);
+		self
+	}
+
 	pub fn with_service_transaction(mut self) -> Self {
 		self.is_service_transaction = true;
 		self
@@ -84,6 +92,7 @@ impl TestClient {
 			hash: tx.hash(),
 			sender: tx.sender(),
 			priority: pool::Priority::Regular,
+			restored: false,
 			transaction: tx,
 			insertion_id: 1,
 		}
@@ -106,6 +115,9 @@ impl pool::client::Client for TestClient {
 		if address == &self.local_address {
 			details.is_local = true;
 		}
+		if Some(*address) == self.priority_address {
+			details.is_priority = true;
+		}
 
 		details
 	}
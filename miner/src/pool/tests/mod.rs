@@ -14,11 +14,15 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity.  If not, see <http://www.gnu.org/licenses/>.
 
-use ethereum_types::U256;
+use std::sync::Arc;
+
+use ethereum_types::{H256, U256};
+use ethkey::{Random, Generator};
+use parking_lot::Mutex;
 use transaction::{self, PendingTransaction};
 use txpool;
 
-use pool::{verifier, TransactionQueue, PrioritizationStrategy};
+use pool::{verifier, TransactionQueue, TransactionEvent, PrioritizationStrategy};
 
 pub mod tx;
 pub mod client;
@@ -31,12 +35,14 @@ fn new_queue() -> TransactionQueue {
 		txpool::Options {
 			max_count: 3,
 			max_per_sender: 3,
-			max_mem_usage: 50
+			max_mem_usage: 50,
+			replace_bump_percent: None,
 		},
 		verifier::Options {
 			minimal_gas_price: 1.into(),
 			block_gas_limit: 1_000_000.into(),
 			tx_gas_limit: 1_000_000.into(),
+			max_nonce_gap: None,
 		},
 		PrioritizationStrategy::GasPriceOnly,
 	)
@@ -49,12 +55,14 @@ fn should_return_correct_nonces_when_dropped_because_of_limit() {
 		txpool::Options {
 			max_count: 3,
 			max_per_sender: 1,
-			max_mem_usage: 50
+			max_mem_usage: 50,
+			replace_bump_percent: None,
 		},
 		verifier::Options {
 			minimal_gas_price: 1.into(),
 			block_gas_limit: 1_000_000.into(),
 			tx_gas_limit: 1_000_000.into(),
+			max_nonce_gap: None,
 		},
 		PrioritizationStrategy::GasPriceOnly,
 	);
@@ -423,12 +431,14 @@ fn should_prefer_current_transactions_when_hitting_the_limit() {
 		txpool::Options {
 			max_count: 1,
 			max_per_sender: 2,
-			max_mem_usage: 50
+			max_mem_usage: 50,
+			replace_bump_percent: None,
 		},
 		verifier::Options {
 			minimal_gas_price: 1.into(),
 			block_gas_limit: 1_000_000.into(),
 			tx_gas_limit: 1_000_000.into(),
+			max_nonce_gap: None,
 		},
 		PrioritizationStrategy::GasPriceOnly,
 	);
@@ -617,6 +627,7 @@ fn should_remove_out_of_date_transactions_occupying_queue() {
 			max_count: 105,
 			max_per_sender: 3,
 			max_mem_usage: 5_000_000,
+			replace_bump_percent: None,
 		},
 		verifier::Options {
 			minimal_gas_price: 10.into(),
@@ -653,7 +664,8 @@ fn should_accept_local_transactions_below_min_gas_price() {
 		txpool::Options {
 			max_count: 3,
 			max_per_sender: 3,
-			max_mem_usage: 50
+			max_mem_usage: 50,
+			replace_bump_percent: None,
 		},
 		verifier::Options {
 			minimal_gas_price: 10.into(),
@@ -766,4 +778,148 @@ fn should_reject_big_transaction() {
 		verifier::Transaction::Local(PendingTransaction::new(big_tx, transaction::Condition::Timestamp(1000).into()))
 	]);
 	assert_eq!(res, vec![Err(transaction::Error::TooBig)]);
-}
\ No newline at end of file
+}
+
+#[test]
+fn should_mark_restored_transactions() {
+	// given
+	let txq = new_queue();
+	let (tx, _, tx2) = Tx::default().signed_triple();
+	let tx3 = Tx::gas_price(0).signed();
+	let hash = tx.hash();
+	let hash2 = tx2.hash();
+
+	// when
+	let res = txq.import(TestClient::new(), vec![
+		verifier::Transaction::Restored(tx.into()),
+		verifier::Transaction::Restored(tx2.into()),
+		verifier::Transaction::Restored(tx3.into()),
+	]);
+
+	// then
+	assert_eq!(res, vec![Ok(()), Ok(()), Err(transaction::Error::InsufficientGasPrice {
+		minimal: 1.into(),
+		got: 0.into(),
+	})]);
+	let all = txq.all_transactions();
+	assert_eq!(all.len(), 2);
+	assert!(all.iter().all(|tx| tx.is_restored()));
+	let top = txq.pending(TestClient::new(), 0, 0, None);
+	assert_eq!(top.len(), 1);
+	assert_eq!(top[0].hash, hash);
+	assert!(all.iter().any(|tx| tx.hash == hash2));
+}
+
+#[test]
+fn should_reject_transactions_with_too_large_nonce_gap() {
+	// given
+	let txq = new_queue();
+	txq.set_verifier_options(verifier::Options {
+		minimal_gas_price: 1.into(),
+		max_nonce_gap: Some(1.into()),
+		..Default::default()
+	});
+	let (tx1, tx2, tx3) = Tx::default().signed_triple();
+	let (_, _, local) = Tx::default().signed_triple();
+
+	// when
+	let res = txq.import(TestClient::new(), vec![tx1, tx2, tx3].unverified());
+	let res2 = txq.import(TestClient::new(), vec![local].local());
+
+	// then
+	assert_eq!(res, vec![Ok(()), Ok(()), Err(transaction::Error::NonceGapTooLarge {
+		limit: 1.into(),
+		got: 2.into(),
+	})]);
+	assert_eq!(res2, vec![Ok(())]);
+	assert_eq!(txq.status().status.transaction_count, 3);
+}
+
+#[test]
+fn should_use_configured_replacement_bump() {
+	// given
+	let txq = TransactionQueue::new(
+		txpool::Options {
+			max_count: 3,
+			max_per_sender: 3,
+			max_mem_usage: 50,
+			replace_bump_percent: Some(50),
+		},
+		verifier::Options {
+			minimal_gas_price: 1.into(),
+			block_gas_limit: 1_000_000.into(),
+			tx_gas_limit: 1_000_000.into(),
+			max_nonce_gap: None,
+		},
+		PrioritizationStrategy::GasPriceOnly,
+	);
+	let keypair = Random.generate().unwrap();
+	let tx = Tx::gas_price(10).unsigned().sign(keypair.secret(), None);
+	let too_cheap = Tx::gas_price(14).unsigned().sign(keypair.secret(), None);
+	let replacement = Tx::gas_price(15).unsigned().sign(keypair.secret(), None);
+
+	let client = TestClient::new().with_balance(1_000_000);
+
+	// when
+	let res = txq.import(client.clone(), vec![tx.unverified()]);
+	let res2 = txq.import(client.clone(), vec![too_cheap.unverified()]);
+	let res3 = txq.import(client.clone(), vec![replacement.unverified()]);
+
+	// then
+	assert_eq!(res, vec![Ok(())]);
+	assert_eq!(res2, vec![Err(transaction::Error::TooCheapToReplace)]);
+	assert_eq!(res3, vec![Ok(())]);
+	assert_eq!(txq.status().status.transaction_count, 1);
+}
+
+#[test]
+fn should_not_evict_priority_sender_transactions_when_full() {
+	// given
+	let txq = new_queue();
+	let (tx1, tx2, tx3) = Tx::gas_price(1).signed_triple();
+	let expensive = Tx::gas_price(10_000).signed();
+	let client = TestClient::new()
+		.with_balance(1_000_000_000)
+		.with_priority_sender(&tx1.sender());
+
+	// when
+	let res = txq.import(client.clone(), vec![tx1, tx2, tx3].unverified());
+	let res2 = txq.import(client.clone(), vec![expensive.unverified()]);
+
+	// then
+	assert_eq!(res, vec![Ok(()), Ok(()), Ok(())]);
+	assert_eq!(res2, vec![Err(transaction::Error::LimitReached)]);
+	assert_eq!(txq.status().status.transaction_count, 3);
+	assert!(txq.pending(client, 0, 0, None).iter().all(|tx| tx.is_from_priority_sender()));
+}
+
+#[test]
+fn should_notify_about_transaction_events() {
+	// given
+	let txq = new_queue();
+	let events = Arc::new(Mutex::new(vec![]));
+	let e = events.clone();
+	txq.add_event_listener(Box::new(move |received: &[(H256, TransactionEvent)]| {
+		e.lock().extend(received.iter().cloned());
+	}));
+	let (tx, replacement) = Tx::default().signed_replacement();
+	let too_cheap = Tx::gas_price(0).signed();
+	let (hash, replacement_hash, too_cheap_hash) = (tx.hash(), replacement.hash(), too_cheap.hash());
+
+	// when
+	txq.import(TestClient::new(), vec![tx.unverified()]);
+	txq.import(TestClient::new(), vec![replacement.unverified()]);
+	txq.import(TestClient::new(), vec![too_cheap.unverified()]);
+	txq.pending(TestClient::new(), 0, 0, None);
+
+	// then
+	assert_eq!(*events.lock(), vec![
+		(hash, TransactionEvent::Added),
+		(replacement_hash, TransactionEvent::Added),
+		(hash, TransactionEvent::Replaced(replacement_hash)),
+		(too_cheap_hash, TransactionEvent::Rejected(
+			transaction::Error::InsufficientGasPrice { minimal: 1.into(), got: 0.into() }.to_string()
+		)),
+		(replacement_hash, TransactionEvent::Ready),
+	]);
+}
@@ -46,6 +46,14 @@ impl Tx {
 		}
 	}
 
+	pub fn gas_and_gas_price(gas: u64, gas_price: u64) -> Self {
+		Tx {
+			gas,
+			gas_price,
+			..Default::default()
+		}
+	}
+
 	pub fn signed(self) -> SignedTransaction {
 		let keypair = Random.generate().unwrap();
 		self.unsigned().sign(keypair.secret(), None)
@@ -43,6 +43,8 @@ pub struct Options {
 	pub block_gas_limit: U256,
 	/// Maximal gas limit for a single transaction.
 	pub tx_gas_limit: U256,
+	/// Maximal distance between the nonce of a non-local transaction and the state nonce of its sender.
+	pub max_nonce_gap: Option<U256>,
 }
 
 #[cfg(test)]
@@ -52,6 +54,7 @@ impl Default for Options {
 			minimal_gas_price: 0.into(),
 			block_gas_limit: U256::max_value(),
 			tx_gas_limit: U256::max_value(),
+			max_nonce_gap: None,
 		}
 	}
 }
@@ -68,6 +71,11 @@ pub enum Transaction {
 	/// We could skip some parts of verification of such transactions
 	Retracted(transaction::UnverifiedTransaction),
 
+	/// Transaction restored from a dump of the pool made on shutdown.
+	///
+	/// Goes through full verification, since the state might have changed in the meantime.
+	Restored(transaction::UnverifiedTransaction),
+
 	/// Locally signed or retracted transaction.
 	///
 	/// We can skip consistency verifications and just verify readiness.
@@ -75,10 +83,12 @@ pub enum Transaction {
 }
 
 impl Transaction {
-	fn hash(&self) -> H256 {
+	/// Return transaction hash
+	pub(crate) fn hash(&self) -> H256 {
 		match *self {
 			Transaction::Unverified(ref tx) => tx.hash(),
 			Transaction::Retracted(ref tx) => tx.hash(),
+			Transaction::Restored(ref tx) => tx.hash(),
 			Transaction::Local(ref tx) => tx.hash(),
 		}
 	}
@@ -87,6 +97,7 @@ impl Transaction {
 		match *self {
 			Transaction::Unverified(ref tx) => &tx.gas,
 			Transaction::Retracted(ref tx) => &tx.gas,
+			Transaction::Restored(ref tx) => &tx.gas,
 			Transaction::Local(ref tx) => &tx.gas,
 		}
 	}
@@ -96,6 +107,7 @@ impl Transaction {
 		match *self {
 			Transaction::Unverified(ref tx) => &tx.gas_price,
 			Transaction::Retracted(ref tx) => &tx.gas_price,
+			Transaction::Restored(ref tx) => &tx.gas_price,
 			Transaction::Local(ref tx) => &tx.gas_price,
 		}
 	}
@@ -104,6 +116,7 @@ impl Transaction {
 		match *self {
 			Transaction::Unverified(ref tx) => &*tx,
 			Transaction::Retracted(ref tx) => &*tx,
+			Transaction::Restored(ref tx) => &*tx,
 			Transaction::Local(ref tx) => &*tx,
 		}
 	}
@@ -121,6 +134,13 @@ impl Transaction {
 			_ => false,
 		}
 	}
+
+	fn is_restored(&self) -> bool {
+		match *self {
+			Transaction::Restored(..) => true,
+			_ => false,
+		}
+	}
 }
 
 /// Transaction verifier.
@@ -212,8 +232,9 @@ impl<C: Client> txpool::Verifier<Transaction> for Verifier<C> {
 		// Some more heavy checks below.
 		// Actually recover sender and verify that transaction
 		let is_retracted = tx.is_retracted();
+		let is_restored = tx.is_restored();
 		let transaction = match tx {
-			Transaction::Retracted(tx) | Transaction::Unverified(tx) => match self.client.verify_transaction(tx) {
+			Transaction::Retracted(tx) | Transaction::Unverified(tx) | Transaction::Restored(tx) => match self.client.verify_transaction(tx) {
 				Ok(signed) => signed.into(),
 				Err(err) => {
 					debug!(target: "txqueue", "[{:?}] Rejected tx {:?}", hash, err);
@@ -279,16 +300,35 @@ impl<C: Client> txpool::Verifier<Transaction> for Verifier<C> {
 			bail!(transaction::Error::Old);
 		}
 
-		let priority = match (is_own || account_details.is_local, is_retracted) {
-			(true, _) => super::Priority::Local,
-			(false, false) => super::Priority::Regular,
-			(false, true) => super::Priority::Retracted,
+		if let Some(max_nonce_gap) = self.options.max_nonce_gap {
+			let gap = transaction.nonce - account_details.nonce;
+			if gap > max_nonce_gap && !(is_own || account_details.is_local) {
+				debug!(
+					target: "txqueue",
+					"[{:?}] Rejected tx with too large nonce gap ({} > {})",
+					hash,
+					gap,
+					max_nonce_gap,
+				);
+				bail!(transaction::Error::NonceGapTooLarge {
+					limit: max_nonce_gap,
+					got: gap,
+				});
+			}
+		}
+
+		let priority = match (is_own || account_details.is_local, account_details.is_priority, is_retracted) {
+			(true, _, _) => super::Priority::Local,
+			(false, true, _) => super::Priority::PrioritySender,
+			(false, false, false) => super::Priority::Regular,
+			(false, false, true) => super::Priority::Retracted,
 		};
 		Ok(VerifiedTransaction {
 			transaction,
 			priority,
 			hash,
 			sender,
+			restored: is_restored,
 			insertion_id: self.id.fetch_add(1, atomic::Ordering::AcqRel),
 		})
 	}
@@ -36,6 +36,9 @@ use futures::Future;
 pub trait NotifyWork : Send + Sync {
 	/// Fired when new mining job available
 	fn notify(&self, pow_hash: H256, difficulty: U256, number: u64);
+
+	/// Whether anyone is currently waiting for the notifications.
+	fn is_subscribed(&self) -> bool { true }
 }
 
 /// POSTs info about new work to given urls.
@@ -26,11 +26,13 @@ use ethereum_types::{U256, H256, Address};
 use bytes::ToPretty;
 use rlp::PayloadInfo;
 use ethcore::account_provider::AccountProvider;
-use ethcore::client::{Mode, DatabaseCompactionProfile, VMType, BlockImportError, Nonce, Balance, BlockChainClient, BlockId, BlockInfo, ImportBlock};
+use ethcore::client::{Mode, DatabaseCompactionProfile, DatabaseBackend, VMType, BlockImportError, Nonce, Balance, BlockChainClient, BlockId, BlockInfo, ImportBlock};
 use ethcore::error::{ImportErrorKind, BlockImportErrorKind};
 use ethcore::miner::Miner;
 use ethcore::verification::queue::VerifierSettings;
+use ethcore::pruning;
 use ethcore_service::ClientService;
+use journaldb::Algorithm;
 use cache::CacheConfig;
 use informant::{Informant, FullNodeInformantData, MillisecondDuration};
 use params::{SpecType, Pruning, Switch, tracing_switch_to_bool, fatdb_switch_to_bool};
@@ -67,6 +69,7 @@ impl FromStr for DataFormat {
 #[derive(Debug, PartialEq)]
 pub enum BlockchainCmd {
 	Kill(KillBlockchain),
+	ConvertPruning(ConvertPruning),
 	Import(ImportBlockchain),
 	Export(ExportBlockchain),
 	ExportState(ExportState),
@@ -79,6 +82,26 @@ pub struct KillBlockchain {
 	pub pruning: Pruning,
 }
 
+#[derive(Debug, PartialEq)]
+pub enum ConvertPruningKind {
+	/// Rewrite the database into another pruning method.
+	Convert(Algorithm),
+	/// Shrink an archive database down to the given number of recent states.
+	Shrink(u64),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ConvertPruning {
+	pub spec: SpecType,
+	pub cache_config: CacheConfig,
+	pub dirs: Directories,
+	pub pruning: Pruning,
+	pub compaction: DatabaseCompactionProfile,
+	pub db_backend: DatabaseBackend,
+	pub wal: bool,
+	pub kind: ConvertPruningKind,
+}
+
 #[derive(Debug, PartialEq)]
 pub struct ImportBlockchain {
 	pub spec: SpecType,
@@ -90,6 +113,7 @@ pub struct ImportBlockchain {
 	pub pruning_history: u64,
 	pub pruning_memory: usize,
 	pub compaction: DatabaseCompactionProfile,
+	pub db_backend: DatabaseBackend,
 	pub wal: bool,
 	pub tracing: Switch,
 	pub fat_db: Switch,
@@ -111,6 +135,7 @@ pub struct ExportBlockchain {
 	pub pruning_history: u64,
 	pub pruning_memory: usize,
 	pub compaction: DatabaseCompactionProfile,
+	pub db_backend: DatabaseBackend,
 	pub wal: bool,
 	pub fat_db: Switch,
 	pub tracing: Switch,
@@ -130,6 +155,7 @@ pub struct ExportState {
 	pub pruning_history: u64,
 	pub pruning_memory: usize,
 	pub compaction: DatabaseCompactionProfile,
+	pub db_backend: DatabaseBackend,
 	pub wal: bool,
 	pub fat_db: Switch,
 	pub tracing: Switch,
@@ -143,6 +169,7 @@ pub struct ExportState {
 pub fn execute(cmd: BlockchainCmd) -> Result<(), String> {
 	match cmd {
 		BlockchainCmd::Kill(kill_cmd) => kill_db(kill_cmd),
+		BlockchainCmd::ConvertPruning(convert_cmd) => convert_pruning(convert_cmd),
 		BlockchainCmd::Import(import_cmd) => {
 			if import_cmd.light {
 				execute_import_light(import_cmd)
@@ -208,7 +235,8 @@ fn execute_import_light(cmd: ImportBlockchain) -> Result<(), String> {
 	let db = db::open_db(&client_path.to_str().expect("DB path could not be converted to string."),
 						 &cmd.cache_config,
 						 &cmd.compaction,
-						 cmd.wal)?;
+						 cmd.wal,
+						 cmd.db_backend)?;
 
 	// TODO: could epoch signals be avilable at the end of the file?
 	let fetch = ::light::client::fetch::unavailable();
@@ -351,6 +379,7 @@ fn execute_import(cmd: ImportBlockchain) -> Result<(), String> {
 		tracing,
 		fat_db,
 		cmd.compaction,
+		cmd.db_backend,
 		cmd.wal,
 		cmd.vm_type,
 		"".into(),
@@ -493,6 +522,7 @@ fn start_client(
 	tracing: Switch,
 	fat_db: Switch,
 	compaction: DatabaseCompactionProfile,
+	db_backend: DatabaseBackend,
 	wal: bool,
 	cache_config: CacheConfig,
 	require_fat_db: bool,
@@ -543,6 +573,7 @@ fn start_client(
 		tracing,
 		fat_db,
 		compaction,
+		db_backend,
 		wal,
 		VMType::default(),
 		"".into(),
@@ -584,6 +615,7 @@ fn execute_export(cmd: ExportBlockchain) -> Result<(), String> {
 		cmd.tracing,
 		cmd.fat_db,
 		cmd.compaction,
+		cmd.db_backend,
 		cmd.wal,
 		cmd.cache_config,
 		false,
@@ -629,6 +661,7 @@ fn execute_export_state(cmd: ExportState) -> Result<(), String> {
 		cmd.tracing,
 		cmd.fat_db,
 		cmd.compaction,
+		cmd.db_backend,
 		cmd.wal,
 		cmd.cache_config,
 		true
@@ -721,6 +754,80 @@ pub fn kill_db(cmd: KillBlockchain) -> Result<(), String> {
 	Ok(())
 }
 
+pub fn convert_pruning(cmd: ConvertPruning) -> Result<(), String> {
+	let spec = cmd.spec.spec(&cmd.dirs.cache)?;
+	let genesis_hash = spec.genesis_header().hash();
+	let db_dirs = cmd.dirs.database(genesis_hash, None, spec.data_dir);
+	let user_defaults_path = db_dirs.user_defaults_path();
+	let mut user_defaults = UserDefaults::load(&user_defaults_path)?;
+	let algorithm = cmd.pruning.to_algorithm(&user_defaults);
+
+	let client_path = db_dirs.client_path(algorithm);
+	if !client_path.exists() {
+		return Err(format!("No {} database found at {}.", algorithm, client_path.display()));
+	}
+
+	// state preimages kept by fat db are not part of the trie and would not be copied.
+	if user_defaults.fat_db {
+		return Err("Databases synced with --fat-db on cannot be converted.".into());
+	}
+
+	execute_upgrades(&cmd.dirs.base, &db_dirs, algorithm, &cmd.compaction)?;
+
+	let open = |path: &::std::path::Path| db::open_db(
+		path.to_str().expect("DB path could not be converted to string."),
+		&cmd.cache_config,
+		&cmd.compaction,
+		cmd.wal,
+		cmd.db_backend,
+	);
+
+	let mut progress = |copied: usize| info!("Copied {} state nodes", copied);
+	let client_db = open(&client_path)?;
+
+	match cmd.kind {
+		ConvertPruningKind::Shrink(keep) => {
+			if algorithm != Algorithm::Archive {
+				return Err(format!("Only archive databases can be shrunk, this one uses {}.", algorithm));
+			}
+			if keep == 0 {
+				return Err("At least one state must be kept.".into());
+			}
+
+			let scratch_path = db_dirs.db_path(algorithm).join("shrink");
+			let deleted = {
+				let scratch = open(&scratch_path)?;
+				pruning::shrink_archive(client_db, scratch, keep, &mut progress)
+					.map_err(|e| format!("Error shrinking database: {}", e))?
+			};
+			fs::remove_dir_all(&scratch_path).map_err(|e| format!("Error removing temporary database: {:?}", e))?;
+
+			info!("Removed {} state nodes. Only the state of the last {} blocks is available.", deleted, keep);
+		},
+		ConvertPruningKind::Convert(target) => {
+			if target == algorithm {
+				return Err(format!("The database already uses {} pruning.", algorithm));
+			}
+
+			let target_path = db_dirs.client_path(target);
+			if target_path.exists() {
+				return Err(format!("A {} database already exists at {}. Remove it with `parity db kill --pruning={}` first.", target, target_path.display(), target));
+			}
+
+			execute_upgrades(&cmd.dirs.base, &db_dirs, target, &cmd.compaction)?;
+			let best = pruning::convert(client_db, algorithm, open(&target_path)?, target, &mut progress)
+				.map_err(|e| format!("Error converting database: {}", e))?;
+
+			user_defaults.pruning = target;
+			user_defaults.save(&user_defaults_path)?;
+
+			info!("Converted state at block #{} to {} pruning. The {} database at {} can now be removed.", best, target, algorithm, client_path.display());
+		},
+	}
+
+	Ok(())
+}
+
 #[cfg(test)]
 mod test {
 	use super::DataFormat;
@@ -230,6 +230,18 @@ usage! {
 			CMD cmd_db_kill {
 				"Clean the database",
 			}
+
+			CMD cmd_db_convert_pruning {
+				"Rewrite the database using another pruning method, without re-syncing",
+
+				ARG arg_db_convert_pruning_to: (Option<String>) = None,
+				"--to=[METHOD]",
+				"Pruning method to convert the database to. METHOD may be one of archive, fast or basic. The database selected by --pruning is left untouched.",
+
+				ARG arg_db_convert_pruning_keep: (Option<u64>) = None,
+				"--keep=[N]",
+				"Instead of converting, shrink an archive database in place down to the states of the last N blocks.",
+			}
 		}
 
 		CMD cmd_export_hardcoded_sync
@@ -312,6 +324,10 @@ usage! {
 			"--db-path=[PATH]",
 			"Specify the database directory path",
 
+			ARG arg_finality_confirmations: (Option<u64>) = None, or |c: &Config| c.parity.as_ref()?.finality_confirmations.clone(),
+			"--finality-confirmations=[NUM]",
+			"Consider blocks with NUM confirmations final when the consensus engine does not provide finality (e.g. proof-of-work).",
+
 		["Convenience options"]
 			FLAG flag_unsafe_expose: (bool) = false, or |c: &Config| c.misc.as_ref()?.unsafe_expose,
 			"--unsafe-expose",
@@ -493,7 +509,7 @@ usage! {
 
 			ARG arg_jsonrpc_apis: (String) = "web3,eth,pubsub,net,parity,private,parity_pubsub,traces,rpc,shh,shh_pubsub", or |c: &Config| c.rpc.as_ref()?.apis.as_ref().map(|vec| vec.join(",")),
 			"--jsonrpc-apis=[APIS]",
-			"Specify the APIs available through the JSONRPC interface using a comma-delimited list of API names. Possible names are: all, safe, web3, net, eth, pubsub, personal, signer, parity, parity_pubsub, parity_accounts, parity_set, traces, rpc, secretstore, shh, shh_pubsub. You can also disable a specific API by putting '-' in the front, example: all,-personal. safe contains following apis: web3, net, eth, pubsub, parity, parity_pubsub, traces, rpc, shh, shh_pubsub",
+			"Specify the APIs available through the JSONRPC interface using a comma-delimited list of API names. Possible names are: all, safe, web3, net, eth, pubsub, personal, signer, parity, parity_pubsub, parity_accounts, parity_set, traces, debug, rpc, secretstore, shh, shh_pubsub. You can also disable a specific API by putting '-' in the front, example: all,-personal. safe contains following apis: web3, net, eth, pubsub, parity, parity_pubsub, traces, rpc, shh, shh_pubsub",
 
 			ARG arg_jsonrpc_hosts: (String) = "none", or |c: &Config| c.rpc.as_ref()?.hosts.as_ref().map(|vec| vec.join(",")),
 			"--jsonrpc-hosts=[HOSTS]",
@@ -526,7 +542,7 @@ usage! {
 
 			ARG arg_ws_apis: (String) = "web3,eth,pubsub,net,parity,parity_pubsub,private,traces,rpc,shh,shh_pubsub", or |c: &Config| c.websockets.as_ref()?.apis.as_ref().map(|vec| vec.join(",")),
 			"--ws-apis=[APIS]",
-			"Specify the APIs available through the WebSockets interface using a comma-delimited list of API names. Possible names are: all, safe, web3, net, eth, pubsub, personal, signer, parity, parity_pubsub, parity_accounts, parity_set, traces, rpc, secretstore, shh, shh_pubsub. You can also disable a specific API by putting '-' in the front, example: all,-personal. safe contains following apis: web3, net, eth, pubsub, parity, parity_pubsub, traces, rpc, shh, shh_pubsub",
+			"Specify the APIs available through the WebSockets interface using a comma-delimited list of API names. Possible names are: all, safe, web3, net, eth, pubsub, personal, signer, parity, parity_pubsub, parity_accounts, parity_set, traces, debug, rpc, secretstore, shh, shh_pubsub. You can also disable a specific API by putting '-' in the front, example: all,-personal. safe contains following apis: web3, net, eth, pubsub, parity, parity_pubsub, traces, rpc, shh, shh_pubsub",
 
 			ARG arg_ws_origins: (String) = "parity://*,chrome-extension://*,moz-extension://*", or |c: &Config| c.websockets.as_ref()?.origins.as_ref().map(|vec| vec.join(",")),
 			"--ws-origins=[URL]",
@@ -551,7 +567,7 @@ usage! {
 
 			ARG arg_ipc_apis: (String) = "web3,eth,pubsub,net,parity,parity_pubsub,parity_accounts,private,traces,rpc,shh,shh_pubsub", or |c: &Config| c.ipc.as_ref()?.apis.as_ref().map(|vec| vec.join(",")),
 			"--ipc-apis=[APIS]",
-			"Specify custom API set available via JSON-RPC over IPC using a comma-delimited list of API names. Possible names are: all, safe, web3, net, eth, pubsub, personal, signer, parity, parity_pubsub, parity_accounts, parity_set, traces, rpc, secretstore, shh, shh_pubsub. You can also disable a specific API by putting '-' in the front, example: all,-personal. safe contains: web3, net, eth, pubsub, parity, parity_pubsub, traces, rpc, shh, shh_pubsub",
+			"Specify custom API set available via JSON-RPC over IPC using a comma-delimited list of API names. Possible names are: all, safe, web3, net, eth, pubsub, personal, signer, parity, parity_pubsub, parity_accounts, parity_set, traces, debug, rpc, secretstore, shh, shh_pubsub. You can also disable a specific API by putting '-' in the front, example: all,-personal. safe contains: web3, net, eth, pubsub, parity, parity_pubsub, traces, rpc, shh, shh_pubsub",
 
 		["API and console options – Dapps"]
 			FLAG flag_no_dapps: (bool) = false, or |c: &Config| c.dapps.as_ref()?.disable.clone(),
@@ -673,6 +689,10 @@ usage! {
 			"--infinite-pending-block",
 			"Pending block will be created with maximal possible gas limit and will execute all transactions in the queue. Note that such block is invalid and should never be attempted to be mined.",
 
+			FLAG flag_tx_queue_priority_contract: (bool) = false, or |c: &Config| c.mining.as_ref()?.tx_queue_priority_contract.clone(),
+			"--tx-queue-priority-contract",
+			"Treat senders certified by the priority_sender_checker registry contract as priority senders.",
+
 			FLAG flag_no_persistent_txqueue: (bool) = false, or |c: &Config| c.parity.as_ref()?.no_persistent_txqueue,
 			"--no-persistent-txqueue",
 			"Don't save pending local transactions to disk to be restored whenever the node restarts.",
@@ -739,7 +759,31 @@ usage! {
 
 			ARG arg_tx_queue_strategy: (String) = "gas_price", or |c: &Config| c.mining.as_ref()?.tx_queue_strategy.clone(),
 			"--tx-queue-strategy=[S]",
-			"Prioritization strategy used to order transactions in the queue. S may be: gas_price - Prioritize txs with high gas price",
+			"Prioritization strategy used to order transactions in the queue. S may be: gas_price - Prioritize txs with high gas price; gas_factor - Prioritize txs with high gas price, penalising txs with very large gas limits; fee_per_byte - Prioritize txs paying a high gas price per byte of transaction data.",
+
+			ARG arg_tx_queue_persist_count: (usize) = 0usize, or |c: &Config| c.mining.as_ref()?.tx_queue_persist_count.clone(),
+			"--tx-queue-persist-count=[LIMIT]",
+			"Maximum number of non-local transactions in the queue, including future ones, to dump to the database on shutdown and restore on startup. Setting this parameter to 0 disables the dump.",
+
+			ARG arg_tx_queue_persist_age: (u64) = 3600u64, or |c: &Config| c.mining.as_ref()?.tx_queue_persist_age.clone(),
+			"--tx-queue-persist-age=[S]",
+			"Maximum age in seconds of a dumped transaction queue to be restored on startup.",
+
+			ARG arg_tx_queue_replace_bump: (Option<usize>) = None, or |c: &Config| c.mining.as_ref()?.tx_queue_replace_bump.clone(),
+			"--tx-queue-replace-bump=[PERCENT]",
+			"Minimal increase of the score (by default the gas price), in percent, required to replace a transaction with the same sender and nonce in the queue. By default it is 12.5%.",
+
+			ARG arg_tx_queue_max_nonce_gap: (Option<u64>) = None, or |c: &Config| c.mining.as_ref()?.tx_queue_max_nonce_gap.clone(),
+			"--tx-queue-max-nonce-gap=[GAP]",
+			"Maximum distance between the nonce of an external transaction and the current nonce of its sender. Transactions further ahead are rejected. By default there is no limit.",
+
+			ARG arg_tx_queue_priority_senders: (Option<String>) = None, or |c: &Config| c.mining.as_ref()?.tx_queue_priority_senders.as_ref().map(|vec| vec.join(",")),
+			"--tx-queue-priority-senders=[ACCOUNTS]",
+			"Comma-delimited list of priority senders. Their transactions are included first, get a reserved share of block gas and are never evicted from a full queue in favour of regular transactions.",
+
+			ARG arg_tx_queue_priority_gas: (usize) = 0usize, or |c: &Config| c.mining.as_ref()?.tx_queue_priority_gas.clone(),
+			"--tx-queue-priority-gas=[PERCENT]",
+			"Percentage of block gas limit reserved for transactions of priority senders. Other transactions never use the reserved gas.",
 
 			ARG arg_stratum_interface: (String) = "local", or |c: &Config| c.stratum.as_ref()?.interface.clone(),
 			"--stratum-interface=[IP]",
@@ -753,6 +797,14 @@ usage! {
 			"--min-gas-price=[STRING]",
 			"Minimum amount of Wei per GAS to be paid for a transaction to be accepted for mining. Overrides --usd-per-tx.",
 
+			ARG arg_min_gas_price_percentile: (Option<usize>) = None, or |c: &Config| c.mining.as_ref()?.min_gas_price_percentile.clone(),
+			"--min-gas-price-percentile=[PCT]",
+			"Learn the minimum amount of Wei per GAS to be paid for a transaction to be accepted for mining from the PCT percentile of gas prices of transactions included in recent blocks. Overrides --usd-per-tx.",
+
+			ARG arg_min_gas_price_blocks: (usize) = 100usize, or |c: &Config| c.mining.as_ref()?.min_gas_price_blocks.clone(),
+			"--min-gas-price-blocks=[N]",
+			"Number of recent blocks to learn the minimum gas price from when --min-gas-price-percentile is set.",
+
 			ARG arg_gas_price_percentile: (usize) = 50usize, or |c: &Config| c.mining.as_ref()?.gas_price_percentile,
 			"--gas-price-percentile=[PCT]",
 			"Set PCT percentile gas price value from last 100 blocks as default gas price when sending transactions.",
@@ -785,6 +837,14 @@ usage! {
 			"--stratum-secret=[STRING]",
 			"Secret for authorizing Stratum server for peers.",
 
+			ARG arg_stratum_extranonce_size: (usize) = 0usize, or |c: &Config| c.stratum.as_ref()?.extranonce_size.clone(),
+			"--stratum-extranonce-size=[BYTES]",
+			"Size of the extranonce allocated to each Stratum connection, at most 8 bytes. 0 disables extranonce allocation.",
+
+			ARG arg_stratum_share_time: (Option<u64>) = None, or |c: &Config| c.stratum.as_ref()?.share_time.clone(),
+			"--stratum-share-time=[SECONDS]",
+			"Enable variable share difficulty for Stratum workers, targeting one share every SECONDS.",
+
 		["Internal Options"]
 			FLAG flag_can_restart: (bool) = false, or |_| None,
 			"--can-restart",
@@ -860,6 +920,10 @@ usage! {
 			"--db-compaction=[TYPE]",
 			"Database compaction type. TYPE may be one of: ssd - suitable for SSDs and fast HDDs; hdd - suitable for slow HDDs; auto - determine automatically.",
 
+			ARG arg_db_backend: (String) = "rocksdb", or |c: &Config| c.footprint.as_ref()?.db_backend.clone(),
+			"--db-backend=[TYPE]",
+			"Key-value store used for the client database. TYPE may be one of: rocksdb; lmdb - lower and more predictable latency, no background compaction. A database can only be opened with the backend it was created with.",
+
 			ARG arg_fat_db: (String) = "auto", or |c: &Config| c.footprint.as_ref()?.fat_db.clone(),
 			"--fat-db=[BOOL]",
 			"Build appropriate information to allow enumeration of all accounts and storage keys. Doubles the size of the state database. BOOL may be one of on, off or auto.",
@@ -1077,6 +1141,7 @@ struct Operating {
 	light: Option<bool>,
 	no_persistent_txqueue: Option<bool>,
 	no_hardcoded_sync: Option<bool>,
+	finality_confirmations: Option<u64>,
 }
 
 #[derive(Default, Debug, PartialEq, Deserialize)]
@@ -1227,6 +1292,8 @@ struct Mining {
 	tx_time_limit: Option<u64>,
 	relay_set: Option<String>,
 	min_gas_price: Option<u64>,
+	min_gas_price_percentile: Option<usize>,
+	min_gas_price_blocks: Option<usize>,
 	gas_price_percentile: Option<usize>,
 	usd_per_tx: Option<String>,
 	usd_per_eth: Option<String>,
@@ -1239,6 +1306,13 @@ struct Mining {
 	tx_queue_mem_limit: Option<u32>,
 	tx_queue_gas: Option<String>,
 	tx_queue_strategy: Option<String>,
+	tx_queue_persist_count: Option<usize>,
+	tx_queue_persist_age: Option<u64>,
+	tx_queue_replace_bump: Option<usize>,
+	tx_queue_max_nonce_gap: Option<u64>,
+	tx_queue_priority_senders: Option<Vec<String>>,
+	tx_queue_priority_contract: Option<bool>,
+	tx_queue_priority_gas: Option<usize>,
 	tx_queue_ban_count: Option<u16>,
 	tx_queue_ban_time: Option<u16>,
 	remove_solved: Option<bool>,
@@ -1253,6 +1327,8 @@ struct Stratum {
 	interface: Option<String>,
 	port: Option<u16>,
 	secret: Option<String>,
+	extranonce_size: Option<usize>,
+	share_time: Option<u64>,
 }
 
 #[derive(Default, Debug, PartialEq, Deserialize)]
@@ -1269,6 +1345,7 @@ struct Footprint {
 	cache_size_queue: Option<u32>,
 	cache_size_state: Option<u32>,
 	db_compaction: Option<String>,
+	db_backend: Option<String>,
 	fat_db: Option<String>,
 	scale_verifiers: Option<bool>,
 	num_verifiers: Option<usize>,
@@ -1493,6 +1570,7 @@ mod tests {
 			cmd_tools_hash: false,
 			cmd_db: false,
 			cmd_db_kill: false,
+			cmd_db_convert_pruning: false,
 			cmd_export_hardcoded_sync: false,
 
 			// Arguments
@@ -1506,6 +1584,8 @@ mod tests {
 			arg_snapshot_file: None,
 			arg_restore_file: None,
 			arg_tools_hash_file: None,
+			arg_db_convert_pruning_to: None,
+			arg_db_convert_pruning_keep: None,
 
 			arg_signer_sign_id: None,
 			arg_signer_reject_id: None,
@@ -1527,6 +1607,7 @@ mod tests {
 			arg_chain: "xyz".into(),
 			arg_base_path: Some("$HOME/.parity".into()),
 			arg_db_path: Some("$HOME/.parity/chains".into()),
+			arg_finality_confirmations: None,
 			arg_keys_path: "$HOME/.parity/keys".into(),
 			arg_identity: "".into(),
 			flag_light: false,
@@ -1650,6 +1731,8 @@ mod tests {
 			arg_tx_time_limit: Some(100u64),
 			arg_relay_set: "cheap".into(),
 			arg_min_gas_price: Some(0u64),
+			arg_min_gas_price_percentile: None,
+			arg_min_gas_price_blocks: 100usize,
 			arg_usd_per_tx: "0.0001".into(),
 			arg_gas_price_percentile: 50usize,
 			arg_usd_per_eth: "auto".into(),
@@ -1662,17 +1745,26 @@ mod tests {
 			arg_tx_queue_mem_limit: 4u32,
 			arg_tx_queue_gas: "off".into(),
 			arg_tx_queue_strategy: "gas_factor".into(),
+			arg_tx_queue_persist_count: 0usize,
+			arg_tx_queue_persist_age: 3600u64,
+			arg_tx_queue_replace_bump: Some(12usize),
+			arg_tx_queue_max_nonce_gap: Some(1024u64),
+			arg_tx_queue_priority_senders: None,
+			arg_tx_queue_priority_gas: 0usize,
 			arg_tx_queue_ban_count: 1u16,
 			arg_tx_queue_ban_time: 180u16,
 			flag_remove_solved: false,
 			arg_notify_work: Some("http://localhost:3001".into()),
 			flag_refuse_service_transactions: false,
 			flag_infinite_pending_block: false,
+			flag_tx_queue_priority_contract: false,
 
 			flag_stratum: false,
 			arg_stratum_interface: "local".to_owned(),
 			arg_stratum_port: 8008u16,
 			arg_stratum_secret: None,
+			arg_stratum_extranonce_size: 0usize,
+			arg_stratum_share_time: None,
 
 			// -- Footprint Options
 			arg_tracing: "auto".into(),
@@ -1686,6 +1778,7 @@ mod tests {
 			arg_cache_size: Some(128),
 			flag_fast_and_loose: false,
 			arg_db_compaction: "ssd".into(),
+			arg_db_backend: "rocksdb".into(),
 			arg_fat_db: "auto".into(),
 			flag_scale_verifiers: true,
 			arg_num_verifiers: Some(6),
@@ -1802,6 +1895,7 @@ mod tests {
 				light: None,
 				no_hardcoded_sync: None,
 				no_persistent_txqueue: None,
+				finality_confirmations: None,
 			}),
 			account: Some(Account {
 				unlock: Some(vec!["0x1".into(), "0x2".into(), "0x3".into()]),
@@ -1909,6 +2003,8 @@ mod tests {
 				work_queue_size: None,
 				relay_set: None,
 				min_gas_price: None,
+				min_gas_price_percentile: None,
+				min_gas_price_blocks: None,
 				gas_price_percentile: None,
 				usd_per_tx: None,
 				usd_per_eth: None,
@@ -1920,6 +2016,13 @@ mod tests {
 				tx_queue_mem_limit: None,
 				tx_queue_gas: Some("off".into()),
 				tx_queue_strategy: None,
+				tx_queue_persist_count: None,
+				tx_queue_persist_age: None,
+				tx_queue_replace_bump: None,
+				tx_queue_max_nonce_gap: None,
+				tx_queue_priority_senders: None,
+				tx_queue_priority_contract: None,
+				tx_queue_priority_gas: None,
 				tx_queue_ban_count: None,
 				tx_queue_ban_time: None,
 				tx_gas_limit: None,
@@ -1942,6 +2045,7 @@ mod tests {
 				cache_size_queue: Some(100),
 				cache_size_state: Some(25),
 				db_compaction: Some("ssd".into()),
+				db_backend: None,
 				fat_db: Some("off".into()),
 				scale_verifiers: Some(false),
 				num_verifiers: None,
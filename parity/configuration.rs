@@ -49,7 +49,8 @@ use ethcore_private_tx::{ProviderConfig, EncryptorConfig};
 use secretstore::{NodeSecretKey, Configuration as SecretStoreConfiguration, ContractAddress as SecretStoreContractAddress};
 use updater::{UpdatePolicy, UpdateFilter, ReleaseTrack};
 use run::RunCmd;
-use blockchain::{BlockchainCmd, ImportBlockchain, ExportBlockchain, KillBlockchain, ExportState, DataFormat};
+use local_store::PoolLimits;
+use blockchain::{BlockchainCmd, ImportBlockchain, ExportBlockchain, KillBlockchain, ConvertPruning, ConvertPruningKind, ExportState, DataFormat};
 use export_hardcoded_sync::ExportHsyncCmd;
 use presale::ImportWallet;
 use account::{AccountCmd, NewAccount, ListAccounts, ImportAccounts, ImportFromGethAccounts};
@@ -137,6 +138,7 @@ impl Configuration {
 		let tracing = self.args.arg_tracing.parse()?;
 		let fat_db = self.args.arg_fat_db.parse()?;
 		let compaction = self.args.arg_db_compaction.parse()?;
+		let db_backend = self.args.arg_db_backend.parse()?;
 		let wal = !self.args.flag_fast_and_loose;
 		let public_node = self.args.flag_public_node;
 		let warp_sync = !self.args.flag_no_warp;
@@ -185,6 +187,22 @@ impl Configuration {
 				dirs: dirs,
 				pruning: pruning,
 			}))
+		} else if self.args.cmd_db && self.args.cmd_db_convert_pruning {
+			let kind = match (self.args.arg_db_convert_pruning_keep, self.args.arg_db_convert_pruning_to.as_ref()) {
+				(Some(keep), None) => ConvertPruningKind::Shrink(keep),
+				(None, Some(to)) => ConvertPruningKind::Convert(to.parse()?),
+				_ => return Err("Exactly one of --to or --keep must be given.".into()),
+			};
+			Cmd::Blockchain(BlockchainCmd::ConvertPruning(ConvertPruning {
+				spec: spec,
+				cache_config: cache_config,
+				dirs: dirs,
+				pruning: pruning,
+				compaction: compaction,
+				db_backend: db_backend,
+				wal: wal,
+				kind: kind,
+			}))
 		} else if self.args.cmd_account {
 			let account_cmd = if self.args.cmd_account_new {
 				let new_acc = NewAccount {
@@ -240,6 +258,7 @@ impl Configuration {
 				pruning_history: pruning_history,
 				pruning_memory: self.args.arg_pruning_memory,
 				compaction: compaction,
+				db_backend: db_backend,
 				wal: wal,
 				tracing: tracing,
 				fat_db: fat_db,
@@ -262,6 +281,7 @@ impl Configuration {
 					pruning_history: pruning_history,
 					pruning_memory: self.args.arg_pruning_memory,
 					compaction: compaction,
+					db_backend: db_backend,
 					wal: wal,
 					tracing: tracing,
 					fat_db: fat_db,
@@ -281,6 +301,7 @@ impl Configuration {
 					pruning_history: pruning_history,
 					pruning_memory: self.args.arg_pruning_memory,
 					compaction: compaction,
+					db_backend: db_backend,
 					wal: wal,
 					tracing: tracing,
 					fat_db: fat_db,
@@ -305,6 +326,7 @@ impl Configuration {
 				tracing: tracing,
 				fat_db: fat_db,
 				compaction: compaction,
+				db_backend: db_backend,
 				file_path: self.args.arg_snapshot_file.clone(),
 				wal: wal,
 				kind: snapshot::Kind::Take,
@@ -322,6 +344,7 @@ impl Configuration {
 				tracing: tracing,
 				fat_db: fat_db,
 				compaction: compaction,
+				db_backend: db_backend,
 				file_path: self.args.arg_restore_file.clone(),
 				wal: wal,
 				kind: snapshot::Kind::Restore,
@@ -335,6 +358,7 @@ impl Configuration {
 				spec: spec,
 				pruning: pruning,
 				compaction: compaction,
+				db_backend: db_backend,
 				wal: wal,
 			};
 			Cmd::ExportHardcodedSync(export_hs_cmd)
@@ -375,6 +399,7 @@ impl Configuration {
 				tracing: tracing,
 				fat_db: fat_db,
 				compaction: compaction,
+				db_backend: db_backend,
 				wal: wal,
 				vm_type: vm_type,
 				warp_sync: warp_sync,
@@ -400,8 +425,13 @@ impl Configuration {
 				serve_light: !self.args.flag_no_serve_light,
 				light: self.args.flag_light,
 				no_persistent_txqueue: self.args.flag_no_persistent_txqueue,
+				tx_queue_persist: PoolLimits {
+					max_count: self.args.arg_tx_queue_persist_count,
+					max_age: Duration::from_secs(self.args.arg_tx_queue_persist_age),
+				},
 				whisper: whisper_config,
 				no_hardcoded_sync: self.args.flag_no_hardcoded_sync,
+				finality_confirmations: self.args.arg_finality_confirmations,
 			};
 			Cmd::Run(run_cmd)
 		};
@@ -524,11 +554,22 @@ impl Configuration {
 
 	fn stratum_options(&self) -> Result<Option<stratum::Options>, String> {
 		if self.args.flag_stratum {
+			if self.args.arg_stratum_extranonce_size > 8 {
+				return Err("Stratum extranonce can't be longer than 8 bytes".into());
+			}
+
 			Ok(Some(stratum::Options {
 				io_path: self.directories().db,
 				listen_addr: self.stratum_interface(),
 				port: self.args.arg_ports_shift + self.args.arg_stratum_port,
 				secret: self.args.arg_stratum_secret.as_ref().map(|s| s.parse::<H256>().unwrap_or_else(|_| keccak(s))),
+				pool: stratum::PoolOptions {
+					extranonce_size: self.args.arg_stratum_extranonce_size,
+					vardiff: self.args.arg_stratum_share_time.map(|secs| stratum::VardiffOptions {
+						target_share_time: Duration::from_secs(secs),
+						..Default::default()
+					}),
+				},
 			}))
 		} else { Ok(None) }
 	}
@@ -541,6 +582,10 @@ impl Configuration {
 
 		let reseal = self.args.arg_reseal_on_txs.parse::<ResealPolicy>()?;
 
+		if self.args.arg_tx_queue_priority_gas > 100 {
+			return Err("--tx-queue-priority-gas must be a percentage between 0 and 100".into());
+		}
+
 		let options = MinerOptions {
 			force_sealing: self.args.flag_force_sealing,
 			reseal_on_external_tx: reseal.external,
@@ -557,6 +602,9 @@ impl Configuration {
 			tx_queue_penalization: to_queue_penalization(self.args.arg_tx_time_limit)?,
 			tx_queue_strategy: to_queue_strategy(&self.args.arg_tx_queue_strategy)?,
 			refuse_service_transactions: self.args.flag_refuse_service_transactions,
+			priority_senders: to_addresses(&self.args.arg_tx_queue_priority_senders)?.into_iter().collect(),
+			priority_senders_contract: self.args.flag_tx_queue_priority_contract,
+			priority_gas_percent: self.args.arg_tx_queue_priority_gas,
 
 			pool_limits: self.pool_limits()?,
 			pool_verification_options: self.pool_verification_options()?,
@@ -576,6 +624,7 @@ impl Configuration {
 			} else {
 				usize::max_value()
 			},
+			replace_bump_percent: self.args.arg_tx_queue_replace_bump,
 		})
 	}
 
@@ -588,6 +637,7 @@ impl Configuration {
 				Some(ref d) => to_u256(d)?,
 				None => U256::max_value(),
 			},
+			max_nonce_gap: self.args.arg_tx_queue_max_nonce_gap.map(Into::into),
 		})
 	}
 
@@ -707,6 +757,14 @@ impl Configuration {
 			return Ok(GasPricerConfig::Fixed(to_u256(dec)?));
 		} else if let Some(dec) = self.args.arg_min_gas_price {
 			return Ok(GasPricerConfig::Fixed(U256::from(dec)));
+		} else if let Some(percentile) = self.args.arg_min_gas_price_percentile {
+			if percentile > 100 {
+				return Err(format!("Invalid percentile: {}. Must be at most 100.", percentile));
+			}
+			return Ok(GasPricerConfig::Percentile {
+				percentile: percentile,
+				blocks: self.args.arg_min_gas_price_blocks,
+			});
 		} else if self.chain()? != SpecType::Foundation {
 			return Ok(GasPricerConfig::Fixed(U256::zero()));
 		}
@@ -1250,13 +1308,14 @@ mod tests {
 
 	use tempdir::TempDir;
 	use ethcore::client::{VMType, BlockId};
+	use journaldb::Algorithm;
 	use ethcore::miner::MinerOptions;
 	use miner::pool::PrioritizationStrategy;
 	use parity_rpc::NetworkSettings;
 	use updater::{UpdatePolicy, UpdateFilter, ReleaseTrack};
 
 	use account::{AccountCmd, NewAccount, ImportAccounts, ListAccounts};
-	use blockchain::{BlockchainCmd, ImportBlockchain, ExportBlockchain, DataFormat, ExportState};
+	use blockchain::{BlockchainCmd, ImportBlockchain, ExportBlockchain, ConvertPruning, ConvertPruningKind, DataFormat, ExportState};
 	use cli::Args;
 	use dir::{Directories, default_hypervisor_path};
 	use helpers::{default_network_config};
@@ -1264,6 +1323,7 @@ mod tests {
 	use presale::ImportWallet;
 	use rpc::{WsConfiguration, UiConfiguration};
 	use run::RunCmd;
+	use local_store::PoolLimits;
 
 	use network::{AllowIP, IpFilter};
 
@@ -1350,6 +1410,7 @@ mod tests {
 			pruning_history: 64,
 			pruning_memory: 32,
 			compaction: Default::default(),
+			db_backend: Default::default(),
 			wal: true,
 			tracing: Default::default(),
 			fat_db: Default::default(),
@@ -1375,6 +1436,7 @@ mod tests {
 			pruning_memory: 32,
 			format: Default::default(),
 			compaction: Default::default(),
+			db_backend: Default::default(),
 			wal: true,
 			tracing: Default::default(),
 			fat_db: Default::default(),
@@ -1384,6 +1446,32 @@ mod tests {
 		})));
 	}
 
+	#[test]
+	fn test_command_db_convert_pruning() {
+		let args = vec!["parity", "db", "convert-pruning", "--to", "archive"];
+		let conf = parse(&args);
+		assert_eq!(conf.into_command().unwrap().cmd, Cmd::Blockchain(BlockchainCmd::ConvertPruning(ConvertPruning {
+			spec: Default::default(),
+			cache_config: Default::default(),
+			dirs: Default::default(),
+			pruning: Default::default(),
+			compaction: Default::default(),
+			db_backend: Default::default(),
+			wal: true,
+			kind: ConvertPruningKind::Convert(Algorithm::Archive),
+		})));
+
+		let args = vec!["parity", "db", "convert-pruning", "--keep", "1000"];
+		let conf = parse(&args);
+		match conf.into_command().unwrap().cmd {
+			Cmd::Blockchain(BlockchainCmd::ConvertPruning(cmd)) => assert_eq!(cmd.kind, ConvertPruningKind::Shrink(1000)),
+			_ => panic!("Should be a convert-pruning command"),
+		}
+
+		let args = vec!["parity", "db", "convert-pruning"];
+		assert!(parse(&args).into_command().is_err());
+	}
+
 	#[test]
 	fn test_command_state_export() {
 		let args = vec!["parity", "export", "state", "state.json"];
@@ -1398,6 +1486,7 @@ mod tests {
 			pruning_memory: 32,
 			format: Default::default(),
 			compaction: Default::default(),
+			db_backend: Default::default(),
 			wal: true,
 			tracing: Default::default(),
 			fat_db: Default::default(),
@@ -1423,6 +1512,7 @@ mod tests {
 			pruning_memory: 32,
 			format: Some(DataFormat::Hex),
 			compaction: Default::default(),
+			db_backend: Default::default(),
 			wal: true,
 			tracing: Default::default(),
 			fat_db: Default::default(),
@@ -1518,6 +1608,7 @@ mod tests {
 			mode: Default::default(),
 			tracing: Default::default(),
 			compaction: Default::default(),
+			db_backend: Default::default(),
 			wal: true,
 			vm_type: Default::default(),
 			geth_compatibility: false,
@@ -1543,7 +1634,12 @@ mod tests {
 			light: false,
 			no_hardcoded_sync: false,
 			no_persistent_txqueue: false,
+			tx_queue_persist: PoolLimits {
+				max_count: 0,
+				max_age: Duration::from_secs(3600),
+			},
 			whisper: Default::default(),
+			finality_confirmations: None,
 		};
 		expected.secretstore_conf.enabled = cfg!(feature = "secretstore");
 		expected.secretstore_conf.http_enabled = cfg!(feature = "secretstore");
@@ -1572,6 +1668,23 @@ mod tests {
 		assert!(conf.miner_options().is_err());
 	}
 
+	#[test]
+	fn should_parse_priority_senders() {
+		// given
+		let sender = "0xdeadbeefcafe0000000000000000000000000000";
+
+		// when
+		let conf0 = parse(&["parity", "--tx-queue-priority-senders", sender, "--tx-queue-priority-gas", "25"]);
+		let conf1 = parse(&["parity", "--tx-queue-priority-gas", "101"]);
+
+		// then
+		let options = conf0.miner_options().unwrap();
+		assert_eq!(options.priority_senders.into_iter().collect::<Vec<_>>(), vec![sender[2..].parse::<Address>().unwrap()]);
+		assert_eq!(options.priority_gas_percent, 25);
+		assert!(!options.priority_senders_contract);
+		assert!(conf1.miner_options().is_err());
+	}
+
 	#[test]
 	fn should_parse_updater_options() {
 		// when
@@ -1880,6 +1993,20 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn should_parse_min_gas_price_percentile() {
+		let args = vec!["parity", "--min-gas-price-percentile", "30", "--min-gas-price-blocks", "50"];
+		let conf = Configuration::parse_cli(&args).unwrap();
+		assert_eq!(conf.gas_pricer_config(), Ok(GasPricerConfig::Percentile {
+			percentile: 30,
+			blocks: 50,
+		}));
+
+		let args = vec!["parity", "--min-gas-price-percentile", "101"];
+		let conf = Configuration::parse_cli(&args).unwrap();
+		assert!(conf.gas_pricer_config().is_err());
+	}
+
 	#[test]
 	fn test_mining_preset() {
 		let args = vec!["parity", "--config", "mining"];
@@ -1970,6 +2097,27 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn should_parse_stratum_pool_options() {
+		// given
+
+		// when
+		let conf0 = parse(&["parity", "--stratum"]);
+		let conf1 = parse(&["parity", "--stratum", "--stratum-extranonce-size", "2", "--stratum-share-time", "15"]);
+		let conf2 = parse(&["parity", "--stratum", "--stratum-extranonce-size", "9"]);
+
+		// then
+		assert_eq!(conf0.stratum_options().unwrap().unwrap().pool, Default::default());
+		assert_eq!(conf1.stratum_options().unwrap().unwrap().pool, stratum::PoolOptions {
+			extranonce_size: 2,
+			vardiff: Some(stratum::VardiffOptions {
+				target_share_time: Duration::from_secs(15),
+				..Default::default()
+			}),
+		});
+		assert!(conf2.stratum_options().is_err());
+	}
+
 	#[test]
 	fn should_apply_ports_shift() {
 		// given
@@ -0,0 +1,61 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+extern crate kvdb_lmdb;
+
+use std::sync::Arc;
+use std::path::Path;
+use ethcore::db::NUM_COLUMNS;
+use ethcore::client::ClientConfig;
+use kvdb::{KeyValueDB, KeyValueDBHandler};
+use self::kvdb_lmdb::{Database, DatabaseConfig};
+
+/// Open a new client DB.
+pub fn open_client_db(client_path: &Path, _client_config: &ClientConfig) -> Result<Arc<KeyValueDB>, String> {
+	let client_db = Arc::new(Database::open(
+		&DatabaseConfig::with_columns(NUM_COLUMNS),
+		&client_path.to_str().expect("DB path could not be converted to string.")
+	).map_err(|e| format!("Client service database error: {:?}", e))?);
+
+	Ok(client_db)
+}
+
+/// Create a restoration db handler.
+pub fn restoration_db_handler(_client_path: &Path, _client_config: &ClientConfig) -> Box<KeyValueDBHandler> {
+	use kvdb::Error;
+
+	struct RestorationDBHandler {
+		config: DatabaseConfig,
+	}
+
+	impl KeyValueDBHandler for RestorationDBHandler {
+		fn open(&self, db_path: &Path) -> Result<Arc<KeyValueDB>, Error> {
+			Ok(Arc::new(Database::open(&self.config, &db_path.to_string_lossy())?))
+		}
+	}
+
+	Box::new(RestorationDBHandler {
+		config: DatabaseConfig::with_columns(NUM_COLUMNS),
+	})
+}
+
+/// Open a new main DB.
+pub fn open_db(client_path: &str) -> Result<Arc<KeyValueDB>, String> {
+	Ok(Arc::new(Database::open(
+		&DatabaseConfig::with_columns(NUM_COLUMNS),
+		client_path
+	).map_err(|e| format!("Failed to open database: {}", e))?))
+}
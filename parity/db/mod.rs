@@ -16,10 +16,65 @@
 
 //! Database-related operations.
 
+use std::sync::Arc;
+use std::path::Path;
+use ethcore::client::{ClientConfig, DatabaseBackend, DatabaseCompactionProfile};
+use kvdb::{KeyValueDB, KeyValueDBHandler};
+
+use cache::CacheConfig;
+
 #[path="rocksdb/mod.rs"]
 mod impls;
 
-pub use self::impls::{open_db, open_client_db, restoration_db_handler, migrate};
+#[path="lmdb/mod.rs"]
+mod lmdb;
+
+pub use self::impls::migrate;
 
 #[cfg(feature = "secretstore")]
 pub use self::impls::open_secretstore_db;
+
+/// Make sure an existing database at `path` was created by `backend`.
+fn check_backend(path: &Path, backend: DatabaseBackend) -> Result<(), String> {
+	let found = if path.join("CURRENT").exists() {
+		Some(DatabaseBackend::RocksDB)
+	} else if path.join("data.mdb").exists() {
+		Some(DatabaseBackend::Lmdb)
+	} else {
+		None
+	};
+
+	match found {
+		Some(found) if found != backend => Err(format!(
+			"Database at {} was created with the {:?} backend, but --db-backend selects {:?}.",
+			path.display(), found, backend,
+		)),
+		_ => Ok(()),
+	}
+}
+
+/// Open a new client DB.
+pub fn open_client_db(client_path: &Path, client_config: &ClientConfig) -> Result<Arc<KeyValueDB>, String> {
+	check_backend(client_path, client_config.db_backend)?;
+	match client_config.db_backend {
+		DatabaseBackend::RocksDB => impls::open_client_db(client_path, client_config),
+		DatabaseBackend::Lmdb => lmdb::open_client_db(client_path, client_config),
+	}
+}
+
+/// Create a restoration db handler using the config generated by `client_path` and `client_config`.
+pub fn restoration_db_handler(client_path: &Path, client_config: &ClientConfig) -> Box<KeyValueDBHandler> {
+	match client_config.db_backend {
+		DatabaseBackend::RocksDB => impls::restoration_db_handler(client_path, client_config),
+		DatabaseBackend::Lmdb => lmdb::restoration_db_handler(client_path, client_config),
+	}
+}
+
+/// Open a new main DB.
+pub fn open_db(client_path: &str, cache_config: &CacheConfig, compaction: &DatabaseCompactionProfile, wal: bool, backend: DatabaseBackend) -> Result<Arc<KeyValueDB>, String> {
+	check_backend(Path::new(client_path), backend)?;
+	match backend {
+		DatabaseBackend::RocksDB => impls::open_db(client_path, cache_config, compaction, wal),
+		DatabaseBackend::Lmdb => lmdb::open_db(client_path),
+	}
+}
@@ -17,7 +17,7 @@
 use std::sync::Arc;
 use std::time::Duration;
 
-use ethcore::client::DatabaseCompactionProfile;
+use ethcore::client::{DatabaseCompactionProfile, DatabaseBackend};
 use ethcore::spec::{SpecParams, OptimizeFor};
 use light::client::fetch::Unavailable as UnavailableDataFetcher;
 use light::Cache as LightDataCache;
@@ -40,6 +40,7 @@ pub struct ExportHsyncCmd {
 	pub spec: SpecType,
 	pub pruning: Pruning,
 	pub compaction: DatabaseCompactionProfile,
+	pub db_backend: DatabaseBackend,
 	pub wal: bool,
 }
 
@@ -90,7 +91,8 @@ pub fn execute(cmd: ExportHsyncCmd) -> Result<String, String> {
 	let db = db::open_db(&db_dirs.client_path(algorithm).to_str().expect("DB path could not be converted to string."),
 						 &cmd.cache_config,
 						 &cmd.compaction,
-						 cmd.wal)?;
+						 cmd.wal,
+						 cmd.db_backend)?;
 
 	let service = light_client::Service::start(config, &spec, UnavailableDataFetcher, db, cache)
 		.map_err(|e| format!("Error starting light client: {}", e))?;
@@ -20,7 +20,7 @@ use std::time::Duration;
 use std::fs::File;
 use ethereum_types::{U256, clean_0x, Address};
 use journaldb::Algorithm;
-use ethcore::client::{Mode, BlockId, VMType, DatabaseCompactionProfile, ClientConfig, VerifierType};
+use ethcore::client::{Mode, BlockId, VMType, DatabaseCompactionProfile, DatabaseBackend, ClientConfig, VerifierType};
 use ethcore::miner::{PendingSet, Penalization};
 use miner::pool::PrioritizationStrategy;
 use cache::CacheConfig;
@@ -99,6 +99,8 @@ pub fn to_pending_set(s: &str) -> Result<PendingSet, String> {
 pub fn to_queue_strategy(s: &str) -> Result<PrioritizationStrategy, String> {
 	match s {
 		"gas_price" => Ok(PrioritizationStrategy::GasPriceOnly),
+		"gas_factor" => Ok(PrioritizationStrategy::GasFactorAndGasPrice),
+		"fee_per_byte" => Ok(PrioritizationStrategy::FeePerByte),
 		other => Err(format!("Invalid queue strategy: {}", other)),
 	}
 }
@@ -209,6 +211,7 @@ pub fn to_client_config(
 		tracing: bool,
 		fat_db: bool,
 		compaction: DatabaseCompactionProfile,
+		db_backend: DatabaseBackend,
 		wal: bool,
 		vm_type: VMType,
 		name: String,
@@ -245,6 +248,7 @@ pub fn to_client_config(
 	client_config.pruning = pruning;
 	client_config.history = pruning_history;
 	client_config.db_compaction = compaction;
+	client_config.db_backend = db_backend;
 	client_config.db_wal = wal;
 	client_config.vm_type = vm_type;
 	client_config.name = name;
@@ -330,7 +334,8 @@ mod tests {
 	use ethereum_types::U256;
 	use ethcore::client::{Mode, BlockId};
 	use ethcore::miner::PendingSet;
-	use super::{to_duration, to_mode, to_block_id, to_u256, to_pending_set, to_address, to_addresses, to_price, geth_ipc_path, to_bootnodes, password_from_file};
+	use miner::pool::PrioritizationStrategy;
+	use super::{to_duration, to_mode, to_block_id, to_u256, to_pending_set, to_queue_strategy, to_address, to_addresses, to_price, geth_ipc_path, to_bootnodes, password_from_file};
 
 	#[test]
 	fn test_to_duration() {
@@ -388,6 +393,14 @@ mod tests {
 		assert!(to_pending_set("othe").is_err());
 	}
 
+	#[test]
+	fn test_queue_strategy() {
+		assert_eq!(to_queue_strategy("gas_price").unwrap(), PrioritizationStrategy::GasPriceOnly);
+		assert_eq!(to_queue_strategy("gas_factor").unwrap(), PrioritizationStrategy::GasFactorAndGasPrice);
+		assert_eq!(to_queue_strategy("fee_per_byte").unwrap(), PrioritizationStrategy::FeePerByte);
+		assert!(to_queue_strategy("gas").is_err());
+	}
+
 	#[test]
 	fn test_to_address() {
 		assert_eq!(
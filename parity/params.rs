@@ -24,7 +24,7 @@ use ethereum_types::{U256, Address};
 use futures_cpupool::CpuPool;
 use hash_fetch::fetch::Client as FetchClient;
 use journaldb::Algorithm;
-use miner::gas_pricer::{GasPricer, GasPriceCalibratorOptions};
+use miner::gas_pricer::{GasPricer, GasPriceCalibratorOptions, GasPricePercentileOptions};
 use parity_version::version_data;
 use user_defaults::UserDefaults;
 
@@ -226,7 +226,11 @@ pub enum GasPricerConfig {
 	Calibrated {
 		usd_per_tx: f32,
 		recalibration_period: Duration,
-	}
+	},
+	Percentile {
+		percentile: usize,
+		blocks: usize,
+	},
 }
 
 impl Default for GasPricerConfig {
@@ -251,7 +255,13 @@ impl GasPricerConfig {
 					fetch,
 					p,
 				)
-			}
+			},
+			GasPricerConfig::Percentile { percentile, blocks } => {
+				GasPricer::new_percentile(GasPricePercentileOptions {
+					percentile: percentile,
+					blocks: blocks,
+				})
+			},
 		}
 	}
 }
@@ -68,6 +68,8 @@ pub enum Api {
 	ParitySet,
 	/// Traces (Safe)
 	Traces,
+	/// Geth-compatible "debug" API (Safe, but expensive)
+	Debug,
 	/// Rpc (Safe)
 	Rpc,
 	/// SecretStore (UNSAFE: arbitrary hash signing)
@@ -100,6 +102,7 @@ impl FromStr for Api {
 			"parity_accounts" => Ok(ParityAccounts),
 			"parity_set" => Ok(ParitySet),
 			"traces" => Ok(Traces),
+			"debug" => Ok(Debug),
 			"rpc" => Ok(Rpc),
 			"secretstore" => Ok(SecretStore),
 			"private" => Ok(Private),
@@ -186,6 +189,7 @@ fn to_modules(apis: &HashSet<Api>) -> BTreeMap<String, String> {
 			Api::ParityPubSub => ("parity_pubsub", "1.0"),
 			Api::ParitySet => ("parity_set", "1.0"),
 			Api::Traces => ("traces", "1.0"),
+			Api::Debug => ("debug", "1.0"),
 			Api::Rpc => ("rpc", "1.0"),
 			Api::SecretStore => ("secretstore", "1.0"),
 			Api::Private => ("private", "1.0"),
@@ -312,6 +316,7 @@ impl FullDependencies {
 						if let Some(h) = client.handler().upgrade() {
 							self.client.add_notify(h);
 						}
+						self.miner.add_work_subscriber(client.handler() as Weak<_>);
 						handler.extend_with(client.to_delegate());
 					}
 				},
@@ -350,7 +355,13 @@ impl FullDependencies {
 						let mut rpc = MetaIoHandler::default();
 						let apis = ApiSet::List(apis.clone()).retain(ApiSet::PubSub).list_apis();
 						self.extend_api(&mut rpc, &apis, true);
-						handler.extend_with(PubSubClient::new(rpc, self.remote.clone()).to_delegate());
+						let mut client = PubSubClient::new(rpc, self.remote.clone());
+						self.client.add_notify(client.state_diffs_handler(self.client.clone()));
+						let h = client.transaction_pool_events_handler();
+						self.miner.add_transaction_events_listener(Box::new(move |events| if let Some(h) = h.upgrade() {
+							h.notify(events);
+						}));
+						handler.extend_with(client.to_delegate());
 					}
 				},
 				Api::ParityAccounts => {
@@ -370,6 +381,9 @@ impl FullDependencies {
 				Api::Traces => {
 					handler.extend_with(TracesClient::new(&self.client).to_delegate())
 				},
+				Api::Debug => {
+					handler.extend_with(DebugClient::new(&self.client).to_delegate())
+				},
 				Api::Rpc => {
 					let modules = to_modules(&apis);
 					handler.extend_with(RpcClient::new(modules).to_delegate());
@@ -583,7 +597,10 @@ impl<C: LightChainClient + 'static> LightDependencies<C> {
 					).to_delegate())
 				},
 				Api::Traces => {
-					handler.extend_with(light::TracesClient.to_delegate())
+					handler.extend_with(light::TracesClient::new(Arc::new(dispatcher.clone()), self.gas_price_percentile).to_delegate())
+				},
+				Api::Debug => {
+					handler.extend_with(light::DebugClient.to_delegate())
 				},
 				Api::Rpc => {
 					let modules = to_modules(&apis);
@@ -674,6 +691,7 @@ impl ApiSet {
 			},
 			ApiSet::All => {
 				public_list.insert(Api::Traces);
+				public_list.insert(Api::Debug);
 				public_list.insert(Api::ParityPubSub);
 				public_list.insert(Api::ParityAccounts);
 				public_list.insert(Api::ParitySet);
@@ -709,6 +727,7 @@ mod test {
 		assert_eq!(Api::ParityAccounts, "parity_accounts".parse().unwrap());
 		assert_eq!(Api::ParitySet, "parity_set".parse().unwrap());
 		assert_eq!(Api::Traces, "traces".parse().unwrap());
+		assert_eq!(Api::Debug, "debug".parse().unwrap());
 		assert_eq!(Api::Rpc, "rpc".parse().unwrap());
 		assert_eq!(Api::SecretStore, "secretstore".parse().unwrap());
 		assert_eq!(Api::Private, "private".parse().unwrap());
@@ -763,7 +782,7 @@ mod test {
 	#[test]
 	fn test_all_apis() {
 		assert_eq!("all".parse::<ApiSet>().unwrap(), ApiSet::List(vec![
-			Api::Web3, Api::Net, Api::Eth, Api::EthPubSub, Api::Parity, Api::ParityPubSub, Api::Traces, Api::Rpc, Api::SecretStore, Api::Whisper, Api::WhisperPubSub,
+			Api::Web3, Api::Net, Api::Eth, Api::EthPubSub, Api::Parity, Api::ParityPubSub, Api::Traces, Api::Debug, Api::Rpc, Api::SecretStore, Api::Whisper, Api::WhisperPubSub,
 			Api::ParityAccounts,
 			Api::ParitySet, Api::Signer,
 			Api::Personal,
@@ -774,7 +793,7 @@ mod test {
 	#[test]
 	fn test_all_without_personal_apis() {
 		assert_eq!("personal,all,-personal".parse::<ApiSet>().unwrap(), ApiSet::List(vec![
-			Api::Web3, Api::Net, Api::Eth, Api::EthPubSub, Api::Parity, Api::ParityPubSub, Api::Traces, Api::Rpc, Api::SecretStore, Api::Whisper, Api::WhisperPubSub,
+			Api::Web3, Api::Net, Api::Eth, Api::EthPubSub, Api::Parity, Api::ParityPubSub, Api::Traces, Api::Debug, Api::Rpc, Api::SecretStore, Api::Whisper, Api::WhisperPubSub,
 			Api::ParityAccounts,
 			Api::ParitySet, Api::Signer,
 			Api::Private
@@ -22,7 +22,7 @@ use std::thread;
 
 use ansi_term::Colour;
 use ethcore::account_provider::{AccountProvider, AccountProviderSettings};
-use ethcore::client::{Client, Mode, DatabaseCompactionProfile, VMType, BlockChainClient, BlockInfo};
+use ethcore::client::{Client, Mode, DatabaseCompactionProfile, DatabaseBackend, VMType, BlockChainClient, BlockInfo};
 use ethcore::ethstore::ethkey;
 use ethcore::miner::{stratum, Miner, MinerService, MinerOptions};
 use ethcore::snapshot;
@@ -106,6 +106,7 @@ pub struct RunCmd {
 	pub tracing: Switch,
 	pub fat_db: Switch,
 	pub compaction: DatabaseCompactionProfile,
+	pub db_backend: DatabaseBackend,
 	pub wal: bool,
 	pub vm_type: VMType,
 	pub geth_compatibility: bool,
@@ -129,8 +130,10 @@ pub struct RunCmd {
 	pub serve_light: bool,
 	pub light: bool,
 	pub no_persistent_txqueue: bool,
+	pub tx_queue_persist: ::local_store::PoolLimits,
 	pub whisper: ::whisper::Config,
 	pub no_hardcoded_sync: bool,
+	pub finality_confirmations: Option<u64>,
 }
 
 // node info fetcher for the local store.
@@ -153,6 +156,20 @@ impl ::local_store::NodeInfo for FullNodeInfo {
 			})
 			.collect()
 	}
+
+	fn queued_transactions(&self) -> Vec<::transaction::PendingTransaction> {
+		let miner = match self.miner.as_ref() {
+			Some(m) => m,
+			None => return Vec::new(),
+		};
+
+		let local = miner.local_transactions();
+		miner.queued_transactions()
+			.into_iter()
+			.filter(|tx| !local.contains_key(&tx.signed().hash()))
+			.map(|tx| tx.pending().clone())
+			.collect()
+	}
 }
 
 type LightClient = ::light::client::Client<::light_helpers::EpochFetch>;
@@ -221,7 +238,8 @@ fn execute_light_impl(cmd: RunCmd, logger: Arc<RotatingLogger>) -> Result<Runnin
 	let db = db::open_db(&db_dirs.client_path(algorithm).to_str().expect("DB path could not be converted to string."),
 						 &cmd.cache_config,
 						 &cmd.compaction,
-						 cmd.wal)?;
+						 cmd.wal,
+						 cmd.db_backend)?;
 
 	let service = light_client::Service::start(config, &spec, fetch, db, cache.clone())
 		.map_err(|e| format!("Error starting light client: {}", e))?;
@@ -565,6 +583,7 @@ fn execute_impl<Cr, Rr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq:
 		tracing,
 		fat_db,
 		cmd.compaction,
+		cmd.db_backend,
 		cmd.wal,
 		cmd.vm_type,
 		cmd.name,
@@ -575,6 +594,7 @@ fn execute_impl<Cr, Rr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq:
 	);
 
 	client_config.queue.verifier_settings = cmd.verifier_settings;
+	client_config.finality_confirmations = cmd.finality_confirmations;
 
 	// set up bootnodes
 	let mut net_conf = cmd.net_conf;
@@ -608,7 +628,8 @@ fn execute_impl<Cr, Rr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq:
 
 	// take handle to client
 	let client = service.client();
-	// Update miners block gas limit
+	// Update miners block gas limit and minimal gas price
+	miner.init_gas_price_samples(&*client);
 	miner.update_transaction_queue_limits(*client.best_block_header().gas_limit());
 
 	// take handle to private transactions service
@@ -627,7 +648,7 @@ fn execute_impl<Cr, Rr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq:
 			}
 		};
 
-		let store = ::local_store::create(db, ::ethcore::db::COL_NODE_INFO, node_info);
+		let mut store = ::local_store::create(db, ::ethcore::db::COL_NODE_INFO, node_info);
 
 		if cmd.no_persistent_txqueue {
 			info!("Running without a persistent transaction queue.");
@@ -635,6 +656,8 @@ fn execute_impl<Cr, Rr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq:
 			if let Err(e) = store.clear() {
 				warn!("Error clearing persistent transaction queue: {}", e);
 			}
+		} else {
+			store.set_pool_limits(cmd.tx_queue_persist);
 		}
 
 		// re-queue pending transactions.
@@ -649,6 +672,19 @@ fn execute_impl<Cr, Rr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq:
 			Err(e) => warn!("Error loading cached pending transactions from disk: {}", e),
 		}
 
+		// re-queue the rest of the pool dumped on shutdown.
+		match store.pool_transactions() {
+			Ok(transactions) => if !transactions.is_empty() {
+				let count = transactions.len();
+				let imported = miner.import_restored_transactions(&*client, transactions)
+					.into_iter()
+					.filter(Result::is_ok)
+					.count();
+				info!("Restored {} out of {} transactions from the dumped transaction pool.", imported, count);
+			},
+			Err(e) => warn!("Error loading dumped transaction pool from disk: {}", e),
+		}
+
 		Arc::new(store)
 	};
 
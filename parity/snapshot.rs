@@ -25,7 +25,7 @@ use ethcore::account_provider::AccountProvider;
 use ethcore::snapshot::{Progress, RestorationStatus, SnapshotService as SS};
 use ethcore::snapshot::io::{SnapshotReader, PackedReader, PackedWriter};
 use ethcore::snapshot::service::Service as SnapshotService;
-use ethcore::client::{Mode, DatabaseCompactionProfile, VMType};
+use ethcore::client::{Mode, DatabaseCompactionProfile, DatabaseBackend, VMType};
 use ethcore::miner::Miner;
 use ethcore::ids::BlockId;
 use ethcore_service::ClientService;
@@ -59,6 +59,7 @@ pub struct SnapshotCommand {
 	pub tracing: Switch,
 	pub fat_db: Switch,
 	pub compaction: DatabaseCompactionProfile,
+	pub db_backend: DatabaseBackend,
 	pub file_path: Option<String>,
 	pub wal: bool,
 	pub kind: Kind,
@@ -173,6 +174,7 @@ impl SnapshotCommand {
 			tracing,
 			fat_db,
 			self.compaction,
+			self.db_backend,
 			self.wal,
 			VMType::default(),
 			"".into(),
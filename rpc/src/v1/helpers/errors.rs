@@ -319,6 +319,9 @@ pub fn transaction_message(error: &TransactionError) -> String {
 		LimitReached => {
 			"There are too many transactions in the queue. Your transaction was dropped due to limit. Try increasing the fee.".into()
 		},
+		NonceGapTooLarge { limit, got } => {
+			format!("Transaction nonce is too far ahead of the account nonce (maximal gap: {}, got: {}). Try sending transactions with lower nonces first.", limit, got)
+		},
 		InsufficientGas { minimal, got } => {
 			format!("Transaction gas is too low. There is not enough gas to cover minimal cost of the transaction (minimal: {}, got: {}). Try increasing supplied gas.", minimal, got)
 		},
@@ -19,6 +19,7 @@
 use std::sync::Arc;
 
 use ethcore::basic_account::BasicAccount;
+use ethcore::client::CallAnalytics;
 use ethcore::encoded;
 use ethcore::executed::{Executed, ExecutionError};
 use ethcore::ids::BlockId;
@@ -102,6 +103,17 @@ fn extract_header(res: &[OnDemandResponse], header: HeaderRef) -> Option<encoded
 	}
 }
 
+// the request for a storage slot of the given account, proved against its storage root.
+// accounts which don't exist have no storage to request.
+fn storage_request(header: encoded::Header, address: Address, key: H256, account: Option<BasicAccount>) -> Option<request::Storage> {
+	account.map(|account| request::Storage {
+		header: header.into(),
+		address: address,
+		key: key,
+		storage_root: Field::Scalar(account.storage_root),
+	})
+}
+
 impl LightFetch {
 	// push the necessary requests onto the request chain to get the header by the given ID.
 	// yield a header reference which other requests can use.
@@ -189,8 +201,40 @@ impl LightFetch {
 		})
 	}
 
+	/// Helper for getting a storage slot of an account at a given block.
+	/// Slots of accounts which don't exist at the given block are zero.
+	pub fn storage_at(&self, address: Address, key: H256, id: BlockId) -> BoxFuture<H256> {
+		let mut reqs = Vec::new();
+		let header_ref = match self.make_header_requests(id, &mut reqs) {
+			Ok(r) => r,
+			Err(e) => return Box::new(future::err(e)),
+		};
+
+		reqs.push(request::Account { header: header_ref.clone(), address: address }.into());
+
+		let fetcher = self.clone();
+		let account = self.send_requests(reqs, |res| {
+			let header = extract_header(&res, header_ref)
+				.expect("these responses correspond to requests that header_ref belongs to; qed");
+			match res.last() {
+				Some(&OnDemandResponse::Account(ref acc)) => (header, acc.clone()),
+				_ => panic!("responses correspond directly with requests in amount and type; qed"),
+			}
+		});
+
+		Box::new(account.and_then(move |(header, account)| {
+			match storage_request(header, address, key, account) {
+				Some(req) => Either::A(fetcher.send_requests(vec![req.into()], |mut res| match res.pop() {
+					Some(OnDemandResponse::Storage(value)) => value,
+					_ => panic!("responses correspond directly with requests in amount and type; qed"),
+				})),
+				None => Either::B(future::ok(H256::new())),
+			}
+		}))
+	}
+
 	/// Helper for getting proved execution.
-	pub fn proved_execution(&self, req: CallRequest, num: Trailing<BlockNumber>) -> BoxFuture<ExecutionResult> {
+	pub fn proved_execution(&self, req: CallRequest, num: Trailing<BlockNumber>, analytics: CallAnalytics) -> BoxFuture<ExecutionResult> {
 		const DEFAULT_GAS_PRICE: u64 = 21_000;
 		// starting gas when gas not provided.
 		const START_GAS: u64 = 50_000;
@@ -275,6 +319,82 @@ impl LightFetch {
 				engine: client.engine().clone(),
 				on_demand: on_demand,
 				sync: sync,
+				analytics: analytics,
+			}))
+		}))
+	}
+
+	/// Re-execute the transaction with the given hash against the proved state
+	/// of its block's parent, in the environment of its block.
+	///
+	/// Light clients don't have access to the intermediate states between the
+	/// transactions of a block, so the transactions preceding the replayed one are
+	/// re-executed first. Peers prove each transaction against the parent state,
+	/// so replays touching state only reached after the preceding transactions
+	/// fail with a bad proof.
+	pub fn replay_transaction(&self, tx_hash: H256, analytics: CallAnalytics) -> BoxFuture<ExecutionResult> {
+		let client = self.client.clone();
+		let fetcher: Self = self.clone();
+		let eip86_transition = self.client.eip86_transition();
+
+		Box::new(self.transaction_by_hash(tx_hash, eip86_transition).and_then(move |found| {
+			let (block_hash, index) = match found {
+				Some((ref tx, index)) => (tx.block_hash.clone().expect("transactions fetched by hash are always mined; qed").into(), index),
+				None => return Either::A(future::err(errors::invalid_params("Unknown transaction", tx_hash))),
+			};
+
+			Either::B(fetcher.block(BlockId::Hash(block_hash)).and_then(move |block| {
+				let parent_hash = block.header_view().parent_hash();
+				let transactions = block.transactions().into_iter()
+					.take(index + 1)
+					.map(|tx| SignedTransaction::new(tx).map_err(errors::transaction))
+					.collect::<Result<Vec<_>>>();
+
+				fetcher.header(BlockId::Hash(parent_hash)).join(future::done(transactions)).and_then(move |(hdr, mut transactions)| {
+					if transactions.len() != index + 1 {
+						return Either::A(future::err(errors::invalid_params("Unknown transaction", tx_hash)));
+					}
+
+					let env_info = match client.env_info(BlockId::Hash(block_hash)) {
+						Some(env_info) => env_info,
+						_ => return Either::A(future::err(errors::unknown_block())),
+					};
+
+					let tx = transactions.pop().expect("length checked above; qed");
+
+					// peers prove execution in the environment of the parent block, so have
+					// them prove the account of this block's author, credited by every transaction.
+					let author_probe = EthTransaction {
+						nonce: 0.into(),
+						gas_price: 0.into(),
+						gas: 21_000.into(),
+						action: Action::Call(env_info.author),
+						value: 0.into(),
+						data: Vec::new(),
+					}.fake_sign(env_info.author);
+
+					let reqs: Vec<OnDemandRequest> = transactions.iter()
+						.chain(Some(&tx))
+						.chain(Some(&author_probe))
+						.map(|tx| request::ExecutionProof { tx: tx.clone(), header: hdr.clone().into() }.into())
+						.collect();
+
+					let engine = client.engine().clone();
+					Either::B(fetcher.send_requests(reqs, |res| res.into_iter().flat_map(|res| match res {
+						OnDemandResponse::ExecutionProof(items) => items,
+						_ => panic!("responses correspond directly with requests in amount and type; qed"),
+					}).collect::<Vec<_>>()).and_then(move |items| {
+						let proof = request::TransactionProof {
+							tx: tx,
+							header: hdr.into(),
+							env_info: env_info,
+							engine: engine,
+							analytics: analytics,
+						};
+
+						proof.check_with_preceding(&items, &transactions).map_err(errors::execution)
+					}))
+				})
 			}))
 		}))
 	}
@@ -453,6 +573,7 @@ struct ExecuteParams {
 	engine: Arc<::ethcore::engines::EthEngine>,
 	on_demand: Arc<OnDemand>,
 	sync: Arc<LightSync>,
+	analytics: CallAnalytics,
 }
 
 // has a peer execute the transaction with given params. If `gas_known` is false,
@@ -490,6 +611,7 @@ fn execute_tx(gas_known: bool, params: ExecuteParams) -> BoxFuture<ExecutionResu
 			header: params.hdr.into(),
 			env_info: params.env_info,
 			engine: params.engine,
+			analytics: params.analytics,
 		};
 
 		let on_demand = params.on_demand;
@@ -506,3 +628,41 @@ fn execute_tx(gas_known: bool, params: ExecuteParams) -> BoxFuture<ExecutionResu
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use ethcore::basic_account::BasicAccount;
+	use ethcore::encoded;
+	use ethcore::header::Header;
+	use ethereum_types::Address;
+	use hash::{H256, KECCAK_EMPTY};
+	use light::on_demand::request;
+	use light::request::Field;
+	use super::storage_request;
+
+	fn header() -> encoded::Header {
+		encoded::Header::new(::rlp::encode(&Header::default()).into_vec())
+	}
+
+	#[test]
+	fn should_not_request_storage_of_absent_account() {
+		assert_eq!(storage_request(header(), Address::from(1), H256::from(2), None), None);
+	}
+
+	#[test]
+	fn should_request_storage_against_account_storage_root() {
+		let account = BasicAccount {
+			nonce: 0.into(),
+			balance: 0.into(),
+			storage_root: H256::from(3),
+			code_hash: KECCAK_EMPTY,
+		};
+
+		assert_eq!(storage_request(header(), Address::from(1), H256::from(2), Some(account)), Some(request::Storage {
+			header: header().into(),
+			address: Address::from(1),
+			key: H256::from(2),
+			storage_root: Field::Scalar(H256::from(3)),
+		}));
+	}
+}
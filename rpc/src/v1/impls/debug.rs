@@ -0,0 +1,88 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Debug api implementation.
+
+use std::sync::Arc;
+
+use ethcore::client::{BlockChainClient, TransactionId, StateClient, StateInfo, Call, BlockId};
+
+use jsonrpc_core::Result;
+use jsonrpc_macros::Trailing;
+use v1::Metadata;
+use v1::traits::Debug;
+use v1::helpers::{errors, fake_sign};
+use v1::types::{BlockNumber, CallRequest, DebugTraceOptions, DebugTraceResult, H256};
+
+fn check_options(options: &DebugTraceOptions) -> Result<()> {
+	match options.tracer {
+		Some(_) => Err(errors::unsupported("Custom tracers are not supported. Omit `tracer` to get struct logs.", None)),
+		None => Ok(()),
+	}
+}
+
+/// Debug api implementation.
+pub struct DebugClient<C> {
+	client: Arc<C>,
+}
+
+impl<C> DebugClient<C> {
+	/// Creates new Debug client.
+	pub fn new(client: &Arc<C>) -> Self {
+		DebugClient {
+			client: client.clone(),
+		}
+	}
+}
+
+impl<C, S> Debug for DebugClient<C> where
+	S: StateInfo + 'static,
+	C: BlockChainClient + StateClient<State=S> + Call<State=S> + 'static
+{
+	type Metadata = Metadata;
+
+	fn trace_transaction(&self, transaction_hash: H256, options: Trailing<DebugTraceOptions>) -> Result<DebugTraceResult> {
+		let options = options.unwrap_or_default();
+		check_options(&options)?;
+
+		self.client.replay_with_struct_logs(TransactionId::Hash(transaction_hash.into()), options.into())
+			.map(DebugTraceResult::from)
+			.map_err(errors::call)
+	}
+
+	fn trace_call(&self, meta: Self::Metadata, request: CallRequest, block: BlockNumber, options: Trailing<DebugTraceOptions>) -> Result<DebugTraceResult> {
+		let options = options.unwrap_or_default();
+		check_options(&options)?;
+
+		let request = CallRequest::into(request);
+		let signed = fake_sign::sign_call(request, meta.is_dapp())?;
+
+		let id = match block {
+			BlockNumber::Num(num) => BlockId::Number(num),
+			BlockNumber::Earliest => BlockId::Earliest,
+			BlockNumber::Latest => BlockId::Latest,
+
+			BlockNumber::Pending => return Err(errors::invalid_params("`BlockNumber::Pending` is not supported", ())),
+		};
+
+		let mut state = self.client.state_at(id).ok_or(errors::state_pruned())?;
+		let header = self.client.block_header(id).ok_or(errors::state_pruned())?;
+
+		self.client.call_with_struct_logs(&signed, options.into(), &mut state, &header.decode().map_err(errors::decode)?)
+			.map(DebugTraceResult::from)
+			.map_err(errors::call)
+	}
+}
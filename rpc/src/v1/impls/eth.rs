@@ -25,8 +25,9 @@ use ethereum_types::{U256, H64, H160, H256, Address};
 use parking_lot::Mutex;
 
 use ethash::SeedHashCompute;
+use hash::keccak;
 use ethcore::account_provider::{AccountProvider, DappId};
-use ethcore::client::{BlockChainClient, BlockId, TransactionId, UncleId, StateOrBlock, StateClient, StateInfo, Call, EngineInfo};
+use ethcore::client::{BlockChainClient, BlockId, TransactionId, UncleId, StateOrBlock, StateClient, StateInfo, Call, EngineInfo, ProvingBlockChainClient};
 use ethcore::ethereum::Ethash;
 use ethcore::filter::Filter as EthcoreFilter;
 use ethcore::header::{BlockNumber as EthBlockNumber};
@@ -49,7 +50,7 @@ use v1::helpers::accounts::unwrap_provider;
 use v1::traits::Eth;
 use v1::types::{
 	RichBlock, Block, BlockTransactions, BlockNumber, Bytes, SyncStatus, SyncInfo,
-	Transaction, CallRequest, Index, Filter, Log, Receipt, Work,
+	Transaction, CallRequest, Index, Filter, Log, Receipt, Work, AccountProof, StorageProof,
 	H64 as RpcH64, H256 as RpcH256, H160 as RpcH160, U256 as RpcU256, block_number_to_id,
 };
 use v1::metadata::Metadata;
@@ -460,7 +461,7 @@ fn check_known<C>(client: &C, number: BlockNumber) -> Result<()> where C: BlockC
 const MAX_QUEUE_SIZE_TO_MINE_ON: usize = 4;	// because uncles go back 6.
 
 impl<C, SN: ?Sized, S: ?Sized, M, EM, T: StateInfo + 'static> Eth for EthClient<C, SN, S, M, EM> where
-	C: miner::BlockChainClient + BlockChainClient + StateClient<State=T> + Call<State=T> + EngineInfo + 'static,
+	C: miner::BlockChainClient + BlockChainClient + ProvingBlockChainClient + StateClient<State=T> + Call<State=T> + EngineInfo + 'static,
 	SN: SnapshotService + 'static,
 	S: SyncProvider + 'static,
 	M: MinerService<State=T> + 'static,
@@ -568,6 +569,48 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T: StateInfo + 'static> Eth for EthClient<
 		Box::new(future::done(res))
 	}
 
+	fn proof(&self, address: RpcH160, keys: Vec<RpcH256>, num: Trailing<BlockNumber>) -> BoxFuture<AccountProof> {
+		let address_hash = keccak(&Address::from(address.clone()));
+
+		let num = num.unwrap_or_default();
+		try_bf!(check_known(&*self.client, num.clone()));
+
+		// proofs are only available for states committed to the database.
+		let id = match num {
+			BlockNumber::Num(n) => BlockId::Number(n),
+			BlockNumber::Earliest => BlockId::Earliest,
+			BlockNumber::Latest | BlockNumber::Pending => BlockId::Latest,
+		};
+
+		let (account_proof, account) = match self.client.prove_account(address_hash, id) {
+			Some(res) => res,
+			None => return Box::new(future::err(errors::state_pruned())),
+		};
+
+		let mut storage_proof = Vec::with_capacity(keys.len());
+		for key in keys {
+			let key: H256 = key.into();
+			match self.client.prove_storage(address_hash, keccak(&key), id) {
+				Some((proof, value)) => storage_proof.push(StorageProof {
+					key: U256::from(&*key).into(),
+					value: U256::from(&*value).into(),
+					proof: proof.into_iter().map(Bytes::new).collect(),
+				}),
+				None => return Box::new(future::err(errors::state_pruned())),
+			}
+		}
+
+		Box::new(future::ok(AccountProof {
+			address: address,
+			balance: account.balance.into(),
+			nonce: account.nonce.into(),
+			code_hash: account.code_hash.into(),
+			storage_hash: account.storage_root.into(),
+			account_proof: account_proof.into_iter().map(Bytes::new).collect(),
+			storage_proof: storage_proof,
+		}))
+	}
+
 	fn transaction_count(&self, address: RpcH160, num: Trailing<BlockNumber>) -> BoxFuture<RpcU256> {
 		let address: Address = RpcH160::into(address);
 
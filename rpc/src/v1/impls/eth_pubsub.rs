@@ -30,17 +30,20 @@ use v1::helpers::{errors, limit_logs, Subscribers};
 use v1::helpers::light_fetch::LightFetch;
 use v1::metadata::Metadata;
 use v1::traits::EthPubSub;
-use v1::types::{pubsub, RichHeader, Log};
+use v1::types::{pubsub, RichHeader, Log, Work};
 
+use ethash::SeedHashCompute;
 use ethcore::encoded;
+use ethcore::ethereum::Ethash;
 use ethcore::filter::Filter as EthFilter;
 use ethcore::client::{BlockChainClient, ChainNotify, ChainRoute, ChainRouteType, BlockId};
+use miner::work_notify::NotifyWork;
 use sync::LightSync;
 use light::cache::Cache;
 use light::on_demand::OnDemand;
 use light::client::{LightChainClient, LightChainNotify};
 use parity_reactor::Remote;
-use ethereum_types::H256;
+use ethereum_types::{H256, U256};
 use bytes::Bytes;
 use parking_lot::{RwLock, Mutex};
 
@@ -52,6 +55,8 @@ pub struct EthPubSubClient<C> {
 	heads_subscribers: Arc<RwLock<Subscribers<Client>>>,
 	logs_subscribers: Arc<RwLock<Subscribers<(Client, EthFilter)>>>,
 	transactions_subscribers: Arc<RwLock<Subscribers<Client>>>,
+	work_subscribers: Arc<RwLock<Subscribers<Client>>>,
+	finalized_heads_subscribers: Arc<RwLock<Subscribers<Client>>>,
 }
 
 impl<C> EthPubSubClient<C> {
@@ -60,6 +65,8 @@ impl<C> EthPubSubClient<C> {
 		let heads_subscribers = Arc::new(RwLock::new(Subscribers::default()));
 		let logs_subscribers = Arc::new(RwLock::new(Subscribers::default()));
 		let transactions_subscribers = Arc::new(RwLock::new(Subscribers::default()));
+		let work_subscribers = Arc::new(RwLock::new(Subscribers::default()));
+		let finalized_heads_subscribers = Arc::new(RwLock::new(Subscribers::default()));
 
 		EthPubSubClient {
 			handler: Arc::new(ChainNotificationHandler {
@@ -68,10 +75,15 @@ impl<C> EthPubSubClient<C> {
 				heads_subscribers: heads_subscribers.clone(),
 				logs_subscribers: logs_subscribers.clone(),
 				transactions_subscribers: transactions_subscribers.clone(),
+				work_subscribers: work_subscribers.clone(),
+				finalized_heads_subscribers: finalized_heads_subscribers.clone(),
+				seed_compute: Mutex::new(SeedHashCompute::new()),
 			}),
 			heads_subscribers,
 			logs_subscribers,
 			transactions_subscribers,
+			work_subscribers,
+			finalized_heads_subscribers,
 		}
 	}
 
@@ -82,6 +94,8 @@ impl<C> EthPubSubClient<C> {
 		*client.heads_subscribers.write() = Subscribers::new_test();
 		*client.logs_subscribers.write() = Subscribers::new_test();
 		*client.transactions_subscribers.write() = Subscribers::new_test();
+		*client.work_subscribers.write() = Subscribers::new_test();
+		*client.finalized_heads_subscribers.write() = Subscribers::new_test();
 		client
 	}
 
@@ -119,6 +133,9 @@ pub struct ChainNotificationHandler<C> {
 	heads_subscribers: Arc<RwLock<Subscribers<Client>>>,
 	logs_subscribers: Arc<RwLock<Subscribers<(Client, EthFilter)>>>,
 	transactions_subscribers: Arc<RwLock<Subscribers<Client>>>,
+	work_subscribers: Arc<RwLock<Subscribers<Client>>>,
+	finalized_heads_subscribers: Arc<RwLock<Subscribers<Client>>>,
+	seed_compute: Mutex<SeedHashCompute>,
 }
 
 impl<C> ChainNotificationHandler<C> {
@@ -131,9 +148,17 @@ impl<C> ChainNotificationHandler<C> {
 	}
 
 	fn notify_heads(&self, headers: &[(encoded::Header, BTreeMap<String, String>)]) {
-		for subscriber in self.heads_subscribers.read().values() {
+		Self::notify_headers(&self.remote, &*self.heads_subscribers.read(), headers)
+	}
+
+	fn notify_finalized_heads(&self, headers: &[(encoded::Header, BTreeMap<String, String>)]) {
+		Self::notify_headers(&self.remote, &*self.finalized_heads_subscribers.read(), headers)
+	}
+
+	fn notify_headers(remote: &Remote, subscribers: &Subscribers<Client>, headers: &[(encoded::Header, BTreeMap<String, String>)]) {
+		for subscriber in subscribers.values() {
 			for &(ref header, ref extra_info) in headers {
-				Self::notify(&self.remote, subscriber, pubsub::Result::Header(RichHeader {
+				Self::notify(remote, subscriber, pubsub::Result::Header(RichHeader {
 					inner: header.into(),
 					extra_info: extra_info.clone(),
 				}));
@@ -184,6 +209,29 @@ impl<C> ChainNotificationHandler<C> {
 	}
 }
 
+impl<C: Send + Sync> NotifyWork for ChainNotificationHandler<C> {
+	fn notify(&self, pow_hash: H256, difficulty: U256, number: u64) {
+		let subscribers = self.work_subscribers.read();
+		if subscribers.is_empty() {
+			return;
+		}
+
+		let work = Work {
+			pow_hash: pow_hash.into(),
+			seed_hash: self.seed_compute.lock().hash_block_number(number).into(),
+			target: Ethash::difficulty_to_boundary(&difficulty).into(),
+			number: Some(number),
+		};
+		for subscriber in subscribers.values() {
+			Self::notify(&self.remote, subscriber, pubsub::Result::Work(work.clone()));
+		}
+	}
+
+	fn is_subscribed(&self) -> bool {
+		!self.work_subscribers.read().is_empty()
+	}
+}
+
 /// A light client wrapper struct.
 pub trait LightClient: Send + Sync {
 	/// Get a recent block header.
@@ -261,6 +309,20 @@ impl<C: BlockChainClient> ChainNotify for ChainNotificationHandler<C> {
 			}
 		});
 	}
+
+	fn finalized_blocks(&self, finalized: Vec<H256>) {
+		const EXTRA_INFO_PROOF: &'static str = "Object exists in in blockchain (fetched earlier), extra_info is always available if object exists; qed";
+		let headers = finalized
+			.into_iter()
+			.filter_map(|hash| self.client.block_header(BlockId::Hash(hash)))
+			.map(|header| {
+				let hash = header.hash();
+				(header, self.client.block_extra_info(BlockId::Hash(hash)).expect(EXTRA_INFO_PROOF))
+			})
+			.collect::<Vec<_>>();
+
+		self.notify_finalized_heads(&headers);
+	}
 }
 
 impl<C: Send + Sync + 'static> EthPubSub for EthPubSubClient<C> {
@@ -295,6 +357,20 @@ impl<C: Send + Sync + 'static> EthPubSub for EthPubSubClient<C> {
 			(pubsub::Kind::NewPendingTransactions, _) => {
 				errors::invalid_params("newPendingTransactions", "Expected no parameters.")
 			},
+			(pubsub::Kind::NewWork, None) => {
+				self.work_subscribers.write().push(subscriber);
+				return;
+			},
+			(pubsub::Kind::NewWork, _) => {
+				errors::invalid_params("newWork", "Expected no parameters.")
+			},
+			(pubsub::Kind::FinalizedHeads, None) => {
+				self.finalized_heads_subscribers.write().push(subscriber);
+				return;
+			},
+			(pubsub::Kind::FinalizedHeads, _) => {
+				errors::invalid_params("finalizedHeads", "Expected no parameters.")
+			},
 			_ => {
 				errors::unimplemented(None)
 			},
@@ -307,7 +383,9 @@ impl<C: Send + Sync + 'static> EthPubSub for EthPubSubClient<C> {
 		let res = self.heads_subscribers.write().remove(&id).is_some();
 		let res2 = self.logs_subscribers.write().remove(&id).is_some();
 		let res3 = self.transactions_subscribers.write().remove(&id).is_some();
+		let res4 = self.work_subscribers.write().remove(&id).is_some();
+		let res5 = self.finalized_heads_subscribers.write().remove(&id).is_some();
 
-		Ok(res || res2 || res3)
+		Ok(res || res2 || res3 || res4 || res5)
 	}
 }
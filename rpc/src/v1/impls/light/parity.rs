@@ -41,11 +41,12 @@ use v1::metadata::Metadata;
 use v1::traits::Parity;
 use v1::types::{
 	Bytes, U256, U64, H160, H256, H512, CallRequest,
-	Peers, Transaction, RpcSettings, Histogram,
+	Peers, Transaction, RpcSettings, Histogram, GasPricePercentile,
 	TransactionStats, LocalTransactionStatus,
 	BlockNumber, ConsensusCapability, VersionInfo,
 	OperationsInfo, DappId, ChainStatus,
 	AccountInfo, HwAccountInfo, Header, RichHeader,
+	SimulatedTransaction, TraceOptions, ValidatorReport,
 };
 use Host;
 
@@ -163,6 +164,10 @@ impl Parity for ParityClient {
 		Ok(U256::default())
 	}
 
+	fn min_gas_price_percentile(&self) -> Result<Option<GasPricePercentile>> {
+		Ok(None)
+	}
+
 	fn extra_data(&self) -> Result<Bytes> {
 		Ok(Bytes::default())
 	}
@@ -390,6 +395,10 @@ impl Parity for ParityClient {
 		})
 	}
 
+	fn validator_reports(&self) -> Result<Vec<ValidatorReport>> {
+		Err(errors::light_unimplemented(None))
+	}
+
 	fn block_header(&self, number: Trailing<BlockNumber>) -> BoxFuture<RichHeader> {
 		use ethcore::encoded;
 
@@ -432,6 +441,10 @@ impl Parity for ParityClient {
 		Box::new(self.fetcher().header(id).and_then(from_encoded))
 	}
 
+	fn finalized_block(&self) -> Result<Option<RichHeader>> {
+		Err(errors::light_unimplemented(None))
+	}
+
 	fn ipfs_cid(&self, content: Bytes) -> Result<String> {
 		ipfs::cid(content)
 	}
@@ -440,6 +453,10 @@ impl Parity for ParityClient {
 		Err(errors::light_unimplemented(None))
 	}
 
+	fn simulate_bundle(&self, _raw_transactions: Vec<Bytes>, _flags: TraceOptions, _block: Trailing<BlockNumber>) -> Result<Vec<SimulatedTransaction>> {
+		Err(errors::light_unimplemented(None))
+	}
+
 	fn node_health(&self) -> BoxFuture<Health> {
 		Box::new(self.health.health()
 			.map_err(|err| errors::internal("Health API failure.", err)))
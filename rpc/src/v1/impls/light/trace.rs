@@ -16,16 +16,59 @@
 
 //! Traces api implementation.
 
-use jsonrpc_core::Result;
+use std::sync::Arc;
+
+use jsonrpc_core::{BoxFuture, Result};
+use jsonrpc_core::futures::{future, Future};
 use jsonrpc_macros::Trailing;
 use v1::Metadata;
 use v1::traits::Traces;
 use v1::helpers::errors;
+use v1::helpers::dispatch::LightDispatcher;
+use v1::helpers::light_fetch::{LightFetch, ExecutionResult};
+use v1::impls::traces::to_call_analytics;
 use v1::types::{TraceFilter, LocalizedTrace, BlockNumber, Index, CallRequest, Bytes, TraceResults, TraceOptions, H256};
 
+fn state_diff_unsupported() -> ::jsonrpc_core::Error {
+	errors::light_unimplemented(Some("State diffs are not available on light clients.".into()))
+}
+
+fn to_trace_results(res: ExecutionResult) -> Result<TraceResults> {
+	match res {
+		Ok(executed) => Ok(executed.into()),
+		Err(e) => Err(errors::execution(e)),
+	}
+}
+
 /// Traces api implementation.
-// TODO: all calling APIs should be possible w. proved remote TX execution.
-pub struct TracesClient;
+///
+/// Calls and transaction replays are re-executed locally against remotely proved state.
+// TODO: `trace_callMany` and `trace_rawTransaction` should be possible w. proved remote TX execution.
+pub struct TracesClient {
+	light_dispatch: Arc<LightDispatcher>,
+	gas_price_percentile: usize,
+}
+
+impl TracesClient {
+	/// Creates new `TracesClient`.
+	pub fn new(light_dispatch: Arc<LightDispatcher>, gas_price_percentile: usize) -> Self {
+		TracesClient {
+			light_dispatch,
+			gas_price_percentile,
+		}
+	}
+
+	/// Create a light blockchain data fetcher.
+	fn fetcher(&self) -> LightFetch {
+		LightFetch {
+			client: self.light_dispatch.client.clone(),
+			on_demand: self.light_dispatch.on_demand.clone(),
+			sync: self.light_dispatch.sync.clone(),
+			cache: self.light_dispatch.cache.clone(),
+			gas_price_percentile: self.gas_price_percentile,
+		}
+	}
+}
 
 impl Traces for TracesClient {
 	type Metadata = Metadata;
@@ -46,8 +89,13 @@ impl Traces for TracesClient {
 		Err(errors::light_unimplemented(None))
 	}
 
-	fn call(&self, _meta: Self::Metadata, _request: CallRequest, _flags: TraceOptions, _block: Trailing<BlockNumber>) -> Result<TraceResults> {
-		Err(errors::light_unimplemented(None))
+	fn call(&self, _meta: Self::Metadata, request: CallRequest, flags: TraceOptions, block: Trailing<BlockNumber>) -> BoxFuture<TraceResults> {
+		let analytics = to_call_analytics(flags);
+		if analytics.state_diffing {
+			return Box::new(future::err(state_diff_unsupported()));
+		}
+
+		Box::new(self.fetcher().proved_execution(request, block, analytics).and_then(to_trace_results))
 	}
 
 	fn call_many(&self, _meta: Self::Metadata, _request: Vec<(CallRequest, TraceOptions)>, _block: Trailing<BlockNumber>) -> Result<Vec<TraceResults>> {
@@ -58,8 +106,13 @@ impl Traces for TracesClient {
 		Err(errors::light_unimplemented(None))
 	}
 
-	fn replay_transaction(&self, _transaction_hash: H256, _flags: TraceOptions) -> Result<TraceResults> {
-		Err(errors::light_unimplemented(None))
+	fn replay_transaction(&self, transaction_hash: H256, flags: TraceOptions) -> BoxFuture<TraceResults> {
+		let analytics = to_call_analytics(flags);
+		if analytics.state_diffing {
+			return Box::new(future::err(state_diff_unsupported()));
+		}
+
+		Box::new(self.fetcher().replay_transaction(transaction_hash.into(), analytics).and_then(to_trace_results))
 	}
 
 	fn replay_block_transactions(&self,  _block_number: BlockNumber, _flags: TraceOptions) ->  Result<Vec<TraceResults>> {
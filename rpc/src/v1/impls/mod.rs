@@ -16,6 +16,7 @@
 
 //! Ethereum rpc interface implementation.
 
+mod debug;
 mod eth;
 mod eth_filter;
 mod eth_pubsub;
@@ -36,6 +37,7 @@ mod private;
 
 pub mod light;
 
+pub use self::debug::DebugClient;
 pub use self::eth::{EthClient, EthClientOptions};
 pub use self::eth_filter::EthFilterClient;
 pub use self::eth_pubsub::EthPubSubClient;
@@ -33,6 +33,8 @@ use ethcore::miner::{self, MinerService};
 use ethcore::mode::Mode;
 use ethcore::state::StateInfo;
 use ethcore_logger::RotatingLogger;
+use rlp::Rlp;
+use transaction::SignedTransaction;
 use node_health::{NodeHealth, Health};
 use updater::{Service as UpdateService};
 
@@ -41,15 +43,17 @@ use jsonrpc_core::futures::{future, Future};
 use jsonrpc_macros::Trailing;
 use v1::helpers::{self, errors, fake_sign, ipfs, SigningQueue, SignerService, NetworkSettings};
 use v1::helpers::accounts::unwrap_provider;
+use v1::impls::traces::to_call_analytics;
 use v1::metadata::Metadata;
 use v1::traits::Parity;
 use v1::types::{
 	Bytes, U256, U64, H160, H256, H512, CallRequest,
-	Peers, Transaction, RpcSettings, Histogram,
+	Peers, Transaction, RpcSettings, Histogram, GasPricePercentile,
 	TransactionStats, LocalTransactionStatus,
 	BlockNumber, ConsensusCapability, VersionInfo,
 	OperationsInfo, DappId, ChainStatus,
 	AccountInfo, HwAccountInfo, RichHeader,
+	SimulatedTransaction, TraceOptions, ValidatorReport,
 	block_number_to_id
 };
 use Host;
@@ -177,6 +181,10 @@ impl<C, M, U, S> Parity for ParityClient<C, M, U> where
 		Ok(self.miner.queue_status().options.minimal_gas_price.into())
 	}
 
+	fn min_gas_price_percentile(&self) -> Result<Option<GasPricePercentile>> {
+		Ok(self.miner.gas_price_percentile_status().map(Into::into))
+	}
+
 	fn extra_data(&self) -> Result<Bytes> {
 		Ok(Bytes::new(self.miner.authoring_params().extra_data))
 	}
@@ -330,7 +338,13 @@ impl<C, M, U, S> Parity for ParityClient<C, M, U> where
 
 		Ok(all_transactions
 		   .into_iter()
-		   .map(|t| Transaction::from_pending(t.pending().clone(), block_number, self.eip86_transition))
+		   .map(|t| {
+			   let mut tx = Transaction::from_pending(t.pending().clone(), block_number, self.eip86_transition);
+			   if t.is_restored() {
+				   tx.restored = Some(true);
+			   }
+			   tx
+		   })
 		   .collect()
 		)
 	}
@@ -428,6 +442,10 @@ impl<C, M, U, S> Parity for ParityClient<C, M, U> where
 		})
 	}
 
+	fn validator_reports(&self) -> Result<Vec<ValidatorReport>> {
+		Ok(self.client.validator_reports().into_iter().map(Into::into).collect())
+	}
+
 	fn block_header(&self, number: Trailing<BlockNumber>) -> BoxFuture<RichHeader> {
 		const EXTRA_INFO_PROOF: &str = "Object exists in blockchain (fetched earlier), extra_info is always available if object exists; qed";
 		let number = number.unwrap_or_default();
@@ -457,6 +475,20 @@ impl<C, M, U, S> Parity for ParityClient<C, M, U> where
 		}))
 	}
 
+	fn finalized_block(&self) -> Result<Option<RichHeader>> {
+		const EXTRA_INFO_PROOF: &str = "Object exists in blockchain (fetched earlier), extra_info is always available if object exists; qed";
+
+		let id = match self.client.finalized_block() {
+			Some(hash) => BlockId::Hash(hash),
+			None => return Ok(None),
+		};
+
+		Ok(self.client.block_header(id.clone()).map(|header| RichHeader {
+			inner: header.into(),
+			extra_info: self.client.block_extra_info(id).expect(EXTRA_INFO_PROOF),
+		}))
+	}
+
 	fn ipfs_cid(&self, content: Bytes) -> Result<String> {
 		ipfs::cid(content)
 	}
@@ -497,6 +529,48 @@ impl<C, M, U, S> Parity for ParityClient<C, M, U> where
 				.map_err(errors::call)
 	}
 
+	fn simulate_bundle(&self, raw_transactions: Vec<Bytes>, flags: TraceOptions, num: Trailing<BlockNumber>) -> Result<Vec<SimulatedTransaction>> {
+		let analytics = to_call_analytics(flags);
+		let transactions = raw_transactions
+			.into_iter()
+			.map(|raw| {
+				let tx = Rlp::new(&raw.into_vec()).as_val().map_err(errors::rlp)?;
+				let signed = SignedTransaction::new(tx).map_err(errors::transaction)?;
+				Ok((signed, analytics))
+			})
+			.collect::<Result<Vec<_>>>()?;
+
+		let num = num.unwrap_or_default();
+
+		let (mut state, header) = if num == BlockNumber::Pending {
+			let info = self.client.chain_info();
+			let state = self.miner.pending_state(info.best_block_number).ok_or(errors::state_pruned())?;
+			let header = self.miner.pending_block_header(info.best_block_number).ok_or(errors::state_pruned())?;
+
+			(state, header)
+		} else {
+			let id = match num {
+				BlockNumber::Num(num) => BlockId::Number(num),
+				BlockNumber::Earliest => BlockId::Earliest,
+				BlockNumber::Latest => BlockId::Latest,
+				BlockNumber::Pending => unreachable!(), // Already covered
+			};
+
+			let state = self.client.state_at(id).ok_or(errors::state_pruned())?;
+			let header = self.client.block_header(id).ok_or(errors::state_pruned())?.decode().map_err(errors::decode)?;
+
+			(state, header)
+		};
+
+		self.client.simulate(&transactions, &mut state, &header)
+			.map(|res| res.into_iter()
+				.zip(transactions.iter())
+				.map(|(executed, &(ref tx, _))| SimulatedTransaction::new(tx.hash().into(), executed))
+				.collect()
+			)
+			.map_err(errors::call)
+	}
+
 	fn node_health(&self) -> BoxFuture<Health> {
 		Box::new(self.health.health()
 			.map_err(|err| errors::internal("Health API failure.", err)))
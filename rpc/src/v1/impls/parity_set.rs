@@ -25,6 +25,8 @@ use sync::ManageNetwork;
 use fetch::{self, Fetch};
 use futures_cpupool::CpuPool;
 use hash::keccak_buffer;
+use rlp::Rlp;
+use transaction::SignedTransaction;
 use updater::{Service as UpdateService};
 
 use jsonrpc_core::{BoxFuture, Result};
@@ -32,7 +34,7 @@ use jsonrpc_core::futures::Future;
 use v1::helpers::dapps::DappsService;
 use v1::helpers::errors;
 use v1::traits::ParitySet;
-use v1::types::{Bytes, H160, H256, U256, ReleaseInfo, Transaction, LocalDapp};
+use v1::types::{Bytes, H160, H256, U64, U256, ReleaseInfo, Transaction, LocalDapp};
 
 /// Parity-specific rpc interface for operations altering the settings.
 pub struct ParitySetClient<C, M, U, F = fetch::Client> {
@@ -210,4 +212,25 @@ impl<C, M, U, F> ParitySet for ParitySetClient<C, M, U, F> where
 		   .map(|t| Transaction::from_pending(t.pending().clone(), block_number + 1, self.eip86_transition))
 		)
 	}
+
+	fn send_bundle(&self, raw_transactions: Vec<Bytes>, target_block: U64) -> Result<H256> {
+		let target_block: u64 = target_block.into();
+		if target_block <= self.client.chain_info().best_block_number {
+			return Err(errors::invalid_params("targetBlock", "Target block has already been mined."));
+		}
+
+		let transactions = raw_transactions.into_iter()
+			.map(|raw| {
+				Rlp::new(&raw.into_vec()).as_val()
+					.map_err(errors::rlp)
+					.and_then(|tx| SignedTransaction::new(tx).map_err(errors::transaction))
+			})
+			.collect::<Result<Vec<_>>>()?;
+
+		if transactions.is_empty() {
+			return Err(errors::invalid_params("transactions", "Bundle is empty."));
+		}
+
+		Ok(self.miner.submit_bundle(&*self.client, transactions, target_block).into())
+	}
 }
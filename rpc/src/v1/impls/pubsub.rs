@@ -16,25 +16,44 @@
 
 //! Parity-specific PUB-SUB rpc implementation.
 
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
 use std::time::Duration;
 use parking_lot::RwLock;
 
 use jsonrpc_core::{self as core, Result, MetaIoHandler};
 use jsonrpc_core::futures::{Future, Stream, Sink};
 use jsonrpc_macros::Trailing;
-use jsonrpc_macros::pubsub::Subscriber;
+use jsonrpc_macros::pubsub::{self, Subscriber};
 use jsonrpc_pubsub::SubscriptionId;
+use serde_json;
 use tokio_timer;
+use futures_cpupool::CpuPool;
 
+use ethcore::client::{BlockChainClient, BlockId, CallAnalytics, ChainNotify, ChainRoute, ChainRouteType};
+use ethereum_types::H256;
+use bytes::Bytes;
+use miner::pool::TransactionEvent;
 use parity_reactor::Remote;
-use v1::helpers::GenericPollManager;
+use v1::helpers::{errors, GenericPollManager, Subscribers};
 use v1::metadata::Metadata;
 use v1::traits::PubSub;
+use v1::types::{BlockStateDiff, TransactionStateDiff, TransactionPoolEvent};
+
+/// Subscription target pushing the state diffs of every imported or retracted block.
+pub const STATE_DIFFS_TARGET: &'static str = "stateDiffs";
+
+/// Subscription target pushing the events of every transaction in the pool.
+pub const TRANSACTION_POOL_EVENTS_TARGET: &'static str = "parity_transactionPoolEvents";
+
+type Client = pubsub::Sink<core::Value>;
 
 /// Parity PubSub implementation.
 pub struct PubSubClient<S: core::Middleware<Metadata>> {
 	poll_manager: Arc<RwLock<GenericPollManager<S>>>,
+	state_diffs_subscribers: Option<Arc<RwLock<Subscribers<Client>>>>,
+	// chain notifications only hold a weak reference to the handler, so we keep it alive here.
+	state_diffs_handler: Option<Arc<ChainNotify>>,
+	transaction_pool_events: Option<Arc<TransactionPoolEventsHandler>>,
 	remote: Remote,
 }
 
@@ -57,9 +76,40 @@ impl<S: core::Middleware<Metadata>> PubSubClient<S> {
 
 		PubSubClient {
 			poll_manager,
+			state_diffs_subscribers: None,
+			state_diffs_handler: None,
+			transaction_pool_events: None,
 			remote,
 		}
 	}
+
+	/// Enables the `stateDiffs` subscription target and returns the chain notification handler feeding it.
+	pub fn state_diffs_handler<C: BlockChainClient + 'static>(&mut self, client: Arc<C>) -> Arc<StateDiffsNotificationHandler<C>> {
+		let subscribers = self.state_diffs_subscribers
+			.get_or_insert_with(|| Arc::new(RwLock::new(Subscribers::default())))
+			.clone();
+
+		let handler = Arc::new(StateDiffsNotificationHandler {
+			client,
+			remote: self.remote.clone(),
+			// a single thread keeps the notifications in import order.
+			pool: CpuPool::new(1),
+			subscribers,
+		});
+		self.state_diffs_handler = Some(handler.clone());
+		handler
+	}
+
+	/// Enables the `parity_transactionPoolEvents` subscription target and returns the handler feeding it.
+	pub fn transaction_pool_events_handler(&mut self) -> Weak<TransactionPoolEventsHandler> {
+		let remote = self.remote.clone();
+		let handler = self.transaction_pool_events.get_or_insert_with(|| Arc::new(TransactionPoolEventsHandler {
+			remote,
+			subscribers: RwLock::new(Subscribers::default()),
+		}));
+
+		Arc::downgrade(handler)
+	}
 }
 
 impl PubSubClient<core::NoopMiddleware> {
@@ -70,6 +120,141 @@ impl PubSubClient<core::NoopMiddleware> {
 		*client.poll_manager.write() = GenericPollManager::new_test(rpc);
 		client
 	}
+
+	/// Enables the `stateDiffs` subscription target with deterministic ids.
+	#[cfg(test)]
+	pub fn state_diffs_handler_test<C: BlockChainClient + 'static>(&mut self, client: Arc<C>) -> Arc<StateDiffsNotificationHandler<C>> {
+		self.state_diffs_subscribers = Some(Arc::new(RwLock::new(Subscribers::new_test())));
+		self.state_diffs_handler(client)
+	}
+
+	/// Enables the `parity_transactionPoolEvents` subscription target with deterministic ids.
+	#[cfg(test)]
+	pub fn transaction_pool_events_handler_test(&mut self) -> Weak<TransactionPoolEventsHandler> {
+		self.transaction_pool_events = Some(Arc::new(TransactionPoolEventsHandler {
+			remote: self.remote.clone(),
+			subscribers: RwLock::new(Subscribers::new_test()),
+		}));
+		self.transaction_pool_events_handler()
+	}
+}
+
+/// Chain notification handler pushing per-block state diffs to `stateDiffs` subscribers.
+pub struct StateDiffsNotificationHandler<C> {
+	client: Arc<C>,
+	remote: Remote,
+	pool: CpuPool,
+	subscribers: Arc<RwLock<Subscribers<Client>>>,
+}
+
+impl<C: BlockChainClient> StateDiffsNotificationHandler<C> {
+	fn block_state_diff(client: &C, hash: H256, removed: bool) -> Option<BlockStateDiff> {
+		let analytics = CallAnalytics {
+			transaction_tracing: false,
+			vm_tracing: false,
+			state_diffing: true,
+		};
+
+		let block = client.block(BlockId::Hash(hash))?;
+		let executed = match client.replay_block_transactions(BlockId::Hash(hash), analytics) {
+			Ok(executed) => executed,
+			Err(e) => {
+				warn!(target: "rpc", "Unable to compute state diffs of block {}: {}", hash, e);
+				return None;
+			},
+		};
+
+		Some(BlockStateDiff {
+			block_hash: hash.into(),
+			block_number: block.number().into(),
+			removed,
+			transactions: block.transaction_hashes().into_iter()
+				.zip(executed)
+				.map(|(transaction_hash, executed)| TransactionStateDiff {
+					transaction_hash: transaction_hash.into(),
+					state_diff: executed.state_diff.map(Into::into),
+				})
+				.collect(),
+		})
+	}
+}
+
+impl<C: BlockChainClient + 'static> ChainNotify for StateDiffsNotificationHandler<C> {
+	fn new_blocks(
+		&self,
+		_imported: Vec<H256>,
+		_invalid: Vec<H256>,
+		route: ChainRoute,
+		_sealed: Vec<H256>,
+		// Block bytes.
+		_proposed: Vec<Bytes>,
+		_duration: Duration,
+	) {
+		// replaying blocks is expensive, skip it when nobody listens.
+		if self.subscribers.read().is_empty() {
+			return;
+		}
+
+		let client = self.client.clone();
+		let remote = self.remote.clone();
+		let subscribers = self.subscribers.clone();
+
+		// replay off the import thread and the event loop, only the notifications are sent from the loop.
+		self.pool.spawn_fn(move || {
+			// retracted blocks come first, in the order of the route.
+			for &(hash, ref typ) in route.route() {
+				let removed = match *typ {
+					ChainRouteType::Enacted => false,
+					ChainRouteType::Retracted => true,
+				};
+
+				let diff = match Self::block_state_diff(&*client, hash, removed) {
+					Some(diff) => serde_json::to_value(diff).expect("State diffs are always serializable; qed"),
+					None => continue,
+				};
+
+				for subscriber in subscribers.read().values() {
+					remote.spawn(subscriber
+						.notify(Ok(diff.clone()))
+						.map(|_| ())
+						.map_err(|e| warn!(target: "rpc", "Unable to send notification: {}", e))
+					);
+				}
+			}
+
+			Ok::<(), ()>(())
+		}).forget();
+	}
+}
+
+/// Transaction pool listener pushing the events of every transaction to `parity_transactionPoolEvents` subscribers.
+pub struct TransactionPoolEventsHandler {
+	remote: Remote,
+	subscribers: RwLock<Subscribers<Client>>,
+}
+
+impl TransactionPoolEventsHandler {
+	/// Notify all subscribers about a batch of transaction pool events.
+	pub fn notify(&self, events: &[(H256, TransactionEvent)]) {
+		let subscribers = self.subscribers.read();
+		if subscribers.is_empty() {
+			return;
+		}
+
+		let events = events.iter()
+			.cloned()
+			.map(|(hash, event)| TransactionPoolEvent::new(hash.into(), event))
+			.collect::<Vec<_>>();
+		let events = serde_json::to_value(events).expect("Transaction pool events are always serializable; qed");
+
+		for subscriber in subscribers.values() {
+			self.remote.spawn(subscriber
+				.notify(Ok(events.clone()))
+				.map(|_| ())
+				.map_err(|e| warn!(target: "rpc", "Unable to send notification: {}", e))
+			);
+		}
+	}
 }
 
 impl<S: core::Middleware<Metadata>> PubSub for PubSubClient<S> {
@@ -77,6 +262,35 @@ impl<S: core::Middleware<Metadata>> PubSub for PubSubClient<S> {
 
 	fn parity_subscribe(&self, mut meta: Metadata, subscriber: Subscriber<core::Value>, method: String, params: Trailing<core::Params>) {
 		let params = params.unwrap_or(core::Params::Array(vec![]));
+
+		if method == STATE_DIFFS_TARGET {
+			let error = match (self.state_diffs_subscribers.as_ref(), params) {
+				(Some(subscribers), core::Params::Array(ref params)) if params.is_empty() => {
+					subscribers.write().push(subscriber);
+					return;
+				},
+				(Some(_), _) => errors::invalid_params(STATE_DIFFS_TARGET, "Expected no parameters."),
+				(None, _) => errors::unimplemented(Some("State diff subscriptions are only available on full nodes.".into())),
+			};
+
+			let _ = subscriber.reject(error);
+			return;
+		}
+
+		if method == TRANSACTION_POOL_EVENTS_TARGET {
+			let error = match (self.transaction_pool_events.as_ref(), params) {
+				(Some(handler), core::Params::Array(ref params)) if params.is_empty() => {
+					handler.subscribers.write().push(subscriber);
+					return;
+				},
+				(Some(_), _) => errors::invalid_params(TRANSACTION_POOL_EVENTS_TARGET, "Expected no parameters."),
+				(None, _) => errors::unimplemented(Some("Transaction pool events are only available on full nodes.".into())),
+			};
+
+			let _ = subscriber.reject(error);
+			return;
+		}
+
 		// Make sure to get rid of PubSub session otherwise it will never be dropped.
 		meta.session = None;
 
@@ -96,6 +310,10 @@ impl<S: core::Middleware<Metadata>> PubSub for PubSubClient<S> {
 
 	fn parity_unsubscribe(&self, id: SubscriptionId) -> Result<bool> {
 		let res = self.poll_manager.write().unsubscribe(&id);
-		Ok(res)
+		let res2 = self.state_diffs_subscribers.as_ref()
+			.map_or(false, |subscribers| subscribers.write().remove(&id).is_some());
+		let res3 = self.transaction_pool_events.as_ref()
+			.map_or(false, |handler| handler.subscribers.write().remove(&id).is_some());
+		Ok(res || res2 || res3)
 	}
 }
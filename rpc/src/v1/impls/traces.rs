@@ -22,14 +22,16 @@ use ethcore::client::{BlockChainClient, CallAnalytics, TransactionId, TraceId, S
 use rlp::Rlp;
 use transaction::SignedTransaction;
 
-use jsonrpc_core::Result;
+use jsonrpc_core::{BoxFuture, Result};
+use jsonrpc_core::futures::future;
 use jsonrpc_macros::Trailing;
 use v1::Metadata;
 use v1::traits::Traces;
 use v1::helpers::{errors, fake_sign};
 use v1::types::{TraceFilter, LocalizedTrace, BlockNumber, Index, CallRequest, Bytes, TraceResults, TraceOptions, H256, block_number_to_id};
 
-fn to_call_analytics(flags: TraceOptions) -> CallAnalytics {
+/// Converts the requested trace options into `CallAnalytics`.
+pub fn to_call_analytics(flags: TraceOptions) -> CallAnalytics {
 	CallAnalytics {
 		transaction_tracing: flags.contains(&("trace".to_owned())),
 		vm_tracing: flags.contains(&("vmTrace".to_owned())),
@@ -87,26 +89,27 @@ impl<C, S> Traces for TracesClient<C> where
 			.map(LocalizedTrace::from))
 	}
 
-	fn call(&self, meta: Self::Metadata, request: CallRequest, flags: TraceOptions, block: Trailing<BlockNumber>) -> Result<TraceResults> {
+	fn call(&self, meta: Self::Metadata, request: CallRequest, flags: TraceOptions, block: Trailing<BlockNumber>) -> BoxFuture<TraceResults> {
 		let block = block.unwrap_or_default();
 
 		let request = CallRequest::into(request);
-		let signed = fake_sign::sign_call(request, meta.is_dapp())?;
+		let signed = try_bf!(fake_sign::sign_call(request, meta.is_dapp()));
 
 		let id = match block {
 			BlockNumber::Num(num) => BlockId::Number(num),
 			BlockNumber::Earliest => BlockId::Earliest,
 			BlockNumber::Latest => BlockId::Latest,
 
-			BlockNumber::Pending => return Err(errors::invalid_params("`BlockNumber::Pending` is not supported", ())),
+			BlockNumber::Pending => return Box::new(future::err(errors::invalid_params("`BlockNumber::Pending` is not supported", ()))),
 		};
 
-		let mut state = self.client.state_at(id).ok_or(errors::state_pruned())?;
-		let header = self.client.block_header(id).ok_or(errors::state_pruned())?;
+		let mut state = try_bf!(self.client.state_at(id).ok_or(errors::state_pruned()));
+		let header = try_bf!(self.client.block_header(id).ok_or(errors::state_pruned()));
+		let header = try_bf!(header.decode().map_err(errors::decode));
 
-		self.client.call(&signed, to_call_analytics(flags), &mut state, &header.decode().map_err(errors::decode)?)
+		Box::new(future::done(self.client.call(&signed, to_call_analytics(flags), &mut state, &header)
 			.map(TraceResults::from)
-			.map_err(errors::call)
+			.map_err(errors::call)))
 	}
 
 	fn call_many(&self, meta: Self::Metadata, requests: Vec<(CallRequest, TraceOptions)>, block: Trailing<BlockNumber>) -> Result<Vec<TraceResults>> {
@@ -158,10 +161,10 @@ impl<C, S> Traces for TracesClient<C> where
 			.map_err(errors::call)
 	}
 
-	fn replay_transaction(&self, transaction_hash: H256, flags: TraceOptions) -> Result<TraceResults> {
-		self.client.replay(TransactionId::Hash(transaction_hash.into()), to_call_analytics(flags))
+	fn replay_transaction(&self, transaction_hash: H256, flags: TraceOptions) -> BoxFuture<TraceResults> {
+		Box::new(future::done(self.client.replay(TransactionId::Hash(transaction_hash.into()), to_call_analytics(flags))
 			.map(TraceResults::from)
-			.map_err(errors::call)
+			.map_err(errors::call)))
 	}
 
 	fn replay_block_transactions(&self, block_number: BlockNumber, flags: TraceOptions) -> Result<Vec<TraceResults>> {
@@ -22,14 +22,16 @@ use std::collections::{BTreeMap, HashMap};
 use bytes::Bytes;
 use ethcore::account_provider::SignError as AccountError;
 use ethcore::block::{Block, SealedBlock, IsBlock};
-use ethcore::client::{Nonce, PrepareOpenBlock, StateClient, EngineInfo};
+use ethcore::client::{BlockChainClient, Nonce, PrepareOpenBlock, StateClient, EngineInfo};
 use ethcore::engines::EthEngine;
 use ethcore::error::Error;
 use ethcore::header::{BlockNumber, Header};
 use ethcore::ids::BlockId;
 use ethcore::miner::{MinerService, AuthoringParams};
+use ethcore::miner::bundles::Bundle;
 use ethcore::receipt::{Receipt, RichReceipt};
 use ethereum_types::{H256, U256, Address};
+use miner::gas_pricer::GasPricePercentileStatus;
 use miner::pool::local_transactions::Status as LocalTransactionStatus;
 use miner::pool::{verifier, VerifiedTransaction, QueueStatus};
 use parking_lot::{RwLock, Mutex};
@@ -50,6 +52,8 @@ pub struct TestMinerService {
 	pub next_nonces: RwLock<HashMap<Address, U256>>,
 	/// Password held by Engine.
 	pub password: RwLock<String>,
+	/// Submitted bundles.
+	pub bundles: Mutex<Vec<Bundle>>,
 
 	authoring_params: RwLock<AuthoringParams>,
 }
@@ -63,6 +67,7 @@ impl Default for TestMinerService {
 			pending_receipts: Mutex::new(BTreeMap::new()),
 			next_nonces: RwLock::new(HashMap::new()),
 			password: RwLock::new(String::new()),
+			bundles: Mutex::new(Vec::new()),
 			authoring_params: RwLock::new(AuthoringParams {
 				author: Address::zero(),
 				gas_range_target: (12345.into(), 54321.into()),
@@ -194,6 +199,15 @@ impl MinerService for TestMinerService {
 		})
 	}
 
+	fn submit_bundle<C>(&self, _chain: &C, transactions: Vec<SignedTransaction>, target_block: BlockNumber) -> H256
+		where C: BlockChainClient
+	{
+		let bundle = Bundle::new(transactions, target_block);
+		let hash = bundle.hash;
+		self.bundles.lock().push(bundle);
+		hash
+	}
+
 	fn remove_transaction(&self, hash: &H256) -> Option<Arc<VerifiedTransaction>> {
 		self.pending_transactions.lock().remove(hash).map(|tx| {
 			Arc::new(VerifiedTransaction::from_pending_block_transaction(tx))
@@ -252,6 +266,7 @@ impl MinerService for TestMinerService {
 				minimal_gas_price: 0x1312d00.into(),
 				block_gas_limit: 5_000_000.into(),
 				tx_gas_limit: 5_000_000.into(),
+				max_nonce_gap: None,
 			},
 			status: txpool::LightStatus {
 				mem_usage: 1_000,
@@ -262,10 +277,21 @@ impl MinerService for TestMinerService {
 				max_count: 1_024,
 				max_per_sender: 16,
 				max_mem_usage: 5_000,
+				replace_bump_percent: None,
 			},
 		}
 	}
 
+	fn gas_price_percentile_status(&self) -> Option<GasPricePercentileStatus> {
+		Some(GasPricePercentileStatus {
+			percentile: 50,
+			blocks: 100,
+			block_number: 42,
+			transactions: 1_000,
+			gas_price: 0x1312d00.into(),
+		})
+	}
+
 	/// Submit `seal` as a valid solution for the header of `pow_hash`.
 	/// Will check the seal, but not actually insert the block into the chain.
 	fn submit_seal(&self, _pow_hash: H256, _seal: Vec<Bytes>) -> Result<SealedBlock, Error> {
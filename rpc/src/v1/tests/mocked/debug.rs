@@ -0,0 +1,94 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use ethcore::executed::{Executed, CallError};
+use ethcore::client::TestBlockChainClient;
+
+use jsonrpc_core::IoHandler;
+use v1::{Metadata, Debug, DebugClient};
+
+struct Tester {
+	client: Arc<TestBlockChainClient>,
+	io: IoHandler<Metadata>,
+}
+
+fn io() -> Tester {
+	let client = Arc::new(TestBlockChainClient::new());
+	*client.execution_result.write() = Some(Ok(Executed {
+		exception: None,
+		gas: 20_000.into(),
+		gas_used: 10_000.into(),
+		refunded: 0.into(),
+		cumulative_gas_used: 10_000.into(),
+		logs: vec![],
+		contracts_created: vec![],
+		output: vec![1, 2, 3],
+		trace: vec![],
+		vm_trace: None,
+		state_diff: None,
+	}));
+	let debug = DebugClient::new(&client);
+	let mut io = IoHandler::default();
+	io.extend_with(debug.to_delegate());
+
+	Tester {
+		client: client,
+		io: io,
+	}
+}
+
+#[test]
+fn rpc_debug_trace_transaction() {
+	let tester = io();
+
+	let request = r#"{"jsonrpc":"2.0","method":"debug_traceTransaction","params":["0x0000000000000000000000000000000000000000000000000000000000000005", {"disableStorage":true}],"id":1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":{"failed":false,"gas":10000,"returnValue":"010203","structLogs":[]},"id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_debug_trace_transaction_state_pruned() {
+	let tester = io();
+	*tester.client.execution_result.write() = Some(Err(CallError::StatePruned));
+
+	let request = r#"{"jsonrpc":"2.0","method":"debug_traceTransaction","params":["0x0000000000000000000000000000000000000000000000000000000000000005"],"id":1}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32000,"message":"This request is not supported because your node is running with state pruning. Run with --pruning=archive."},"id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_debug_trace_call() {
+	let tester = io();
+
+	let request = r#"{"jsonrpc":"2.0","method":"debug_traceCall","params":[{}, "latest"],"id":1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":{"failed":false,"gas":10000,"returnValue":"010203","structLogs":[]},"id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_debug_trace_call_custom_tracer() {
+	let tester = io();
+
+	let request = r#"{"jsonrpc":"2.0","method":"debug_traceCall","params":[{}, "latest", {"tracer":"callTracer"}],"id":1}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32000,"message":"Custom tracers are not supported. Omit `tracer` to get struct logs."},"id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+}
@@ -22,11 +22,13 @@ use std::time::{Instant, Duration, SystemTime, UNIX_EPOCH};
 use ethereum_types::{H256, U256, Address};
 use parking_lot::Mutex;
 use ethcore::account_provider::AccountProvider;
+use ethcore::basic_account::BasicAccount;
 use ethcore::client::{BlockChainClient, BlockId, EachBlockWith, Executed, TestBlockChainClient, TransactionId};
 use ethcore::log_entry::{LocalizedLogEntry, LogEntry};
 use ethcore::miner::MinerService;
 use ethcore::receipt::{LocalizedReceipt, TransactionOutcome};
 use ethkey::Secret;
+use hash::keccak;
 use sync::SyncState;
 use miner::external::ExternalMiner;
 use rlp;
@@ -480,6 +482,42 @@ fn rpc_eth_storage_at() {
 	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_eth_get_proof_pruned() {
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "eth_getProof",
+		"params": ["0x0000000000000000000000000000000000000001", ["0x0000000000000000000000000000000000000000000000000000000000000004"], "latest"],
+		"id": 1
+	}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32000,"message":"This request is not supported because your node is running with state pruning. Run with --pruning=archive."},"id":1}"#;
+
+	assert_eq!(EthTester::default().io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_eth_get_proof() {
+	let tester = EthTester::default();
+	let address_hash = keccak(&Address::from(1));
+	tester.client.account_proofs.write().insert(address_hash, (vec![vec![0x01, 0x02], vec![0x03]], BasicAccount {
+		nonce: 1.into(),
+		balance: 5.into(),
+		storage_root: H256::from(7),
+		code_hash: H256::from(8),
+	}));
+	tester.client.storage_proofs.write().insert((address_hash, keccak(&H256::from(4))), (vec![vec![0x04, 0x05]], H256::from(9)));
+
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "eth_getProof",
+		"params": ["0x0000000000000000000000000000000000000001", ["0x0000000000000000000000000000000000000000000000000000000000000004"], "latest"],
+		"id": 1
+	}"#;
+	let response = r#"{"jsonrpc":"2.0","result":{"accountProof":["0x0102","0x03"],"address":"0x0000000000000000000000000000000000000001","balance":"0x5","codeHash":"0x0000000000000000000000000000000000000000000000000000000000000008","nonce":"0x1","storageHash":"0x0000000000000000000000000000000000000000000000000000000000000007","storageProof":[{"key":"0x4","proof":["0x0405"],"value":"0x9"}]},"id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+}
+
 #[test]
 fn rpc_eth_transaction_count() {
 	let request = r#"{
@@ -268,6 +268,17 @@ fn rpc_parity_min_gas_price() {
 	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_parity_min_gas_price_percentile() {
+	let deps = Dependencies::new();
+	let io = deps.default_client();
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_minGasPricePercentile", "params": [], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":{"percentile":50,"blocks":100,"blockNumber":42,"transactions":1000,"gasPrice":"0x1312d00"},"id":1}"#;
+
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+}
+
 #[test]
 fn rpc_parity_dev_logs() {
 	let deps = Dependencies::new();
@@ -531,6 +542,47 @@ fn rpc_parity_node_kind() {
 	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_parity_finalized_block() {
+	use ethcore::client::{BlockChainClient, EachBlockWith};
+	use ethcore::ids::BlockId;
+
+	let deps = Dependencies::new();
+	deps.client.add_blocks(2, EachBlockWith::Nothing);
+	let io = deps.default_client();
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_getFinalizedBlock", "params":[], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":null,"id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+
+	let finalized = deps.client.block_hash(BlockId::Number(1)).unwrap();
+	*deps.client.finalized_block.write() = Some(finalized);
+
+	let response = io.handle_request_sync(request).unwrap();
+	assert!(response.contains(&format!(r#""hash":"0x{:x}""#, finalized)), "Should return the finalized block header.");
+	assert!(response.contains(r#""number":"0x1""#), "Should return the finalized block header.");
+}
+
+#[test]
+fn rpc_parity_validator_reports() {
+	use ethcore::validator_report::{ValidatorReport, ReportKind};
+
+	let deps = Dependencies::new();
+	let io = deps.default_client();
+
+	deps.client.validator_reports.write().push(ValidatorReport {
+		validator: Address::from(5),
+		block: 10,
+		kind: ReportKind::Benign,
+		transaction: Some(H256::from(1)),
+	});
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_validatorReports", "params":[], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":[{"blockNumber":"0xa","kind":"benign","proof":null,"transactionHash":"0x0000000000000000000000000000000000000000000000000000000000000001","validator":"0x0000000000000000000000000000000000000005"}],"id":1}"#;
+
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+}
+
 #[test]
 fn rpc_parity_cid() {
 	let deps = Dependencies::new();
@@ -579,6 +631,45 @@ fn rpc_parity_call() {
 	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_parity_simulate_bundle() {
+	use rlp;
+	use rustc_hex::ToHex;
+	use transaction::{Action, Transaction};
+
+	let deps = Dependencies::new();
+	deps.client.set_execution_result(Ok(Executed {
+		exception: None,
+		gas: U256::from(0x76c0),
+		gas_used: U256::from(0x5208),
+		refunded: U256::zero(),
+		cumulative_gas_used: U256::from(0x5208),
+		logs: vec![],
+		contracts_created: vec![],
+		output: vec![0x12, 0x34, 0xff],
+		trace: vec![],
+		vm_trace: None,
+		state_diff: None,
+	}));
+	let io = deps.default_client();
+
+	let keypair = Random.generate().unwrap();
+	let tx = Transaction {
+		nonce: 0.into(),
+		gas_price: 0x9184e72a000u64.into(),
+		gas: 0x76c0.into(),
+		action: Action::Call(5.into()),
+		value: 0x9184e72au64.into(),
+		data: vec![]
+	}.sign(keypair.secret(), None);
+	let raw = rlp::encode(&*tx).into_vec().to_hex();
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_simulateBundle", "params": [["0x"#.to_owned() + &raw + r#""], [], "latest"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":[{"transactionHash":""#.to_owned() + &format!("0x{:x}", tx.hash()) + r#"","status":"0x1","gasUsed":"0x5208","cumulativeGasUsed":"0x5208","logs":[],"output":"0x1234ff","trace":[],"vmTrace":null,"stateDiff":null}],"id":1}"#;
+
+	assert_eq!(io.handle_request_sync(&request), Some(response));
+}
+
 #[test]
 fn rpc_parity_node_health() {
 	let deps = Dependencies::new();
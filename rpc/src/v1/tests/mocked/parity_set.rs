@@ -255,3 +255,54 @@ fn rpc_parity_set_dapps_list() {
 
 	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
 }
+
+#[test]
+fn rpc_parity_send_bundle() {
+	use ethkey::{Generator, Random};
+	use rlp;
+	use rustc_hex::ToHex;
+	use transaction::{Transaction, Action};
+
+	let miner = miner_service();
+	let client = client_service();
+	let network = network_service();
+	let updater = updater_service();
+	let mut io = IoHandler::new();
+	io.extend_with(parity_set_client(&client, &miner, &updater, &network).to_delegate());
+
+	let keypair = Random.generate().unwrap();
+	let tx = Transaction {
+		nonce: 0.into(),
+		gas_price: 0x9184e72a000u64.into(),
+		gas: 0x76c0.into(),
+		action: Action::Call(5.into()),
+		value: 0x9184e72au64.into(),
+		data: vec![]
+	}.sign(keypair.secret(), None);
+	let raw = rlp::encode(&*tx).into_vec().to_hex();
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_sendBundle", "params":[["0x"#.to_owned() + &raw + r#""], "0x5"], "id": 1}"#;
+	let response = io.handle_request_sync(&request).unwrap();
+
+	let bundles = miner.bundles.lock();
+	assert_eq!(bundles.len(), 1);
+	assert_eq!(bundles[0].transactions, vec![tx]);
+	assert_eq!(bundles[0].target_block, 5);
+	assert_eq!(response, format!(r#"{{"jsonrpc":"2.0","result":"0x{:x}","id":1}}"#, bundles[0].hash));
+}
+
+#[test]
+fn rpc_parity_send_bundle_rejects_mined_target_block() {
+	let miner = miner_service();
+	let client = client_service();
+	let network = network_service();
+	let updater = updater_service();
+	let mut io = IoHandler::new();
+	io.extend_with(parity_set_client(&client, &miner, &updater, &network).to_delegate());
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_sendBundle", "params":[[], "0x0"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: targetBlock","data":"\"Target block has already been mined.\""},"id":1}"#;
+
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+	assert!(miner.bundles.lock().is_empty());
+}
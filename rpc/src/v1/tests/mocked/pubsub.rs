@@ -76,3 +76,124 @@ fn should_subscribe_to_a_method() {
 	assert_eq!(res, None);
 }
 
+
+#[test]
+fn should_subscribe_to_state_diffs() {
+	use std::time::Duration;
+	use ethcore::client::{TestBlockChainClient, EachBlockWith, Executed, ChainNotify, ChainRoute, ChainRouteType, BlockInfo, BlockId};
+	use ethcore::state_diff::StateDiff;
+
+	// given
+	let el = EventLoop::spawn();
+	let mut client = TestBlockChainClient::new();
+	client.add_blocks(1, EachBlockWith::Transaction);
+	let h1 = client.block_hash_delta_minus(1);
+	let tx_hash = client.block(BlockId::Hash(h1)).unwrap().transactions()[0].hash();
+	client.set_execution_result(Ok(Executed {
+		exception: None,
+		gas: 20_000.into(),
+		gas_used: 10_000.into(),
+		refunded: 0.into(),
+		cumulative_gas_used: 10_000.into(),
+		logs: vec![],
+		contracts_created: vec![],
+		output: vec![],
+		trace: vec![],
+		vm_trace: None,
+		state_diff: Some(StateDiff { raw: Default::default() }),
+	}));
+
+	let mut pubsub = PubSubClient::new_test(rpc(), el.remote());
+	let handler = pubsub.state_diffs_handler_test(Arc::new(client));
+
+	let mut io = MetaIoHandler::default();
+	io.extend_with(pubsub.to_delegate());
+
+	let mut metadata = Metadata::default();
+	let (sender, receiver) = futures::sync::mpsc::channel(8);
+	metadata.session = Some(Arc::new(Session::new(sender)));
+
+	// Subscribe
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_subscribe", "params": ["stateDiffs"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":"0x416d77337e24399d","id":1}"#;
+	assert_eq!(io.handle_request_sync(request, metadata.clone()), Some(response.to_owned()));
+
+	// Retract and re-enact the block
+	let route = ChainRoute::new(vec![(h1, ChainRouteType::Retracted), (h1, ChainRouteType::Enacted)]);
+	handler.new_blocks(vec![], vec![], route, vec![], vec![], Duration::from_millis(0));
+
+	let notification = |removed: bool| format!(
+		r#"{{"jsonrpc":"2.0","method":"parity_subscription","params":{{"result":{{"blockHash":"{:#x}","blockNumber":"0x1","removed":{},"transactions":[{{"stateDiff":{{}},"transactionHash":"{:#x}"}}]}},"subscription":"0x416d77337e24399d"}}}}"#,
+		h1, removed, tx_hash,
+	);
+
+	let (res, receiver) = receiver.into_future().wait().unwrap();
+	assert_eq!(res, Some(notification(true)));
+	let (res, receiver) = receiver.into_future().wait().unwrap();
+	assert_eq!(res, Some(notification(false)));
+
+	// And unsubscribe
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_unsubscribe", "params": ["0x416d77337e24399d"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":true,"id":1}"#;
+	assert_eq!(io.handle_request_sync(request, metadata), Some(response.to_owned()));
+
+	let (res, _receiver) = receiver.into_future().wait().unwrap();
+	assert_eq!(res, None);
+}
+
+#[test]
+fn should_subscribe_to_transaction_pool_events() {
+	use miner::pool::TransactionEvent;
+
+	// given
+	let el = EventLoop::spawn();
+	let mut pubsub = PubSubClient::new_test(rpc(), el.remote());
+	let handler = pubsub.transaction_pool_events_handler_test();
+
+	let mut io = MetaIoHandler::default();
+	io.extend_with(pubsub.to_delegate());
+
+	let mut metadata = Metadata::default();
+	let (sender, receiver) = futures::sync::mpsc::channel(8);
+	metadata.session = Some(Arc::new(Session::new(sender)));
+
+	// Subscribe
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_subscribe", "params": ["parity_transactionPoolEvents"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":"0x416d77337e24399d","id":1}"#;
+	assert_eq!(io.handle_request_sync(request, metadata.clone()), Some(response.to_owned()));
+
+	// Check notifications
+	handler.upgrade().unwrap().notify(&[
+		(5.into(), TransactionEvent::Added),
+		(5.into(), TransactionEvent::Dropped(Some(10.into()))),
+	]);
+	let (res, receiver) = receiver.into_future().wait().unwrap();
+	let response = r#"{"jsonrpc":"2.0","method":"parity_subscription","params":{"result":[{"event":"added","hash":"0x0000000000000000000000000000000000000000000000000000000000000005"},{"by":"0x000000000000000000000000000000000000000000000000000000000000000a","event":"dropped","hash":"0x0000000000000000000000000000000000000000000000000000000000000005"}],"subscription":"0x416d77337e24399d"}}"#;
+	assert_eq!(res, Some(response.into()));
+
+	// And unsubscribe
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_unsubscribe", "params": ["0x416d77337e24399d"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":true,"id":1}"#;
+	assert_eq!(io.handle_request_sync(request, metadata), Some(response.to_owned()));
+
+	let (res, _receiver) = receiver.into_future().wait().unwrap();
+	assert_eq!(res, None);
+}
+
+#[test]
+fn should_reject_state_diffs_without_chain_notifications() {
+	// given
+	let el = EventLoop::spawn();
+	let pubsub = PubSubClient::new_test(rpc(), el.remote()).to_delegate();
+
+	let mut io = MetaIoHandler::default();
+	io.extend_with(pubsub);
+
+	let mut metadata = Metadata::default();
+	let (sender, _receiver) = futures::sync::mpsc::channel(8);
+	metadata.session = Some(Arc::new(Session::new(sender)));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_subscribe", "params": ["stateDiffs"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32000,"message":"This request is not implemented yet. Please create an issue on Github repo.","data":"State diff subscriptions are only available on full nodes."},"id":1}"#;
+	assert_eq!(io.handle_request_sync(request, metadata), Some(response.to_owned()));
+}
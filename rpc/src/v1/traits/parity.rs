@@ -24,11 +24,12 @@ use jsonrpc_macros::Trailing;
 use node_health::Health;
 use v1::types::{
 	H160, H256, H512, U256, U64, Bytes, CallRequest,
-	Peers, Transaction, RpcSettings, Histogram,
+	Peers, Transaction, RpcSettings, Histogram, GasPricePercentile,
 	TransactionStats, LocalTransactionStatus,
 	BlockNumber, ConsensusCapability, VersionInfo,
 	OperationsInfo, DappId, ChainStatus,
 	AccountInfo, HwAccountInfo, RichHeader,
+	SimulatedTransaction, TraceOptions, ValidatorReport,
 };
 
 build_rpc_trait! {
@@ -72,6 +73,11 @@ build_rpc_trait! {
 		#[rpc(name = "parity_minGasPrice")]
 		fn min_gas_price(&self) -> Result<U256>;
 
+		/// Returns minimal gas price learned from transactions included in recent blocks along with
+		/// the inputs it was computed from, or `null` if the minimal gas price is not learned from recent blocks.
+		#[rpc(name = "parity_minGasPricePercentile")]
+		fn min_gas_price_percentile(&self) -> Result<Option<GasPricePercentile>>;
+
 		/// Returns latest logs
 		#[rpc(name = "parity_devLogs")]
 		fn dev_logs(&self) -> Result<Vec<String>>;
@@ -211,11 +217,20 @@ build_rpc_trait! {
 		#[rpc(name = "parity_nodeKind")]
 		fn node_kind(&self) -> Result<::v1::types::NodeKind>;
 
+		/// Returns validator misbehaviour reports sent by this node, oldest first.
+		#[rpc(name = "parity_validatorReports")]
+		fn validator_reports(&self) -> Result<Vec<ValidatorReport>>;
+
 		/// Get block header.
 		/// Same as `eth_getBlockByNumber` but without uncles and transactions.
 		#[rpc(name = "parity_getBlockHeaderByNumber")]
 		fn block_header(&self, Trailing<BlockNumber>) -> BoxFuture<RichHeader>;
 
+		/// Get the header of the latest finalized block, if any.
+		/// On chains without finality only blocks with the configured number of confirmations are final.
+		#[rpc(name = "parity_getFinalizedBlock")]
+		fn finalized_block(&self) -> Result<Option<RichHeader>>;
+
 		/// Get IPFS CIDv0 given protobuf encoded bytes.
 		#[rpc(name = "parity_cidV0")]
 		fn ipfs_cid(&self, Bytes) -> Result<String>;
@@ -224,6 +239,11 @@ build_rpc_trait! {
 		#[rpc(meta, name = "parity_call")]
 		fn call(&self, Self::Metadata, Vec<CallRequest>, Trailing<BlockNumber>) -> Result<Vec<Bytes>>;
 
+		/// Simulates signed transactions applied in order on top of the given block, e.g. `pending`,
+		/// without importing them. Returns per-transaction receipts and the requested traces.
+		#[rpc(name = "parity_simulateBundle")]
+		fn simulate_bundle(&self, Vec<Bytes>, TraceOptions, Trailing<BlockNumber>) -> Result<Vec<SimulatedTransaction>>;
+
 		/// Returns node's health report.
 		#[rpc(name = "parity_nodeHealth")]
 		fn node_health(&self) -> BoxFuture<Health>;
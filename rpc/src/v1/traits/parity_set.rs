@@ -18,7 +18,7 @@
 
 use jsonrpc_core::{BoxFuture, Result};
 
-use v1::types::{Bytes, H160, H256, U256, ReleaseInfo, Transaction, LocalDapp};
+use v1::types::{Bytes, H160, H256, U64, U256, ReleaseInfo, Transaction, LocalDapp};
 
 build_rpc_trait! {
 	/// Parity-specific rpc interface for operations altering the settings.
@@ -119,5 +119,13 @@ build_rpc_trait! {
 		/// Returns `true` when transaction was removed, `false` if it was not found.
 		#[rpc(name = "parity_removeTransaction")]
 		fn remove_transaction(&self, H256) -> Result<Option<Transaction>>;
+
+		/// Submits a bundle of signed transactions to be included in authored blocks
+		/// as a unit, in the given order. The bundle is included only if all of its
+		/// transactions succeed and is discarded after the target block number.
+		/// Bundles are only included in blocks with status code receipts (EIP-658).
+		/// Returns the hash of the bundle.
+		#[rpc(name = "parity_sendBundle")]
+		fn send_bundle(&self, Vec<Bytes>, U64) -> Result<H256>;
 	}
 }
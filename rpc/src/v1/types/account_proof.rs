@@ -0,0 +1,77 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Merkle proofs of account state (EIP-1186).
+
+use v1::types::{Bytes, H160, H256, U256};
+
+/// Account state with Merkle proofs of the account and the requested storage slots.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+#[serde(rename_all="camelCase")]
+pub struct AccountProof {
+	/// Address of the account.
+	pub address: H160,
+	/// Balance of the account.
+	pub balance: U256,
+	/// Nonce of the account.
+	pub nonce: U256,
+	/// Hash of the account's code.
+	pub code_hash: H256,
+	/// Root of the account's storage trie.
+	pub storage_hash: H256,
+	/// State trie nodes on the path from the state root to the account, in order from the root.
+	pub account_proof: Vec<Bytes>,
+	/// Proofs of the requested storage slots.
+	pub storage_proof: Vec<StorageProof>,
+}
+
+/// Value of a storage slot with a Merkle proof.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct StorageProof {
+	/// Storage key.
+	pub key: U256,
+	/// Storage value.
+	pub value: U256,
+	/// Storage trie nodes on the path from the storage root to the value, in order from the root.
+	pub proof: Vec<Bytes>,
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use v1::types::Bytes;
+	use super::{AccountProof, StorageProof};
+
+	#[test]
+	fn account_proof_serialization() {
+		let proof = AccountProof {
+			address: 1.into(),
+			balance: 2.into(),
+			nonce: 3.into(),
+			code_hash: 4.into(),
+			storage_hash: 5.into(),
+			account_proof: vec![Bytes::new(vec![0x80])],
+			storage_proof: vec![StorageProof {
+				key: 6.into(),
+				value: 7.into(),
+				proof: vec![],
+			}],
+		};
+
+		let serialized = serde_json::to_string(&proof).unwrap();
+		assert_eq!(serialized, r#"{"address":"0x0000000000000000000000000000000000000001","balance":"0x2","nonce":"0x3","codeHash":"0x0000000000000000000000000000000000000000000000000000000000000004","storageHash":"0x0000000000000000000000000000000000000000000000000000000000000005","accountProof":["0x80"],"storageProof":[{"key":"0x6","value":"0x7","proof":[]}]}"#);
+	}
+}
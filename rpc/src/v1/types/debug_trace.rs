@@ -0,0 +1,169 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Geth-compatible struct log traces.
+
+use std::collections::BTreeMap;
+use ethcore::client::Executed;
+use ethcore::trace::{FlatTrace, StructLog as EthStructLog, StructLoggerConfig};
+use ethereum_types::{H256, U256};
+use rustc_hex::ToHex;
+
+/// Options of `debug_traceTransaction` and `debug_traceCall`.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DebugTraceOptions {
+	/// Don't capture the stack.
+	#[serde(default)]
+	pub disable_stack: bool,
+	/// Don't capture the memory.
+	#[serde(default)]
+	pub disable_memory: bool,
+	/// Don't capture the storage.
+	#[serde(default)]
+	pub disable_storage: bool,
+	/// Custom JavaScript tracer. Not supported.
+	pub tracer: Option<String>,
+}
+
+impl From<DebugTraceOptions> for StructLoggerConfig {
+	fn from(options: DebugTraceOptions) -> Self {
+		StructLoggerConfig {
+			disable_stack: options.disable_stack,
+			disable_memory: options.disable_memory,
+			disable_storage: options.disable_storage,
+		}
+	}
+}
+
+/// Machine state before executing a single instruction.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLog {
+	/// Program counter.
+	pub pc: u64,
+	/// Instruction mnemonic.
+	pub op: String,
+	/// Gas remaining.
+	pub gas: u64,
+	/// Gas cost of the instruction.
+	pub gas_cost: u64,
+	/// Call depth.
+	pub depth: u64,
+	/// Stack words, bottom first.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub stack: Option<Vec<String>>,
+	/// Memory words.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub memory: Option<Vec<String>>,
+	/// Written storage slots.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub storage: Option<BTreeMap<String, String>>,
+}
+
+fn word_to_hex(word: U256) -> String {
+	H256::from(word).to_hex()
+}
+
+impl From<EthStructLog> for StructLog {
+	fn from(log: EthStructLog) -> Self {
+		StructLog {
+			pc: log.pc as u64,
+			op: log.op.into(),
+			gas: log.gas.low_u64(),
+			gas_cost: log.gas_cost.low_u64(),
+			depth: log.depth as u64,
+			stack: log.stack.map(|stack| stack.into_iter().map(word_to_hex).collect()),
+			memory: log.memory.map(|memory| memory.chunks(32).map(|word| word.to_hex()).collect()),
+			storage: log.storage.map(|storage| storage.into_iter()
+				.map(|(key, value)| (word_to_hex(key), word_to_hex(value)))
+				.collect()
+			),
+		}
+	}
+}
+
+/// Result of `debug_traceTransaction` and `debug_traceCall`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DebugTraceResult {
+	/// Gas used.
+	pub gas: u64,
+	/// Whether the execution ended with an exception.
+	pub failed: bool,
+	/// Output of the call, without `0x` prefix.
+	pub return_value: String,
+	/// Per-instruction logs.
+	pub struct_logs: Vec<StructLog>,
+}
+
+impl From<Executed<FlatTrace, Vec<EthStructLog>>> for DebugTraceResult {
+	fn from(executed: Executed<FlatTrace, Vec<EthStructLog>>) -> Self {
+		DebugTraceResult {
+			gas: executed.gas_used.low_u64(),
+			failed: executed.exception.is_some(),
+			return_value: executed.output.to_hex(),
+			struct_logs: executed.vm_trace.unwrap_or_default().into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use std::collections::BTreeMap;
+	use ethcore::trace::StructLog as EthStructLog;
+	use super::{DebugTraceOptions, DebugTraceResult, StructLog};
+
+	#[test]
+	fn should_deserialize_options() {
+		let s = r#"{"disableStack":true,"disableStorage":true}"#;
+		let deserialized: DebugTraceOptions = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized, DebugTraceOptions {
+			disable_stack: true,
+			disable_memory: false,
+			disable_storage: true,
+			tracer: None,
+		});
+	}
+
+	#[test]
+	fn should_serialize_struct_logs() {
+		let mut storage = BTreeMap::new();
+		storage.insert(1.into(), 42.into());
+
+		let log = EthStructLog {
+			pc: 9,
+			op: "SSTORE",
+			gas: 82.into(),
+			gas_cost: 20000.into(),
+			depth: 1,
+			stack: Some(vec![42.into(), 1.into()]),
+			memory: None,
+			storage: Some(storage),
+		};
+
+		let result = DebugTraceResult {
+			gas: 21000,
+			failed: false,
+			return_value: "".into(),
+			struct_logs: vec![StructLog::from(log)],
+		};
+
+		let serialized = serde_json::to_string(&result).unwrap();
+		assert_eq!(serialized, r#"{"gas":21000,"failed":false,"returnValue":"","structLogs":[{"pc":9,"op":"SSTORE","gas":82,"gasCost":20000,"depth":1,"stack":["000000000000000000000000000000000000000000000000000000000000002a","0000000000000000000000000000000000000000000000000000000000000001"],"storage":{"0000000000000000000000000000000000000000000000000000000000000001":"000000000000000000000000000000000000000000000000000000000000002a"}}]}"#);
+	}
+}
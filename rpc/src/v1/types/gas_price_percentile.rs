@@ -0,0 +1,49 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Minimal gas price learned from recent blocks.
+
+use miner::gas_pricer::GasPricePercentileStatus;
+use v1::types::U256;
+
+/// Minimal gas price learned from transactions included in recent blocks, along with its inputs.
+#[derive(Debug, Serialize)]
+pub struct GasPricePercentile {
+	/// Percentile of gas prices of included transactions used.
+	pub percentile: usize,
+	/// Number of recent blocks sampled.
+	pub blocks: usize,
+	/// Number of the latest block sampled.
+	#[serde(rename="blockNumber")]
+	pub block_number: u64,
+	/// Number of transactions sampled.
+	pub transactions: usize,
+	/// Resulting minimal gas price.
+	#[serde(rename="gasPrice")]
+	pub gas_price: U256,
+}
+
+impl From<GasPricePercentileStatus> for GasPricePercentile {
+	fn from(s: GasPricePercentileStatus) -> Self {
+		GasPricePercentile {
+			percentile: s.percentile,
+			blocks: s.blocks,
+			block_number: s.block_number,
+			transactions: s.transactions,
+			gas_price: s.gas_price.into(),
+		}
+	}
+}
@@ -18,6 +18,7 @@
 //! RPC types
 
 mod account_info;
+mod account_proof;
 mod block;
 mod block_number;
 mod bytes;
@@ -25,8 +26,10 @@ mod call_request;
 mod confirmations;
 mod consensus_status;
 mod dapps;
+mod debug_trace;
 mod derivation;
 mod filter;
+mod gas_price_percentile;
 mod hash;
 mod histogram;
 mod index;
@@ -36,6 +39,7 @@ mod provenance;
 mod receipt;
 mod rpc_settings;
 mod secretstore;
+mod simulation;
 mod sync;
 mod trace;
 mod trace_filter;
@@ -43,12 +47,14 @@ mod transaction;
 mod transaction_request;
 mod transaction_condition;
 mod uint;
+mod validator_report;
 mod work;
 mod private_receipt;
 
 pub mod pubsub;
 
 pub use self::account_info::{AccountInfo, ExtAccountInfo, HwAccountInfo};
+pub use self::account_proof::{AccountProof, StorageProof};
 pub use self::bytes::Bytes;
 pub use self::block::{RichBlock, Block, BlockTransactions, Header, RichHeader, Rich};
 pub use self::block_number::{BlockNumber, block_number_to_id};
@@ -59,8 +65,10 @@ pub use self::confirmations::{
 };
 pub use self::consensus_status::*;
 pub use self::dapps::LocalDapp;
+pub use self::debug_trace::{DebugTraceOptions, DebugTraceResult, StructLog};
 pub use self::derivation::{DeriveHash, DeriveHierarchical, Derive};
 pub use self::filter::{Filter, FilterChanges};
+pub use self::gas_price_percentile::GasPricePercentile;
 pub use self::hash::{H64, H160, H256, H512, H520, H2048};
 pub use self::histogram::Histogram;
 pub use self::index::Index;
@@ -70,16 +78,18 @@ pub use self::provenance::{Origin, DappId};
 pub use self::receipt::Receipt;
 pub use self::rpc_settings::RpcSettings;
 pub use self::secretstore::EncryptedDocumentKey;
+pub use self::simulation::SimulatedTransaction;
 pub use self::sync::{
 	SyncStatus, SyncInfo, Peers, PeerInfo, PeerNetworkInfo, PeerProtocolsInfo,
 	TransactionStats, ChainStatus, EthProtocolInfo, PipProtocolInfo,
 };
-pub use self::trace::{LocalizedTrace, TraceResults};
+pub use self::trace::{LocalizedTrace, TraceResults, BlockStateDiff, TransactionStateDiff};
 pub use self::trace_filter::TraceFilter;
-pub use self::transaction::{Transaction, RichRawTransaction, LocalTransactionStatus};
+pub use self::transaction::{Transaction, RichRawTransaction, LocalTransactionStatus, TransactionPoolEvent};
 pub use self::transaction_request::TransactionRequest;
 pub use self::transaction_condition::TransactionCondition;
 pub use self::uint::{U128, U256, U64};
+pub use self::validator_report::ValidatorReport;
 pub use self::work::Work;
 pub use self::private_receipt::{PrivateTransactionReceipt, PrivateTransactionReceiptAndTransaction};
 
@@ -0,0 +1,102 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+use ethcore::client::Executed;
+use v1::types::{Bytes, Log, H256, U256, U64};
+use super::trace::{Trace, VMTrace, StateDiff};
+
+/// Result of a transaction simulated on top of a block.
+#[derive(Debug, Serialize)]
+pub struct SimulatedTransaction {
+	/// Transaction hash
+	#[serde(rename="transactionHash")]
+	pub transaction_hash: H256,
+	/// Status code, `0x1` if the execution succeeded
+	pub status: U64,
+	/// Gas used by the transaction
+	#[serde(rename="gasUsed")]
+	pub gas_used: U256,
+	/// Cumulative gas used in the block after this transaction
+	#[serde(rename="cumulativeGasUsed")]
+	pub cumulative_gas_used: U256,
+	/// Logs
+	pub logs: Vec<Log>,
+	/// The output of the call/create
+	pub output: Bytes,
+	/// The transaction trace
+	pub trace: Vec<Trace>,
+	/// The VM trace
+	#[serde(rename="vmTrace")]
+	pub vm_trace: Option<VMTrace>,
+	/// The state diff
+	#[serde(rename="stateDiff")]
+	pub state_diff: Option<StateDiff>,
+}
+
+impl SimulatedTransaction {
+	/// Convert execution results of a transaction with given hash.
+	pub fn new(transaction_hash: H256, executed: Executed) -> Self {
+		SimulatedTransaction {
+			transaction_hash: transaction_hash,
+			status: if executed.exception.is_none() { 1.into() } else { 0.into() },
+			gas_used: executed.gas_used.into(),
+			cumulative_gas_used: executed.cumulative_gas_used.into(),
+			logs: executed.logs.into_iter().enumerate().map(|(index, entry)| {
+				let mut log = Log::from(entry);
+				log.transaction_hash = Some(transaction_hash);
+				log.transaction_log_index = Some(index.into());
+				log
+			}).collect(),
+			output: executed.output.into(),
+			trace: executed.trace.into_iter().map(Into::into).collect(),
+			vm_trace: executed.vm_trace.map(Into::into),
+			state_diff: executed.state_diff.map(Into::into),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use ethcore::client::Executed;
+	use ethcore::log_entry::LogEntry;
+	use super::SimulatedTransaction;
+
+	#[test]
+	fn simulated_transaction_serialization() {
+		let executed = Executed {
+			exception: None,
+			gas: 50_000.into(),
+			gas_used: 21_000.into(),
+			refunded: 29_000.into(),
+			cumulative_gas_used: 42_000.into(),
+			logs: vec![LogEntry {
+				address: 5.into(),
+				topics: vec![],
+				data: vec![1],
+			}],
+			contracts_created: vec![],
+			output: vec![],
+			trace: vec![],
+			vm_trace: None,
+			state_diff: None,
+		};
+
+		let simulated = SimulatedTransaction::new(1.into(), executed);
+		let serialized = serde_json::to_string(&simulated).unwrap();
+		assert_eq!(serialized, r#"{"transactionHash":"0x0000000000000000000000000000000000000000000000000000000000000001","status":"0x1","gasUsed":"0x5208","cumulativeGasUsed":"0xa410","logs":[{"address":"0x0000000000000000000000000000000000000005","topics":[],"data":"0x01","blockHash":null,"blockNumber":null,"transactionHash":"0x0000000000000000000000000000000000000000000000000000000000000001","transactionIndex":null,"logIndex":null,"transactionLogIndex":"0x0","type":"pending"}],"output":"0x","trace":[],"vmTrace":null,"stateDiff":null}"#);
+	}
+}
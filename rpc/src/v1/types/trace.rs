@@ -632,6 +632,32 @@ impl From<Executed> for TraceResults {
 	}
 }
 
+#[derive(Debug, Serialize)]
+/// State changes of a single transaction.
+pub struct TransactionStateDiff {
+	/// Transaction hash.
+	#[serde(rename="transactionHash")]
+	pub transaction_hash: H256,
+	/// The state diff.
+	#[serde(rename="stateDiff")]
+	pub state_diff: Option<StateDiff>,
+}
+
+#[derive(Debug, Serialize)]
+/// State changes of all transactions in a block.
+pub struct BlockStateDiff {
+	/// Block hash.
+	#[serde(rename="blockHash")]
+	pub block_hash: H256,
+	/// Block number.
+	#[serde(rename="blockNumber")]
+	pub block_number: U256,
+	/// Whether the block was retracted by a reorganization and its changes reverted.
+	pub removed: bool,
+	/// Per-transaction state diffs, in block order.
+	pub transactions: Vec<TransactionStateDiff>,
+}
+
 #[cfg(test)]
 mod tests {
 	use serde_json;
@@ -73,6 +73,9 @@ pub struct Transaction {
 	pub s: U256,
 	/// Transaction activates at specified block.
 	pub condition: Option<TransactionCondition>,
+	/// Whether the transaction was restored from a dump of the pool made on shutdown.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub restored: Option<bool>,
 }
 
 /// Local Transaction Status
@@ -96,6 +99,21 @@ pub enum LocalTransactionStatus {
 	Canceled(Transaction),
 }
 
+/// Event in the life of a transaction in the pool.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TransactionPoolEvent {
+	/// Transaction hash
+	pub hash: H256,
+	/// Event: `added`, `ready`, `replaced`, `dropped`, `rejected`, `invalid`, `canceled` or `culled`
+	pub event: &'static str,
+	/// Hash of the transaction which replaced or pushed out this one
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub by: Option<H256>,
+	/// Reason of the rejection
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+}
+
 impl Serialize for LocalTransactionStatus {
 	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 		where S: Serializer
@@ -202,6 +220,7 @@ impl Transaction {
 			r: signature.r().into(),
 			s: signature.s().into(),
 			condition: None,
+			restored: None,
 		}
 	}
 
@@ -236,6 +255,7 @@ impl Transaction {
 			r: signature.r().into(),
 			s: signature.s().into(),
 			condition: None,
+			restored: None,
 		}
 	}
 
@@ -270,9 +290,35 @@ impl LocalTransactionStatus {
 	}
 }
 
+impl TransactionPoolEvent {
+	/// Convert pool `TransactionEvent` of transaction with given hash into RPC `TransactionPoolEvent`.
+	pub fn new(hash: H256, event: miner::pool::TransactionEvent) -> Self {
+		use miner::pool::TransactionEvent::*;
+
+		let (event, by, error) = match event {
+			Added => ("added", None, None),
+			Ready => ("ready", None, None),
+			Replaced(by) => ("replaced", Some(by.into()), None),
+			Dropped(by) => ("dropped", by.map(Into::into), None),
+			Rejected(error) => ("rejected", None, Some(error)),
+			Invalid => ("invalid", None, None),
+			Canceled => ("canceled", None, None),
+			Culled => ("culled", None, None),
+		};
+
+		TransactionPoolEvent {
+			hash,
+			event,
+			by,
+			error,
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
-	use super::{Transaction, LocalTransactionStatus};
+	use super::{Transaction, LocalTransactionStatus, TransactionPoolEvent};
+	use miner::pool::TransactionEvent;
 	use serde_json;
 
 	#[test]
@@ -282,6 +328,14 @@ mod tests {
 		assert_eq!(serialized, r#"{"hash":"0x0000000000000000000000000000000000000000000000000000000000000000","nonce":"0x0","blockHash":null,"blockNumber":null,"transactionIndex":null,"from":"0x0000000000000000000000000000000000000000","to":null,"value":"0x0","gasPrice":"0x0","gas":"0x0","input":"0x","creates":null,"raw":"0x","publicKey":null,"chainId":null,"standardV":"0x0","v":"0x0","r":"0x0","s":"0x0","condition":null}"#);
 	}
 
+	#[test]
+	fn test_restored_transaction_serialize() {
+		let mut t = Transaction::default();
+		t.restored = Some(true);
+		let serialized = serde_json::to_string(&t).unwrap();
+		assert!(serialized.ends_with(r#""condition":null,"restored":true}"#));
+	}
+
 	#[test]
 	fn test_local_transaction_status_serialize() {
 		let tx_ser = serde_json::to_string(&Transaction::default()).unwrap();
@@ -326,5 +380,24 @@ mod tests {
 			r#","hash":"0x000000000000000000000000000000000000000000000000000000000000000a","gasPrice":"0x5"}"#
 		);
 	}
-}
 
+	#[test]
+	fn test_transaction_pool_event_serialize() {
+		let added = TransactionPoolEvent::new(5.into(), TransactionEvent::Added);
+		let replaced = TransactionPoolEvent::new(5.into(), TransactionEvent::Replaced(10.into()));
+		let rejected = TransactionPoolEvent::new(5.into(), TransactionEvent::Rejected("Just because".into()));
+
+		assert_eq!(
+			serde_json::to_string(&added).unwrap(),
+			r#"{"hash":"0x0000000000000000000000000000000000000000000000000000000000000005","event":"added"}"#
+		);
+		assert_eq!(
+			serde_json::to_string(&replaced).unwrap(),
+			r#"{"hash":"0x0000000000000000000000000000000000000000000000000000000000000005","event":"replaced","by":"0x000000000000000000000000000000000000000000000000000000000000000a"}"#
+		);
+		assert_eq!(
+			serde_json::to_string(&rejected).unwrap(),
+			r#"{"hash":"0x0000000000000000000000000000000000000000000000000000000000000005","event":"rejected","error":"Just because"}"#
+		);
+	}
+}
@@ -0,0 +1,72 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+use ethcore::validator_report::{self, ReportKind};
+use v1::types::{Bytes, H160, H256, U64};
+
+/// Validator misbehaviour report sent by this node.
+#[derive(Debug, Serialize)]
+pub struct ValidatorReport {
+	/// Reported validator
+	pub validator: H160,
+	/// Block at which the misbehaviour was detected
+	#[serde(rename="blockNumber")]
+	pub block_number: U64,
+	/// Whether the misbehaviour was `benign` or `malicious`
+	pub kind: String,
+	/// Proof of malicious misbehaviour
+	pub proof: Option<Bytes>,
+	/// Hash of the reporting transaction, `null` if it couldn't be submitted
+	#[serde(rename="transactionHash")]
+	pub transaction_hash: Option<H256>,
+}
+
+impl From<validator_report::ValidatorReport> for ValidatorReport {
+	fn from(r: validator_report::ValidatorReport) -> Self {
+		let (kind, proof) = match r.kind {
+			ReportKind::Benign => ("benign", None),
+			ReportKind::Malicious(proof) => ("malicious", Some(proof.into())),
+		};
+
+		ValidatorReport {
+			validator: r.validator.into(),
+			block_number: r.block.into(),
+			kind: kind.into(),
+			proof: proof,
+			transaction_hash: r.transaction.map(Into::into),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use ethcore::validator_report::{self, ReportKind};
+	use super::ValidatorReport;
+
+	#[test]
+	fn validator_report_serialization() {
+		let report = ValidatorReport::from(validator_report::ValidatorReport {
+			validator: 5.into(),
+			block: 10,
+			kind: ReportKind::Malicious(vec![0xc0]),
+			transaction: None,
+		});
+
+		let serialized = serde_json::to_string(&report).unwrap();
+		assert_eq!(serialized, r#"{"validator":"0x0000000000000000000000000000000000000005","blockNumber":"0xa","kind":"malicious","proof":"0xc0","transactionHash":null}"#);
+	}
+}
@@ -23,6 +23,10 @@ pub struct Options {
 	pub max_per_sender: usize,
 	/// Maximal memory usage.
 	pub max_mem_usage: usize,
+	/// Minimal score increase, in percent, that `Scoring` should require
+	/// before replacing a transaction already in the pool.
+	/// `None` leaves it to the default of the `Scoring`.
+	pub replace_bump_percent: Option<usize>,
 }
 
 impl Default for Options {
@@ -31,6 +35,7 @@ impl Default for Options {
 			max_count: 1024,
 			max_per_sender: 16,
 			max_mem_usage: 8 * 1024 * 1024,
+			replace_bump_percent: None,
 		}
 	}
 }
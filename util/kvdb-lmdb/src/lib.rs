@@ -0,0 +1,539 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Key-Value store abstraction with `LMDB` backend.
+//!
+//! LMDB is a copy-on-write B+tree without background compaction, trading some
+//! write throughput for predictable read and write latency.
+
+#[macro_use]
+extern crate log;
+
+extern crate elastic_array;
+extern crate interleaved_ordered;
+extern crate lmdb;
+extern crate parking_lot;
+
+#[macro_use]
+extern crate kvdb;
+
+use std::collections::{HashMap, VecDeque};
+use std::path::{PathBuf, Path};
+use std::{fs, io, mem};
+
+use parking_lot::{Mutex, MutexGuard, RwLock};
+use lmdb::{Environment, Database as Table, DatabaseFlags, WriteFlags, Transaction, Cursor};
+use interleaved_ordered::{interleave_ordered, InterleaveOrdered};
+
+use elastic_array::ElasticArray32;
+use kvdb::{KeyValueDB, DBTransaction, DBValue, DBOp, Result};
+
+/// Default maximum size of the memory map. Pages are only allocated when written,
+/// so this only reserves address space.
+#[cfg(target_pointer_width = "64")]
+const DB_DEFAULT_MAP_SIZE: usize = 1024 * 1024 * 1024 * 1024;
+#[cfg(not(target_pointer_width = "64"))]
+const DB_DEFAULT_MAP_SIZE: usize = 1024 * 1024 * 1024;
+
+/// Number of entries read from disk by a single iteration step.
+const ITER_PAGE_SIZE: usize = 1024;
+
+enum KeyState {
+	Insert(DBValue),
+	Delete,
+}
+
+/// Database configuration
+#[derive(Clone)]
+pub struct DatabaseConfig {
+	/// Maximum size of the database in bytes.
+	pub map_size: usize,
+	/// Set number of columns
+	pub columns: Option<u32>,
+}
+
+impl DatabaseConfig {
+	/// Create new `DatabaseConfig` with default parameters and specified set of columns.
+	pub fn with_columns(columns: Option<u32>) -> Self {
+		let mut config = Self::default();
+		config.columns = columns;
+		config
+	}
+}
+
+impl Default for DatabaseConfig {
+	fn default() -> DatabaseConfig {
+		DatabaseConfig {
+			map_size: DB_DEFAULT_MAP_SIZE,
+			columns: None,
+		}
+	}
+}
+
+/// Iterator over flushed data, reading the database a page at a time so that
+/// no read transaction is held between calls to `next`.
+struct PagedIterator<'a> {
+	db: &'a Database,
+	col: Option<u32>,
+	start: Vec<u8>,
+	last: Option<Box<[u8]>>,
+	page: VecDeque<(Box<[u8]>, Box<[u8]>)>,
+	done: bool,
+}
+
+impl<'a> Iterator for PagedIterator<'a> {
+	type Item = (Box<[u8]>, Box<[u8]>);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.page.is_empty() && !self.done {
+			let page = match self.last {
+				Some(ref last) => self.db.read_page(self.col, last, true),
+				None => self.db.read_page(self.col, &self.start, false),
+			};
+
+			let page = page.unwrap_or_else(|e| {
+				warn!("Error iterating over database: {}", e);
+				Vec::new()
+			});
+
+			self.done = page.len() < ITER_PAGE_SIZE;
+			self.last = page.last().map(|&(ref k, _)| k.clone());
+			self.page.extend(page);
+		}
+
+		self.page.pop_front()
+	}
+}
+
+/// Database iterator (for flushed data only)
+pub struct DatabaseIterator<'a> {
+	iter: InterleaveOrdered<::std::vec::IntoIter<(Box<[u8]>, Box<[u8]>)>, PagedIterator<'a>>,
+}
+
+impl<'a> Iterator for DatabaseIterator<'a> {
+	type Item = (Box<[u8]>, Box<[u8]>);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next()
+	}
+}
+
+struct EnvAndColumns {
+	env: Environment,
+	// the default column is stored in a named table as well, since the unnamed
+	// table holds the names of all the others.
+	tables: Vec<Table>,
+}
+
+fn table_name(col: usize) -> String {
+	match col {
+		0 => "default".into(),
+		c => format!("col{}", c - 1),
+	}
+}
+
+/// Key-Value database.
+pub struct Database {
+	env: RwLock<Option<EnvAndColumns>>,
+	config: DatabaseConfig,
+	path: String,
+	// Dirty values added with `write_buffered`. Cleaned on `flush`.
+	overlay: RwLock<Vec<HashMap<ElasticArray32<u8>, KeyState>>>,
+	// Values currently being flushed. Cleared when `flush` completes.
+	flushing: RwLock<Vec<HashMap<ElasticArray32<u8>, KeyState>>>,
+	// Prevents concurrent flushes.
+	// Value indicates if a flush is in progress.
+	flushing_lock: Mutex<bool>,
+}
+
+impl Database {
+	/// Open database with default settings.
+	pub fn open_default(path: &str) -> Result<Database> {
+		Database::open(&DatabaseConfig::default(), path)
+	}
+
+	/// Open database file. Creates if it does not exist.
+	pub fn open(config: &DatabaseConfig, path: &str) -> Result<Database> {
+		fs::create_dir_all(path)?;
+
+		let num_tables = config.columns.unwrap_or(0) as usize + 1;
+		let env = Environment::new()
+			.set_max_dbs(num_tables as u32)
+			.set_map_size(config.map_size)
+			.open(Path::new(path))
+			.map_err(|e| e.to_string())?;
+
+		let tables = (0..num_tables)
+			.map(|c| env.create_db(Some(table_name(c).as_str()), DatabaseFlags::empty()))
+			.collect::<::std::result::Result<Vec<_>, _>>()
+			.map_err(|e| e.to_string())?;
+
+		Ok(Database {
+			env: RwLock::new(Some(EnvAndColumns { env: env, tables: tables })),
+			config: config.clone(),
+			overlay: RwLock::new((0..num_tables).map(|_| HashMap::new()).collect()),
+			flushing: RwLock::new((0..num_tables).map(|_| HashMap::new()).collect()),
+			flushing_lock: Mutex::new(false),
+			path: path.to_owned(),
+		})
+	}
+
+	/// Helper to create new transaction for this database.
+	pub fn transaction(&self) -> DBTransaction {
+		DBTransaction::new()
+	}
+
+	fn to_overlay_column(col: Option<u32>) -> usize {
+		col.map_or(0, |c| (c + 1) as usize)
+	}
+
+	/// Commit transaction to database.
+	pub fn write_buffered(&self, tr: DBTransaction) {
+		let mut overlay = self.overlay.write();
+		let ops = tr.ops;
+		for op in ops {
+			match op {
+				DBOp::Insert { col, key, value } => {
+					let c = Self::to_overlay_column(col);
+					overlay[c].insert(key, KeyState::Insert(value));
+				},
+				DBOp::Delete { col, key } => {
+					let c = Self::to_overlay_column(col);
+					overlay[c].insert(key, KeyState::Delete);
+				},
+			}
+		};
+	}
+
+	/// Commit buffered changes to database. Must be called under `flush_lock`
+	fn write_flushing_with_lock(&self, _lock: &mut MutexGuard<bool>) -> Result<()> {
+		match *self.env.read() {
+			Some(EnvAndColumns { ref env, ref tables }) => {
+				mem::swap(&mut *self.overlay.write(), &mut *self.flushing.write());
+				{
+					let mut txn = env.begin_rw_txn().map_err(|e| e.to_string())?;
+					for (c, column) in self.flushing.read().iter().enumerate() {
+						for (key, state) in column.iter() {
+							match *state {
+								KeyState::Delete => delete(&mut txn, tables[c], key)?,
+								KeyState::Insert(ref value) => {
+									txn.put(tables[c], &key[..], &value[..], WriteFlags::empty()).map_err(|e| e.to_string())?;
+								},
+							}
+						}
+					}
+					txn.commit().map_err(|e| e.to_string())?;
+				}
+
+				for column in self.flushing.write().iter_mut() {
+					column.clear();
+					column.shrink_to_fit();
+				}
+				Ok(())
+			},
+			None => Err("Database is closed".into())
+		}
+	}
+
+	/// Commit buffered changes to database.
+	pub fn flush(&self) -> Result<()> {
+		let mut lock = self.flushing_lock.lock();
+		// If the write transaction fails to allocate the thread gets terminated and the lock is released.
+		// The value inside the lock is used to detect that.
+		if *lock {
+			// This can only happen if another flushing thread is terminated unexpectedly.
+			return Err("Database write failure. Running low on memory perhaps?".into());
+		}
+		*lock = true;
+		let result = self.write_flushing_with_lock(&mut lock);
+		*lock = false;
+		result
+	}
+
+	/// Commit transaction to database.
+	pub fn write(&self, tr: DBTransaction) -> Result<()> {
+		match *self.env.read() {
+			Some(EnvAndColumns { ref env, ref tables }) => {
+				let mut txn = env.begin_rw_txn().map_err(|e| e.to_string())?;
+				let ops = tr.ops;
+				for op in ops {
+					// remove any buffered operation for this key
+					let c = Self::to_overlay_column(op.col());
+					self.overlay.write()[c].remove(op.key());
+
+					match op {
+						DBOp::Insert { key, value, .. } => {
+							txn.put(tables[c], &key[..], &value[..], WriteFlags::empty()).map_err(|e| e.to_string())?
+						},
+						DBOp::Delete { key, .. } => delete(&mut txn, tables[c], &key)?,
+					}
+				}
+
+				txn.commit().map_err(|e| e.to_string().into())
+			},
+			None => Err("Database is closed".into())
+		}
+	}
+
+	/// Get value by key.
+	pub fn get(&self, col: Option<u32>, key: &[u8]) -> Result<Option<DBValue>> {
+		match *self.env.read() {
+			Some(EnvAndColumns { ref env, ref tables }) => {
+				let c = Self::to_overlay_column(col);
+				let overlay = &self.overlay.read()[c];
+				match overlay.get(key) {
+					Some(&KeyState::Insert(ref value)) => Ok(Some(value.clone())),
+					Some(&KeyState::Delete) => Ok(None),
+					None => {
+						let flushing = &self.flushing.read()[c];
+						match flushing.get(key) {
+							Some(&KeyState::Insert(ref value)) => Ok(Some(value.clone())),
+							Some(&KeyState::Delete) => Ok(None),
+							None => {
+								let txn = env.begin_ro_txn().map_err(|e| e.to_string())?;
+								let value = match txn.get(tables[c], &key) {
+									Ok(value) => Ok(Some(DBValue::from_slice(value))),
+									Err(lmdb::Error::NotFound) => Ok(None),
+									Err(e) => Err(e.to_string().into()),
+								};
+								value
+							},
+						}
+					},
+				}
+			},
+			None => Ok(None),
+		}
+	}
+
+	/// Get value by partial key. Prefix size should match configured prefix size. Only searches flushed values.
+	// TODO: support prefix seek for unflushed data
+	pub fn get_by_prefix(&self, col: Option<u32>, prefix: &[u8]) -> Option<Box<[u8]>> {
+		self.iter_from_prefix(col, prefix).and_then(|mut iter| {
+			match iter.next() {
+				Some((k, v)) => if k.starts_with(prefix) { Some(v) } else { None },
+				_ => None
+			}
+		})
+	}
+
+	// read up to `ITER_PAGE_SIZE` flushed entries, starting at `from`.
+	fn read_page(&self, col: Option<u32>, from: &[u8], skip_from: bool) -> Result<Vec<(Box<[u8]>, Box<[u8]>)>> {
+		match *self.env.read() {
+			Some(EnvAndColumns { ref env, ref tables }) => {
+				let txn = env.begin_ro_txn().map_err(|e| e.to_string())?;
+				let mut cursor = txn.open_ro_cursor(tables[Self::to_overlay_column(col)]).map_err(|e| e.to_string())?;
+				let iter = match from.is_empty() {
+					true => cursor.iter_start(),
+					false => cursor.iter_from(from),
+				};
+
+				let page = iter
+					.filter(|&(k, _)| !(skip_from && k == from))
+					.take(ITER_PAGE_SIZE)
+					.map(|(k, v)| (k.to_vec().into_boxed_slice(), v.to_vec().into_boxed_slice()))
+					.collect();
+				Ok(page)
+			},
+			None => Ok(Vec::new()),
+		}
+	}
+
+	fn paged_iter(&self, col: Option<u32>, from: &[u8]) -> PagedIterator {
+		PagedIterator {
+			db: self,
+			col: col,
+			start: from.to_vec(),
+			last: None,
+			page: VecDeque::new(),
+			done: false,
+		}
+	}
+
+	/// Get database iterator for flushed data.
+	pub fn iter(&self, col: Option<u32>) -> Option<DatabaseIterator> {
+		if self.env.read().is_none() {
+			return None;
+		}
+
+		let overlay = &self.overlay.read()[Self::to_overlay_column(col)];
+		let mut overlay_data = overlay.iter()
+			.filter_map(|(k, v)| match *v {
+				KeyState::Insert(ref value) =>
+					Some((k.clone().into_vec().into_boxed_slice(), value.clone().into_vec().into_boxed_slice())),
+				KeyState::Delete => None,
+			}).collect::<Vec<_>>();
+		overlay_data.sort();
+
+		Some(DatabaseIterator {
+			iter: interleave_ordered(overlay_data, self.paged_iter(col, &[])),
+		})
+	}
+
+	fn iter_from_prefix(&self, col: Option<u32>, prefix: &[u8]) -> Option<DatabaseIterator> {
+		if self.env.read().is_none() {
+			return None;
+		}
+
+		Some(DatabaseIterator {
+			iter: interleave_ordered(Vec::new(), self.paged_iter(col, prefix)),
+		})
+	}
+
+	/// Close the database
+	fn close(&self) {
+		*self.env.write() = None;
+		self.overlay.write().clear();
+		self.flushing.write().clear();
+	}
+
+	/// Restore the database from a copy at given path.
+	pub fn restore(&self, new_db: &str) -> Result<()> {
+		self.close();
+
+		let mut backup_db = PathBuf::from(&self.path);
+		backup_db.pop();
+		backup_db.push("backup_db");
+
+		let existed = match fs::rename(&self.path, &backup_db) {
+			Ok(_) => true,
+			Err(e) => if let io::ErrorKind::NotFound = e.kind() {
+				false
+			} else {
+				return Err(e.into());
+			}
+		};
+
+		match fs::rename(&new_db, &self.path) {
+			Ok(_) => {
+				// clean up the backup.
+				if existed {
+					fs::remove_dir_all(&backup_db)?;
+				}
+			}
+			Err(e) => {
+				// restore the backup.
+				if existed {
+					fs::rename(&backup_db, &self.path)?;
+				}
+				return Err(e.into())
+			}
+		}
+
+		// reopen the database and steal handles into self
+		let db = Self::open(&self.config, &self.path)?;
+		*self.env.write() = mem::replace(&mut *db.env.write(), None);
+		*self.overlay.write() = mem::replace(&mut *db.overlay.write(), Vec::new());
+		*self.flushing.write() = mem::replace(&mut *db.flushing.write(), Vec::new());
+		Ok(())
+	}
+
+	/// The number of non-default column families.
+	pub fn num_columns(&self) -> u32 {
+		self.env.read().as_ref()
+			.map(|env| env.tables.len() as u32 - 1)
+			.unwrap_or(0)
+	}
+}
+
+// deleting a missing key is not an error for `KeyValueDB`.
+fn delete(txn: &mut lmdb::RwTransaction, table: Table, key: &[u8]) -> Result<()> {
+	match txn.del(table, &key, None) {
+		Ok(()) | Err(lmdb::Error::NotFound) => Ok(()),
+		Err(e) => Err(e.to_string().into()),
+	}
+}
+
+// duplicate declaration of methods here to avoid trait import in certain existing cases
+// at time of addition.
+impl KeyValueDB for Database {
+	fn get(&self, col: Option<u32>, key: &[u8]) -> Result<Option<DBValue>> {
+		Database::get(self, col, key)
+	}
+
+	fn get_by_prefix(&self, col: Option<u32>, prefix: &[u8]) -> Option<Box<[u8]>> {
+		Database::get_by_prefix(self, col, prefix)
+	}
+
+	fn write_buffered(&self, transaction: DBTransaction) {
+		Database::write_buffered(self, transaction)
+	}
+
+	fn write(&self, transaction: DBTransaction) -> Result<()> {
+		Database::write(self, transaction)
+	}
+
+	fn flush(&self) -> Result<()> {
+		Database::flush(self)
+	}
+
+	fn iter<'a>(&'a self, col: Option<u32>) -> Box<Iterator<Item=(Box<[u8]>, Box<[u8]>)> + 'a> {
+		let unboxed = Database::iter(self, col);
+		Box::new(unboxed.into_iter().flat_map(|inner| inner))
+	}
+
+	fn iter_from_prefix<'a>(&'a self, col: Option<u32>, prefix: &'a [u8])
+		-> Box<Iterator<Item=(Box<[u8]>, Box<[u8]>)> + 'a>
+	{
+		let unboxed = Database::iter_from_prefix(self, col, prefix);
+		Box::new(unboxed.into_iter().flat_map(|inner| inner))
+	}
+
+	fn restore(&self, new_db: &str) -> Result<()> {
+		Database::restore(self, new_db)
+	}
+}
+
+impl Drop for Database {
+	fn drop(&mut self) {
+		// write all buffered changes if we can.
+		let _ = self.flush();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	extern crate ethereum_types;
+	extern crate tempdir;
+
+	use self::tempdir::TempDir;
+	use self::ethereum_types::H256;
+	use super::*;
+
+	kvdb_tests!();
+
+	#[test]
+	fn iterates_across_pages() {
+		let tempdir = TempDir::new("").unwrap();
+		let db = Database::open_default(tempdir.path().to_str().unwrap()).unwrap();
+
+		let count = ITER_PAGE_SIZE * 2 + 10;
+		let mut batch = db.transaction();
+		for i in 0..count {
+			batch.put(None, &H256::from(i as u64), b"value");
+		}
+		db.write(batch).unwrap();
+
+		let keys: Vec<_> = db.iter(None).unwrap().map(|(k, _)| k).collect();
+		assert_eq!(keys.len(), count);
+		assert!(keys.windows(2).all(|w| w[0] < w[1]));
+
+		let from = H256::from(5u64);
+		let first = db.iter_from_prefix(None, &from).unwrap().next().unwrap();
+		assert_eq!(&*first.0, &*from);
+		assert_eq!(db.iter_from_prefix(None, &from).unwrap().count(), count - 5);
+	}
+}
@@ -25,6 +25,7 @@ extern crate regex;
 extern crate rocksdb;
 
 extern crate ethereum_types;
+#[macro_use]
 extern crate kvdb;
 
 use std::cmp;
@@ -716,70 +717,11 @@ impl Drop for Database {
 mod tests {
 	extern crate tempdir;
 
-	use std::str::FromStr;
 	use self::tempdir::TempDir;
 	use ethereum_types::H256;
 	use super::*;
 
-	fn test_db(config: &DatabaseConfig) {
-		let tempdir = TempDir::new("").unwrap();
-		let db = Database::open(config, tempdir.path().to_str().unwrap()).unwrap();
-		let key1 = H256::from_str("02c69be41d0b7e40352fc85be1cd65eb03d40ef8427a0ca4596b1ead9a00e9fc").unwrap();
-		let key2 = H256::from_str("03c69be41d0b7e40352fc85be1cd65eb03d40ef8427a0ca4596b1ead9a00e9fc").unwrap();
-		let key3 = H256::from_str("01c69be41d0b7e40352fc85be1cd65eb03d40ef8427a0ca4596b1ead9a00e9fc").unwrap();
-
-		let mut batch = db.transaction();
-		batch.put(None, &key1, b"cat");
-		batch.put(None, &key2, b"dog");
-		db.write(batch).unwrap();
-
-		assert_eq!(&*db.get(None, &key1).unwrap().unwrap(), b"cat");
-
-		let contents: Vec<_> = db.iter(None).into_iter().flat_map(|inner| inner).collect();
-		assert_eq!(contents.len(), 2);
-		assert_eq!(&*contents[0].0, &*key1);
-		assert_eq!(&*contents[0].1, b"cat");
-		assert_eq!(&*contents[1].0, &*key2);
-		assert_eq!(&*contents[1].1, b"dog");
-
-		let mut batch = db.transaction();
-		batch.delete(None, &key1);
-		db.write(batch).unwrap();
-
-		assert!(db.get(None, &key1).unwrap().is_none());
-
-		let mut batch = db.transaction();
-		batch.put(None, &key1, b"cat");
-		db.write(batch).unwrap();
-
-		let mut transaction = db.transaction();
-		transaction.put(None, &key3, b"elephant");
-		transaction.delete(None, &key1);
-		db.write(transaction).unwrap();
-		assert!(db.get(None, &key1).unwrap().is_none());
-		assert_eq!(&*db.get(None, &key3).unwrap().unwrap(), b"elephant");
-
-		assert_eq!(&*db.get_by_prefix(None, &key3).unwrap(), b"elephant");
-		assert_eq!(&*db.get_by_prefix(None, &key2).unwrap(), b"dog");
-
-		let mut transaction = db.transaction();
-		transaction.put(None, &key1, b"horse");
-		transaction.delete(None, &key3);
-		db.write_buffered(transaction);
-		assert!(db.get(None, &key3).unwrap().is_none());
-		assert_eq!(&*db.get(None, &key1).unwrap().unwrap(), b"horse");
-
-		db.flush().unwrap();
-		assert!(db.get(None, &key3).unwrap().is_none());
-		assert_eq!(&*db.get(None, &key1).unwrap().unwrap(), b"horse");
-	}
-
-	#[test]
-	fn kvdb() {
-		let tempdir = TempDir::new("").unwrap();
-		let _ = Database::open_default(tempdir.path().to_str().unwrap()).unwrap();
-		test_db(&DatabaseConfig::default());
-	}
+	kvdb_tests!();
 
 	#[test]
 	#[cfg(target_os = "linux")]
@@ -840,21 +782,4 @@ mod tests {
 			assert_eq!(db.num_columns(), 0);
 		}
 	}
-
-	#[test]
-	fn write_clears_buffered_ops() {
-		let tempdir = TempDir::new("").unwrap();
-		let config = DatabaseConfig::default();
-		let db = Database::open(&config, tempdir.path().to_str().unwrap()).unwrap();
-
-		let mut batch = db.transaction();
-		batch.put(None, b"foo", b"bar");
-		db.write_buffered(batch);
-
-		let mut batch = db.transaction();
-		batch.put(None, b"foo", b"baz");
-		db.write(batch).unwrap();
-
-		assert_eq!(db.get(None, b"foo").unwrap().unwrap().as_ref(), b"baz");
-	}
 }
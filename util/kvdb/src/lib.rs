@@ -185,3 +185,133 @@ pub trait KeyValueDBHandler: Send + Sync {
 	/// Open the predefined key-value database.
 	fn open(&self, path: &Path) -> Result<Arc<KeyValueDB>>;
 }
+
+/// Generates the test cases shared by all on-disk `KeyValueDB` backends.
+///
+/// Expects `Database`, `DatabaseConfig`, `TempDir` and `H256` to be in scope at the
+/// invocation site, which should be the backend's `tests` module.
+#[macro_export]
+macro_rules! kvdb_tests {
+	() => {
+		fn test_db(config: &DatabaseConfig) {
+			use std::str::FromStr;
+
+			let tempdir = TempDir::new("").unwrap();
+			let db = Database::open(config, tempdir.path().to_str().unwrap()).unwrap();
+			let key1 = H256::from_str("02c69be41d0b7e40352fc85be1cd65eb03d40ef8427a0ca4596b1ead9a00e9fc").unwrap();
+			let key2 = H256::from_str("03c69be41d0b7e40352fc85be1cd65eb03d40ef8427a0ca4596b1ead9a00e9fc").unwrap();
+			let key3 = H256::from_str("01c69be41d0b7e40352fc85be1cd65eb03d40ef8427a0ca4596b1ead9a00e9fc").unwrap();
+
+			let mut batch = db.transaction();
+			batch.put(None, &key1, b"cat");
+			batch.put(None, &key2, b"dog");
+			db.write(batch).unwrap();
+
+			assert_eq!(&*db.get(None, &key1).unwrap().unwrap(), b"cat");
+
+			let contents: Vec<_> = db.iter(None).into_iter().flat_map(|inner| inner).collect();
+			assert_eq!(contents.len(), 2);
+			assert_eq!(&*contents[0].0, &*key1);
+			assert_eq!(&*contents[0].1, b"cat");
+			assert_eq!(&*contents[1].0, &*key2);
+			assert_eq!(&*contents[1].1, b"dog");
+
+			let mut batch = db.transaction();
+			batch.delete(None, &key1);
+			db.write(batch).unwrap();
+
+			assert!(db.get(None, &key1).unwrap().is_none());
+
+			let mut batch = db.transaction();
+			batch.put(None, &key1, b"cat");
+			db.write(batch).unwrap();
+
+			let mut transaction = db.transaction();
+			transaction.put(None, &key3, b"elephant");
+			transaction.delete(None, &key1);
+			db.write(transaction).unwrap();
+			assert!(db.get(None, &key1).unwrap().is_none());
+			assert_eq!(&*db.get(None, &key3).unwrap().unwrap(), b"elephant");
+
+			assert_eq!(&*db.get_by_prefix(None, &key3).unwrap(), b"elephant");
+			assert_eq!(&*db.get_by_prefix(None, &key2).unwrap(), b"dog");
+
+			let mut transaction = db.transaction();
+			transaction.put(None, &key1, b"horse");
+			transaction.delete(None, &key3);
+			db.write_buffered(transaction);
+			assert!(db.get(None, &key3).unwrap().is_none());
+			assert_eq!(&*db.get(None, &key1).unwrap().unwrap(), b"horse");
+
+			db.flush().unwrap();
+			assert!(db.get(None, &key3).unwrap().is_none());
+			assert_eq!(&*db.get(None, &key1).unwrap().unwrap(), b"horse");
+		}
+
+		#[test]
+		fn kvdb() {
+			let tempdir = TempDir::new("").unwrap();
+			let _ = Database::open_default(tempdir.path().to_str().unwrap()).unwrap();
+			test_db(&DatabaseConfig::default());
+		}
+
+		#[test]
+		fn columns_are_distinct() {
+			let tempdir = TempDir::new("").unwrap();
+			let config = DatabaseConfig::with_columns(Some(2));
+			let db = Database::open(&config, tempdir.path().to_str().unwrap()).unwrap();
+			assert_eq!(db.num_columns(), 2);
+
+			let mut batch = db.transaction();
+			batch.put(None, b"key", b"none");
+			batch.put(Some(0), b"key", b"zero");
+			batch.put(Some(1), b"key", b"one");
+			db.write(batch).unwrap();
+
+			assert_eq!(&*db.get(None, b"key").unwrap().unwrap(), b"none");
+			assert_eq!(&*db.get(Some(0), b"key").unwrap().unwrap(), b"zero");
+			assert_eq!(&*db.get(Some(1), b"key").unwrap().unwrap(), b"one");
+			assert_eq!(db.iter(Some(1)).unwrap().count(), 1);
+		}
+
+		#[test]
+		fn restore_replaces_database() {
+			let tempdir = TempDir::new("").unwrap();
+			let path = tempdir.path().join("db");
+			let new_path = tempdir.path().join("new_db");
+
+			let db = Database::open_default(path.to_str().unwrap()).unwrap();
+			let mut batch = db.transaction();
+			batch.put(None, b"foo", b"old");
+			db.write(batch).unwrap();
+
+			{
+				let new_db = Database::open_default(new_path.to_str().unwrap()).unwrap();
+				let mut batch = new_db.transaction();
+				batch.put(None, b"bar", b"new");
+				new_db.write(batch).unwrap();
+			}
+
+			db.restore(new_path.to_str().unwrap()).unwrap();
+			assert!(db.get(None, b"foo").unwrap().is_none());
+			assert_eq!(&*db.get(None, b"bar").unwrap().unwrap(), b"new");
+		}
+
+		#[test]
+		fn write_clears_buffered_ops() {
+			let tempdir = TempDir::new("").unwrap();
+			let config = DatabaseConfig::default();
+			let db = Database::open(&config, tempdir.path().to_str().unwrap()).unwrap();
+
+			let mut batch = db.transaction();
+			batch.put(None, b"foo", b"bar");
+			db.write_buffered(batch);
+
+			let mut batch = db.transaction();
+			batch.put(None, b"foo", b"baz");
+			db.write(batch).unwrap();
+
+			assert_eq!(db.get(None, b"foo").unwrap().unwrap().as_ref(), b"baz");
+		}
+	}
+}